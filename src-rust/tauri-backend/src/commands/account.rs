@@ -0,0 +1,90 @@
+use fosscopetoolkit_core::config::github::{DeviceCode, DeviceFlow, ForgeKind, GitHubAccount};
+use fosscopetoolkit_core::models::{RateLimit, TokenCapabilities};
+use fosscopetoolkit_core::Error;
+
+use crate::state::{AppState, Session};
+
+/// Sign in to a forge with a username and a personal access token.
+///
+/// `forge` is the [kind of forge](ForgeKind) to sign in to, GitHub if `None`.
+/// `base_url` is the base URL of the GitHub API, e.g. of a GitHub Enterprise Server, or `None` for GitHub.com,
+/// and the URL of the instance for Gitea and Forgejo, where it is required.
+/// `graphql_url` and `upload_url` are the URLs of the GraphQL and uploads APIs of GitHub, derived from `base_url` if `None`.
+#[tauri::command]
+pub async fn login(
+    state: tauri::State<'_, AppState>,
+    forge: Option<ForgeKind>,
+    username: String,
+    token: String,
+    base_url: Option<String>,
+    graphql_url: Option<String>,
+    upload_url: Option<String>,
+) -> Result<(), Error> {
+    let mut account = GitHubAccount::new(username, token);
+    account.forge = forge.unwrap_or_default();
+    account.base_url = base_url;
+    account.graphql_url = graphql_url;
+    account.upload_url = upload_url;
+    let session = match account.forge {
+        ForgeKind::GitHub => Session::GitHub(account.login().await?),
+        ForgeKind::Gitea => Session::Gitea(account.login_gitea().await?),
+    };
+    *state.session.lock().await = Some(session);
+    Ok(())
+}
+
+/// Start signing in to GitHub with the OAuth device flow.
+///
+/// The returned code has to be shown to the user, who enters it at its verification URI,
+/// before calling [`finish_device_login`](finish_device_login) with it and the same `base_url`.
+///
+/// `base_url` is the base URL of the GitHub API, whose instance issues the code, or `None` for GitHub.com.
+#[tauri::command]
+pub async fn start_device_login(base_url: Option<String>) -> Result<DeviceCode, Error> {
+    DeviceFlow::from_env(base_url.as_deref())?.request_code().await
+}
+
+/// Wait for the user to authorize the device code, and sign in to GitHub with the obtained token.
+///
+/// Returns the username of the signed in user.
+#[tauri::command]
+pub async fn finish_device_login(
+    state: tauri::State<'_, AppState>,
+    code: DeviceCode,
+    base_url: Option<String>,
+    graphql_url: Option<String>,
+    upload_url: Option<String>,
+) -> Result<String, Error> {
+    let token = DeviceFlow::from_env(base_url.as_deref())?.poll_token(&code).await?;
+    let mut account = GitHubAccount::new(String::new(), token);
+    account.base_url = base_url;
+    account.graphql_url = graphql_url;
+    account.upload_url = upload_url;
+    let (account, github) = account.login_with_token().await?;
+    *state.session.lock().await = Some(Session::GitHub(github));
+    Ok(account.username)
+}
+
+/// Get the GitHub API quota of the signed in user, refreshed from GitHub.
+#[tauri::command]
+pub async fn rate_limit(state: tauri::State<'_, AppState>) -> Result<RateLimit, Error> {
+    let session = state.session().await?;
+    match &*session {
+        Session::GitHub(github) => github.fetch_rate_limit().await,
+        Session::Gitea(_) => Err(Error::Forge("Gitea and Forgejo do not report the API quota.".to_string())),
+        Session::Sandbox(..) => Err(Error::Forge("The sandbox has no API quota.".to_string())),
+    }
+}
+
+/// Get the capabilities of the token of the signed in user checked at login,
+/// to warn about the ones it lacks that did not prevent the login.
+///
+/// Returns `None` on Gitea and Forgejo, whose tokens are not checked, and in the sandbox, where every capability is granted.
+#[tauri::command]
+pub async fn token_capabilities(state: tauri::State<'_, AppState>) -> Result<Option<TokenCapabilities>, Error> {
+    let session = state.session().await?;
+    match &*session {
+        Session::GitHub(github) => Ok(github.token_capabilities()),
+        Session::Gitea(_) | Session::Sandbox(..) => Ok(None),
+    }
+}
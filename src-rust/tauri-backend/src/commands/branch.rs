@@ -0,0 +1,38 @@
+use fosscopetoolkit_core::models::GitHubRepo;
+use fosscopetoolkit_core::models::repo_rule::get_repo_rule;
+use fosscopetoolkit_core::workflow::branches::{self, ContributionBranch};
+use fosscopetoolkit_core::Error;
+
+use crate::commands::fork::contributor_repo;
+use crate::state::{with_forge, AppState};
+
+/// List the contribution branches of the contributor repository, with the pull requests opened from them.
+#[tauri::command]
+pub async fn list_contribution_branches(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+) -> Result<Vec<ContributionBranch>, Error> {
+    let session = state.session().await?;
+    let contributor = contributor_repo(&session, &upstream)?;
+    with_forge!(&*session, forge => {
+        let repo_rule = get_repo_rule(&upstream, forge).await?;
+        branches::list_contribution_branches(forge, &upstream, &contributor, &repo_rule).await
+    })
+}
+
+/// Delete the contribution branches whose pull requests have been merged or closed.
+///
+/// Returns the deleted branches, or only lists the branches that would be deleted if `dry_run` is `true`.
+#[tauri::command]
+pub async fn clean_up_branches(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+    dry_run: bool,
+) -> Result<Vec<ContributionBranch>, Error> {
+    let session = state.session().await?;
+    let contributor = contributor_repo(&session, &upstream)?;
+    with_forge!(&*session, forge => {
+        let repo_rule = get_repo_rule(&upstream, forge).await?;
+        branches::clean_up_branches(forge, &upstream, &contributor, &repo_rule, dry_run).await
+    })
+}
@@ -0,0 +1,113 @@
+use fosscopetoolkit_core::apis::Forge;
+use fosscopetoolkit_core::get_contributor_repo;
+use fosscopetoolkit_core::models::{ForkStatus, GitHubRepo};
+use fosscopetoolkit_core::models::repo_rule::get_repo_rule;
+use fosscopetoolkit_core::Error;
+
+use crate::state::{with_forge, AppState, Session};
+
+/// Get the stored contributor repository of an upstream repository in the runtime storage directory of the session.
+pub(crate) fn contributor_repo(session: &Session, upstream: &GitHubRepo) -> Result<GitHubRepo, Error> {
+    get_contributor_repo(session.workspace(), upstream)?.ok_or_else(|| Error::NotFound(format!(
+        "contributor repository of {}", upstream.get_full_name()
+    )))
+}
+
+/// Compare the contributor repository with the base branch of the upstream repository.
+#[tauri::command]
+pub async fn fork_status(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+    base: String,
+) -> Result<ForkStatus, Error> {
+    let session = state.session().await?;
+    let contributor = contributor_repo(&session, &upstream)?;
+    with_forge!(&*session, forge => forge.fork_status(&contributor, &upstream, &base).await)
+}
+
+/// Synchronize the contributor repository with the base branch of the upstream repository.
+///
+/// Fails with a `ForkDiverged` error if the fork has diverged and cannot be synchronized automatically.
+#[tauri::command]
+pub async fn sync_fork(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+    base: String,
+) -> Result<(), Error> {
+    let session = state.session().await?;
+    let contributor = contributor_repo(&session, &upstream)?;
+    with_forge!(&*session, forge => forge.sync_fork(&contributor, &base).await)
+}
+
+/// List the forks of the upstream repository owned by the user or by an organization the user is a member of,
+/// so the user can choose the contributor repository.
+#[tauri::command]
+pub async fn user_forks(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+) -> Result<Vec<GitHubRepo>, Error> {
+    let session = state.session().await?;
+    with_forge!(&*session, forge => forge.get_user_forks(&upstream).await)
+}
+
+/// Check that a repository is one of the forks of the user, and store it as the contributor repository of the upstream repository.
+async fn store_contributor_repo(session: &Session, upstream: &GitHubRepo, repo: &GitHubRepo) -> Result<GitHubRepo, Error> {
+    let forks = with_forge!(session, forge => forge.get_user_forks(upstream).await)?;
+    let full_name = repo.get_full_name();
+    let fork = forks.into_iter()
+        .find(|fork| fork.get_full_name().eq_ignore_ascii_case(&full_name))
+        .ok_or_else(|| Error::InvalidInput(format!("{} is not one of your forks of {}", full_name, upstream.get_full_name())))?;
+    fosscopetoolkit_core::set_contributor_repo(session.workspace(), fork.clone(), upstream)?;
+    Ok(fork)
+}
+
+/// Use one of the forks listed by [`user_forks`](user_forks) as the contributor repository of the upstream repository,
+/// storing it in the runtime storage directory of the session.
+///
+/// Fails with an `InvalidInput` error if the repository is not one of the forks of the user.
+#[tauri::command]
+pub async fn set_contributor_repo(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+    repo: GitHubRepo,
+) -> Result<(), Error> {
+    let session = state.session().await?;
+    store_contributor_repo(&session, &upstream, &repo).await?;
+    Ok(())
+}
+
+/// The contributor repository of an upstream repository, with how it compares to the base branch of the upstream repository.
+///
+/// # Fields
+/// - `repo`: The contributor repository.
+/// - `base`: The base branch of the upstream repository, as the repository rule defines it.
+/// - `status`: The [ForkStatus](ForkStatus) of the contributor repository, to offer synchronizing it if it is behind.
+#[derive(serde::Serialize)]
+pub struct ContributorRepo {
+    pub repo: GitHubRepo,
+    pub base: String,
+    pub status: ForkStatus,
+}
+
+/// Resolve the contributor repository of the upstream repository, or store `repo` as it if given,
+/// and compare it with the base branch of the upstream repository, so a sync can be offered right away.
+///
+/// Fails with a `NotFound` error if no contributor repository is stored and `repo` is `None`,
+/// in which case the user has to choose one of the [`user_forks`](user_forks) first.
+#[tauri::command]
+pub async fn contributor_repo_status(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+    repo: Option<GitHubRepo>,
+) -> Result<ContributorRepo, Error> {
+    let session = state.session().await?;
+    let repo = match repo {
+        Some(repo) => store_contributor_repo(&session, &upstream, &repo).await?,
+        None => contributor_repo(&session, &upstream)?,
+    };
+    with_forge!(&*session, forge => {
+        let base = get_repo_rule(&upstream, forge).await?.git.base;
+        let status = forge.fork_status(&repo, &upstream, &base).await?;
+        Ok(ContributorRepo { repo, base, status })
+    })
+}
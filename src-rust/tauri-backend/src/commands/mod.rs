@@ -0,0 +1,6 @@
+pub mod account;
+pub mod branch;
+pub mod fork;
+pub mod pull_request;
+pub mod repo_rule;
+pub mod workflow;
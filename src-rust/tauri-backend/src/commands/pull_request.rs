@@ -0,0 +1,64 @@
+use fosscopetoolkit_core::apis::Forge;
+use fosscopetoolkit_core::models::{GitHubRepo, PullRequest, PullRequestStatus};
+use fosscopetoolkit_core::Error;
+
+use crate::state::{with_forge, AppState, Session};
+
+/// List the open pull requests of the logged in user against the upstream repository.
+#[tauri::command]
+pub async fn list_pull_requests(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+) -> Result<Vec<PullRequest>, Error> {
+    let session = state.session().await?;
+    with_forge!(&*session, forge => forge.list_user_pull_requests(&upstream).await)
+}
+
+/// Get the reviews, mergeability and checks of a pull request.
+#[tauri::command]
+pub async fn pull_request_status(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+    number: u64,
+) -> Result<PullRequestStatus, Error> {
+    let session = state.session().await?;
+    with_forge!(&*session, forge => forge.pull_request_status(&upstream, number).await)
+}
+
+/// Close a pull request without merging it.
+#[tauri::command]
+pub async fn close_pull_request(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+    number: u64,
+) -> Result<PullRequest, Error> {
+    let session = state.session().await?;
+    with_forge!(&*session, forge => forge.close_pull_request(&upstream, number).await)
+}
+
+/// Mark a draft pull request as ready for review.
+#[tauri::command]
+pub async fn mark_ready_for_review(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+    pull_request: PullRequest,
+) -> Result<(), Error> {
+    let session = state.session().await?;
+    with_forge!(&*session, forge => Forge::mark_ready_for_review(forge, &upstream, &pull_request).await)
+}
+
+/// Merge a pull request in the sandbox, as a maintainer would, so the next steps of the workflow can be rehearsed.
+///
+/// Returns the SHA of the merge commit. Pull requests can only be merged in the sandbox.
+#[tauri::command]
+pub async fn merge_sandbox_pull_request(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+    number: u64,
+) -> Result<String, Error> {
+    let session = state.session().await?;
+    match &*session {
+        Session::Sandbox(sandbox, _) => sandbox.merge_pull_request(&upstream, number),
+        Session::GitHub(_) | Session::Gitea(_) => Err(Error::Forge("Pull requests can only be merged in the sandbox.".to_string())),
+    }
+}
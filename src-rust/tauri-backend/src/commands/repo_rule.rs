@@ -0,0 +1,28 @@
+use fosscopetoolkit_core::models::GitHubRepo;
+use fosscopetoolkit_core::models::repo_rule::{get_repo_rule, Variable};
+use fosscopetoolkit_core::Error;
+
+use crate::state::{with_forge, AppState};
+
+/// Get the variables the repository rule of the upstream repository declares for an action,
+/// so the form asking for them can be built.
+#[tauri::command]
+pub async fn action_variables(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+    action: String,
+) -> Result<Vec<Variable>, Error> {
+    let session = state.session().await?;
+    with_forge!(&*session, forge => {
+        let repo_rule = get_repo_rule(&upstream, forge).await?;
+        Ok(repo_rule.variables_of(&action).into_iter().cloned().collect())
+    })
+}
+
+/// Validate the value given to a variable, returning the normalized value.
+///
+/// Fails with an `InvalidInput` error describing why the value is not valid.
+#[tauri::command]
+pub fn check_variable(variable: Variable, input: String) -> Result<String, Error> {
+    variable.parse(&input)
+}
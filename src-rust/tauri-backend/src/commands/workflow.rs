@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use fosscopetoolkit_core::apis::Forge;
+use fosscopetoolkit_core::models::{GitHubRepo, PullRequest};
+use fosscopetoolkit_core::models::repo_rule::{get_repo_rule, Article, RepoRule};
+use fosscopetoolkit_core::workflow;
+use fosscopetoolkit_core::workflow::action::StepArticle;
+use fosscopetoolkit_core::workflow::translate::select::{ArticleIndex, ExistingSelection};
+use fosscopetoolkit_core::Error;
+
+use crate::commands::fork::contributor_repo;
+use crate::state::{with_forge, AppState};
+
+/// Borrow the variables given by the frontend as the template variables of the core workflow.
+fn template_vars(vars: &HashMap<String, String>) -> HashMap<&str, String> {
+    vars.iter().map(|(name, value)| (name.as_str(), value.clone())).collect()
+}
+
+/// Find an article type of the repository rule by its type name.
+fn article_type<'a>(repo_rule: &'a RepoRule, name: &str) -> Result<&'a Article, Error> {
+    repo_rule.articles.iter()
+        .find(|article| article.r#type == name)
+        .ok_or_else(|| Error::NotFound(format!("article type {} in the repository rule", name)))
+}
+
+/// Find where an article has been selected before in the upstream repository, so it is not translated twice.
+///
+/// The index of the articles read is kept in the runtime storage directory of the session for the next search.
+#[tauri::command]
+pub async fn existing_selections(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+    via: String,
+) -> Result<Vec<ExistingSelection>, Error> {
+    let session = state.session().await?;
+    let workspace = session.workspace();
+    let mut index = ArticleIndex::load(workspace, &upstream).unwrap_or_default();
+    let selections = with_forge!(&*session, forge => {
+        let repo_rule = get_repo_rule(&upstream, forge).await?;
+        workflow::translate::select::find_existing_selections(forge, &upstream, &repo_rule, &via, &mut index).await
+    })?;
+    index.save(workspace, &upstream)?;
+    Ok(selections)
+}
+
+/// An article fetched to be selected.
+///
+/// # Fields
+/// - `title`: The title of the article.
+/// - `content`: The content of the article in Markdown, rendered with the article template of its type.
+/// - `article_id`: The ID generated for the article, the file name of the article without the `.md` extension.
+/// - `unchecked`: Why the ID could not be checked against the existing articles, to be shown as a warning.
+///   `None` if the ID was checked.
+/// - `vars`: The variables given, with the ones filled in by selecting the article (e.g. `selector`, `article_title`
+///   and `article_id`), to be passed to [`commit_article`](commit_article) and [`create_pull_request`](create_pull_request).
+#[derive(serde::Serialize)]
+pub struct FetchedArticle {
+    pub title: String,
+    pub content: String,
+    pub article_id: String,
+    pub unchecked: Option<String>,
+    pub vars: HashMap<String, String>,
+}
+
+/// Fetch an article to select it, and generate its ID as the repository rule of the upstream repository defines.
+///
+/// # Arguments
+/// - `article_type`: The type name of the article. e.g. `news`.
+/// - `vars`: The variables of the `select` action, which must contain the URL of the article (`via`).
+#[tauri::command]
+pub async fn fetch_article(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+    article_type: String,
+    mut vars: HashMap<String, String>,
+) -> Result<FetchedArticle, Error> {
+    let session = state.session().await?;
+    let contributor = contributor_repo(&session, &upstream)?;
+    with_forge!(&*session, forge => {
+        let repo_rule = get_repo_rule(&upstream, forge).await?;
+        let article_type = self::article_type(&repo_rule, &article_type)?;
+        vars.insert("selector".to_string(), forge.get_user().await?);
+        vars.insert("type_name".to_string(), article_type.r#type.clone());
+        vars.insert("type_desc".to_string(), article_type.description.clone());
+
+        let (content, title) = workflow::translate::select::fetch(&repo_rule, article_type, &template_vars(&vars)).await?;
+        vars.insert("article_title".to_string(), title.clone());
+
+        let generated = workflow::article_id::generate(
+            forge, &upstream, &contributor, article_type, &repo_rule, &title, &template_vars(&vars),
+        ).await?;
+        vars.insert("article_id".to_string(), generated.id.clone());
+
+        Ok(FetchedArticle {
+            title,
+            content,
+            article_id: generated.id,
+            unchecked: generated.unchecked.map(|e| e.to_string()),
+            vars,
+        })
+    })
+}
+
+/// List the articles in the source step directories of an action on the base branch of the upstream repository,
+/// for the user to choose the one to perform the action on.
+#[tauri::command]
+pub async fn list_step_articles(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+    action: String,
+) -> Result<Vec<StepArticle>, Error> {
+    let session = state.session().await?;
+    with_forge!(&*session, forge => {
+        let repo_rule = get_repo_rule(&upstream, forge).await?;
+        workflow::action::list_step_articles(forge, &upstream, repo_rule.action(&action)?, &repo_rule).await
+    })
+}
+
+/// Read the content of an article listed by [`list_step_articles`](list_step_articles).
+#[tauri::command]
+pub async fn step_article_content(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+    article: StepArticle,
+) -> Result<String, Error> {
+    let session = state.session().await?;
+    let content = with_forge!(&*session, forge => forge.get_blob(&upstream, &article.sha).await)?;
+    String::from_utf8(content).map_err(|e| Error::parse(format!("the content of {}", article.path), e))
+}
+
+/// Commit an article to the contributor repository performing an action of the repository rule,
+/// e.g. [`SELECT`](workflow::translate::select::submit::SELECT) with an article fetched by [`fetch_article`](fetch_article).
+///
+/// # Arguments
+/// - `action`: The name of the action. e.g. `select`.
+/// - `article_type`: The type name of the article. e.g. `news`.
+/// - `file_name`: The file name of the article. e.g. `20240715-hello.md`.
+/// - `content`: The content of the article, as edited by the user.
+/// - `vars`: The variables of the action.
+#[tauri::command]
+pub async fn commit_article(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+    action: String,
+    article_type: String,
+    file_name: String,
+    content: String,
+    vars: HashMap<String, String>,
+) -> Result<(), Error> {
+    let session = state.session().await?;
+    let contributor = contributor_repo(&session, &upstream)?;
+    with_forge!(&*session, forge => {
+        let repo_rule = get_repo_rule(&upstream, forge).await?;
+        workflow::action::commit(
+            forge, &upstream, &contributor, repo_rule.action(&action)?, &file_name, &content,
+            self::article_type(&repo_rule, &article_type)?, &repo_rule, &template_vars(&vars),
+        ).await
+    })
+}
+
+/// Create the pull request of an action from the contributor repository to the upstream repository,
+/// after the article has been committed with [`commit_article`](commit_article).
+#[tauri::command]
+pub async fn create_pull_request(
+    state: tauri::State<'_, AppState>,
+    upstream: GitHubRepo,
+    action: String,
+    vars: HashMap<String, String>,
+) -> Result<PullRequest, Error> {
+    let session = state.session().await?;
+    let contributor = contributor_repo(&session, &upstream)?;
+    with_forge!(&*session, forge => {
+        let repo_rule = get_repo_rule(&upstream, forge).await?;
+        workflow::action::create_pr(forge, &upstream, &contributor, repo_rule.action(&action)?, &repo_rule, &template_vars(&vars)).await
+    })
+}
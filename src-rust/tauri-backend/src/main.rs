@@ -1,15 +1,66 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+use std::path::PathBuf;
+
+use fosscopetoolkit_core::{Error, SANDBOX_DIR};
+
+mod commands;
+mod state;
+
 // Learn more about Tauri commands at https://tauri.app/v1/guides/features/command
 #[tauri::command]
 fn greet(name: &str) -> String {
     "Hello ".to_string() + name
 }
 
+/// Create the state of the app, signed in to the sandbox if the app is started with `--sandbox`.
+fn app_state() -> Result<state::AppState, Error> {
+    if !std::env::args().skip(1).any(|arg| arg == "--sandbox") {
+        return Ok(state::AppState::default());
+    }
+    state::AppState::sandbox(PathBuf::from(SANDBOX_DIR))
+}
+
 fn main() {
+    let app_state = match app_state() {
+        Ok(app_state) => app_state,
+        Err(e) => {
+            eprintln!("Failed to open the sandbox: {}", e);
+            std::process::exit(1);
+        }
+    };
+
     tauri::Builder::default()
-        .invoke_handler(tauri::generate_handler![greet])
+        .manage(app_state)
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            commands::account::login,
+            commands::account::start_device_login,
+            commands::account::finish_device_login,
+            commands::account::rate_limit,
+            commands::account::token_capabilities,
+            commands::branch::list_contribution_branches,
+            commands::branch::clean_up_branches,
+            commands::fork::fork_status,
+            commands::fork::sync_fork,
+            commands::fork::user_forks,
+            commands::fork::set_contributor_repo,
+            commands::fork::contributor_repo_status,
+            commands::pull_request::list_pull_requests,
+            commands::pull_request::pull_request_status,
+            commands::pull_request::close_pull_request,
+            commands::pull_request::mark_ready_for_review,
+            commands::pull_request::merge_sandbox_pull_request,
+            commands::repo_rule::action_variables,
+            commands::repo_rule::check_variable,
+            commands::workflow::existing_selections,
+            commands::workflow::fetch_article,
+            commands::workflow::list_step_articles,
+            commands::workflow::step_article_content,
+            commands::workflow::commit_article,
+            commands::workflow::create_pull_request,
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -0,0 +1,86 @@
+use std::path::{Path, PathBuf};
+
+use fosscopetoolkit_core::apis::{Forge, GiteaApi, GitHubApi, SANDBOX_UPSTREAM_REPOS, SandboxForge};
+use fosscopetoolkit_core::models::GitHubRepo;
+use fosscopetoolkit_core::{get_contributor_repo, set_contributor_repo, Error, WORKSPACE_DIR};
+use tokio::sync::{MappedMutexGuard, Mutex, MutexGuard};
+
+/// The forge the signed in user works with.
+///
+/// # Variants
+/// - `GitHub`: The GitHub API wrapper of the signed in user.
+/// - `Gitea`: The API wrapper of the Gitea or Forgejo instance the user has signed in to.
+/// - `Sandbox`: The sandbox, in which every repository and pull request only exists on this computer,
+///   and the runtime storage directory it was started in.
+// There is only ever one session, so its size does not matter.
+#[allow(clippy::large_enum_variant)]
+pub enum Session {
+    GitHub(GitHubApi),
+    Gitea(GiteaApi),
+    Sandbox(SandboxForge, PathBuf),
+}
+
+impl Session {
+    /// Get the runtime storage directory of the session, kept apart in the sandbox so it never touches the real one.
+    pub fn workspace(&self) -> &Path {
+        match self {
+            Session::GitHub(_) | Session::Gitea(_) => Path::new(WORKSPACE_DIR),
+            Session::Sandbox(_, workspace) => workspace,
+        }
+    }
+}
+
+/// Run an expression with the [Forge](fosscopetoolkit_core::apis::Forge) of a [Session](Session), whichever it is.
+macro_rules! with_forge {
+    ($session:expr, $forge:ident => $body:expr) => {
+        match $session {
+            $crate::state::Session::GitHub($forge) => $body,
+            $crate::state::Session::Gitea($forge) => $body,
+            $crate::state::Session::Sandbox($forge, _) => $body,
+        }
+    };
+}
+pub(crate) use with_forge;
+
+/// The state shared between all Tauri commands.
+///
+/// # Fields
+/// - `session`: The [Session](Session) of the signed in user, or `None` if the user has not signed in yet.
+#[derive(Default)]
+pub struct AppState {
+    pub session: Mutex<Option<Session>>,
+}
+
+impl AppState {
+    /// Start the sandbox mode in a runtime storage directory, in which the user is signed in to the sandbox from the start.
+    ///
+    /// # Arguments
+    /// - `workspace`: The runtime storage directory of the sandbox mode, which the sandbox is kept in.
+    ///
+    /// The user's fork of each upstream repository of the sandbox is created, and used as the contributor repository
+    /// unless another one has been chosen, so the workflow can be rehearsed without choosing a fork first.
+    pub fn sandbox(workspace: PathBuf) -> Result<Self, Error> {
+        let sandbox = SandboxForge::start(&workspace)?;
+        for (owner, name) in SANDBOX_UPSTREAM_REPOS {
+            let upstream = GitHubRepo::new(owner.to_string(), name.to_string());
+            if get_contributor_repo(&workspace, &upstream)?.is_some() {
+                continue;
+            }
+            let fork = sandbox.fork_repo(&GitHubRepo::new(sandbox.username().to_string(), name.to_string()), &upstream)?;
+            set_contributor_repo(&workspace, fork, &upstream)?;
+        }
+        Ok(Self {
+            session: Mutex::new(Some(Session::Sandbox(sandbox, workspace))),
+        })
+    }
+
+    /// Get the session of the signed in user.
+    ///
+    /// # Returns
+    /// - `Result<MappedMutexGuard<Session>, Error>`: The locked session,
+    ///   or [`Error::Unauthorized`](Error::Unauthorized) if the user has not signed in yet.
+    pub async fn session(&self) -> Result<MappedMutexGuard<'_, Session>, Error> {
+        MutexGuard::try_map(self.session.lock().await, Option::as_mut)
+            .map_err(|_| Error::Unauthorized("The user has not signed in to GitHub.".to_string()))
+    }
+}
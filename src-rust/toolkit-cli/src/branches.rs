@@ -0,0 +1,71 @@
+use std::io::{stdin, stdout, Write};
+
+use fosscopetoolkit_core::apis::Forge;
+use fosscopetoolkit_core::models::{GitHubRepo, PullRequestState};
+use fosscopetoolkit_core::models::repo_rule::get_repo_rule;
+use fosscopetoolkit_core::workflow::branches;
+
+/// List the contribution branches of the contributor repository, and let the user delete those whose pull requests
+/// have been merged or closed.
+pub async fn clean_up_branches(github: &impl Forge, upstream_repo: &GitHubRepo, contributor_repo: &GitHubRepo) {
+    let repo_rule = match get_repo_rule(upstream_repo, github).await {
+        Ok(repo_rule) => repo_rule,
+        Err(e) => {
+            eprintln!("Failed to get repository rule: {}", e);
+            return;
+        }
+    };
+    let contribution_branches = match branches::list_contribution_branches(
+        github, upstream_repo, contributor_repo, &repo_rule,
+    ).await {
+        Ok(contribution_branches) => contribution_branches,
+        Err(e) => {
+            eprintln!("Failed to list the branches of your fork: {}", e);
+            return;
+        }
+    };
+    if contribution_branches.is_empty() {
+        println!("{} 中没有贡献分支。", contributor_repo.get_full_name());
+        return;
+    }
+
+    for branch in &contribution_branches {
+        let state = match &branch.pull_request {
+            None => "没有 Pull Request".to_string(),
+            Some(pr) => {
+                let state = match pr.state {
+                    PullRequestState::Open => "开放",
+                    PullRequestState::Closed => "已关闭",
+                    PullRequestState::Merged => "已合并",
+                };
+                format!("#{} {}", pr.number, state)
+            }
+        };
+        let action = if branch.is_deletable() {
+            "将被删除"
+        } else if branch.pull_request.as_ref().is_some_and(|pr| pr.state != PullRequestState::Open) {
+            "保留（Pull Request 关闭后有新的提交）"
+        } else {
+            "保留"
+        };
+        println!("{} ({})：{}", branch.name, state, action);
+    }
+
+    let deletable = contribution_branches.iter().filter(|branch| branch.is_deletable()).count();
+    if deletable == 0 {
+        println!("没有可以清理的分支。");
+        return;
+    }
+    print!("是否删除以上 {} 个分支？(y/N) ", deletable);
+    let _ = stdout().flush();
+    let mut user_input = String::new();
+    stdin().read_line(&mut user_input).unwrap_or(0);
+    if !matches!(user_input.to_lowercase().trim(), "y" | "yes") {
+        return;
+    }
+
+    match branches::clean_up_branches(github, upstream_repo, contributor_repo, &repo_rule, false).await {
+        Ok(deleted) => println!("已删除 {} 个分支。", deleted.len()),
+        Err(e) => eprintln!("Failed to delete the branches: {}", e),
+    }
+}
@@ -1,14 +1,29 @@
 use std::io::{stdin, stdout, Write};
+use std::path::Path;
 
-use fosscopetoolkit_core::{get_contributor_repo, set_contributor_repo};
-use fosscopetoolkit_core::apis::GitHubApi;
+use fosscopetoolkit_core::{Error, get_contributor_repo, set_contributor_repo, SANDBOX_DIR, WORKSPACE_DIR};
+use fosscopetoolkit_core::apis::{Forge, GiteaApi, GitHubApi, SandboxForge};
 use fosscopetoolkit_core::config::config::initial_configuration_process;
-use fosscopetoolkit_core::config::github::GitHubAccount;
-use fosscopetoolkit_core::models::GitHubRepo;
+use fosscopetoolkit_core::config::github::{DeviceFlow, ForgeKind, GitHubAccount};
+use fosscopetoolkit_core::models::{GitHubRepo, TokenCapabilities};
+use fosscopetoolkit_core::models::repo_rule::get_repo_rule;
+use fosscopetoolkit_core::workflow::translate::select::submit::SELECT;
 
+mod branches;
+mod pull_request;
+mod validate;
 mod workflow;
 
-async fn fork_creation_process(github: &GitHubApi, upstream_repo: &GitHubRepo) -> bool {
+/// Store the contributor repository, exiting the program if it cannot be stored.
+fn save_contributor_repo(workspace: &Path, repo: GitHubRepo, upstream_repo: &GitHubRepo) {
+    println!("Setting the contributor repository to: {}", repo.get_full_name());
+    if let Err(e) = set_contributor_repo(workspace, repo, upstream_repo) {
+        eprintln!("Failed to set the contributor repository: {}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn fork_creation_process(github: &impl Forge, workspace: &Path, upstream_repo: &GitHubRepo) -> bool {
     print!("Do you want to use another fork or create a new fork? (y/n) ");
     let mut user_input = String::new();
     let _ = stdout().flush();
@@ -35,7 +50,7 @@ async fn fork_creation_process(github: &GitHubApi, upstream_repo: &GitHubRepo) -
             let fork_repo = GitHubRepo::new(fork_owner, fork_repo_name);
 
             // Check if the forked repository exists.
-            let fork_exists = github.validate_repo(fork_repo.clone(), upstream_repo.clone()).await;
+            let fork_exists = github.validate_repo(&fork_repo, upstream_repo).await;
             match fork_exists {
                 Ok(_) => {
                     // Ask the user if they want to use the forked repository.
@@ -46,7 +61,7 @@ async fn fork_creation_process(github: &GitHubApi, upstream_repo: &GitHubRepo) -
                     stdin().read_line(&mut user_input).unwrap_or(0);
                     match user_input.to_lowercase().trim() {
                         "y" | "yes" => {
-                            set_contributor_repo(fork_repo, upstream_repo);
+                            save_contributor_repo(workspace, fork_repo, upstream_repo);
                             return true;
                         }
                         _ => {
@@ -55,26 +70,32 @@ async fn fork_creation_process(github: &GitHubApi, upstream_repo: &GitHubRepo) -
                         }
                     }
                 }
-                Err("Repository does not exist") => {
+                Err(Error::NotFound(_)) => {
                     // Create a new forked repository and use it as the contributor repository.
-                    let fork = github.create_fork(
-                        fork_repo.clone(), upstream_repo.clone(),
-                    ).await;
+                    let fork = github.create_fork_with_progress(&fork_repo, upstream_repo, |elapsed| {
+                        println!("The forked repository is being created, please wait... ({}s)", elapsed.as_secs());
+                    }).await;
                     match fork {
                         Ok(fork) => {
                             println!("Forked repository created successfully: {}", fork.get_full_name());
-                            set_contributor_repo(fork, upstream_repo);
+                            save_contributor_repo(workspace, fork, upstream_repo);
                             return true;
                         }
-                        Err(_) => {
-                            eprintln!("Failed to create the forked repository.");
+                        Err(e @ Error::ForkTimeout { .. }) => {
+                            // The fork will be listed as one of the forks of the user once it is created.
+                            eprintln!("{}", e);
+                            std::process::exit(1);
+                        }
+                        Err(e) => {
+                            eprintln!("Failed to create the forked repository: {}", e);
                             std::process::exit(1);
                         }
                     }
                 }
-                Err(_) => {
+                Err(e) => {
                     // The repo either is not a fork or is not a fork of the upstream repository.
                     // Ask the user to provide a new owner/repository name.
+                    eprintln!("{}", e);
                     return false;
                 }
             }
@@ -86,59 +107,244 @@ async fn fork_creation_process(github: &GitHubApi, upstream_repo: &GitHubRepo) -
     }
 }
 
-async fn create_fork(github: &GitHubApi, upstream_repo: GitHubRepo) {
+async fn create_fork(github: &impl Forge, workspace: &Path, upstream_repo: GitHubRepo) {
     loop {
-        let result = fork_creation_process(github, &upstream_repo).await;
+        let result = fork_creation_process(github, workspace, &upstream_repo).await;
         if result {
             break;
         }
     }
 }
 
-async fn fork_check(github: &GitHubApi, upstream_repo: GitHubRepo) {
-    let user_fork = github.get_user_fork(upstream_repo.clone()).await;
-    match user_fork {
-        Ok(fork) => {
-            println!("You have forked the upstream repository: {}", upstream_repo.get_full_name());
-            println!("Your fork repo have the name: {}", fork.get_full_name());
-            print!("Do you want to use this forked repository as the contributor repository? (y/n) ");
-            let mut user_input = String::new();
-            let _ = stdout().flush();
-            stdin().read_line(&mut user_input).unwrap_or(0);
-            match user_input.to_lowercase().trim() {
-                "y" | "yes" => {
-                    set_contributor_repo(fork, &upstream_repo);
-                }
-                _ => {
-                    create_fork(github, upstream_repo).await;
-                }
-            }
+async fn fork_check(github: &impl Forge, workspace: &Path, upstream_repo: GitHubRepo) {
+    let forks = match github.get_user_forks(&upstream_repo).await {
+        Ok(forks) => forks,
+        Err(e) => {
+            eprintln!("Failed to check the forks of the upstream repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if forks.is_empty() {
+        println!("You don't have a fork of the upstream repository: {}", upstream_repo.get_full_name());
+        create_fork(github, workspace, upstream_repo).await;
+        return;
+    }
+
+    println!("You have forked the upstream repository: {}", upstream_repo.get_full_name());
+    println!("Your forks of the upstream repository:");
+    for (index, fork) in forks.iter().enumerate() {
+        println!("{}. {}", index + 1, fork.get_full_name());
+    }
+    print!("Please choose the fork to use as the contributor repository, or enter 0 to use another fork or create a new fork (default: 1): ");
+    let mut user_input = String::new();
+    let _ = stdout().flush();
+    stdin().read_line(&mut user_input).unwrap_or(0);
+    let choice = match user_input.trim() {
+        "" => Some(0),
+        input => input.parse::<usize>().ok().and_then(|choice| choice.checked_sub(1)),
+    };
+    match choice.and_then(|choice| forks.get(choice)) {
+        Some(fork) => {
+            save_contributor_repo(workspace, fork.clone(), &upstream_repo);
+        }
+        None => {
+            create_fork(github, workspace, upstream_repo).await;
+        }
+    }
+}
+
+async fn fork_sync_check(github: &impl Forge, upstream_repo: &GitHubRepo, contributor_repo: &GitHubRepo) {
+    // The fork is compared against the base branch that contributions are made to.
+    let base = match get_repo_rule(upstream_repo, github).await {
+        Ok(repo_rule) => repo_rule.git.base,
+        Err(e) => {
+            eprintln!("Failed to get repository rule, skipping the fork synchronization check: {}", e);
+            return;
+        }
+    };
+
+    let status = match github.fork_status(contributor_repo, upstream_repo, &base).await {
+        Ok(status) => status,
+        Err(e) => {
+            eprintln!("Failed to check the status of your fork: {}", e);
+            return;
+        }
+    };
+    if status.is_up_to_date() {
+        return;
+    }
+
+    println!(
+        "Your fork {} is {} commit(s) behind and {} commit(s) ahead of {}:{}.",
+        contributor_repo.get_full_name(), status.behind_by, status.ahead_by, upstream_repo.get_full_name(), base
+    );
+    if status.has_diverged() {
+        println!("Your fork has diverged from the upstream repository, synchronizing it may not be possible.");
+    }
+    print!("Do you want to synchronize your fork with the upstream repository? (y/n) ");
+    let mut user_input = String::new();
+    let _ = stdout().flush();
+    stdin().read_line(&mut user_input).unwrap_or(0);
+    if !matches!(user_input.to_lowercase().trim(), "y" | "yes") {
+        return;
+    }
+
+    match github.sync_fork(contributor_repo, &base).await {
+        Ok(_) => println!("Your fork has been synchronized with the upstream repository."),
+        Err(Error::ForkDiverged(_)) => {
+            eprintln!(
+                "Your fork cannot be synchronized automatically because the {} branch has diverged. \
+                Please resolve the conflicts on GitHub or reset the branch manually.",
+                base
+            );
         }
+        Err(e) => eprintln!("Failed to synchronize your fork: {}", e),
+    }
+}
+
+/// Store the GitHub account, so the user does not have to sign in again, exiting the program if it cannot be stored.
+fn save_github_account(github_account: &GitHubAccount) {
+    let json_str = match serde_json::to_string(github_account) {
+        Ok(json_str) => json_str,
         Err(_) => {
-            println!("You don't have a fork of the upstream repository: {}", upstream_repo.get_full_name());
-            create_fork(github, upstream_repo).await;
+            eprintln!("Failed to save the GitHub account.");
+            std::process::exit(1);
+        }
+    };
+    std::fs::create_dir_all(WORKSPACE_DIR).unwrap();
+    let mut file = std::fs::File::create(
+        Path::new(WORKSPACE_DIR).join("github_account.json")
+    ).unwrap();
+    file.write_all(json_str.as_bytes()).unwrap();
+}
+
+/// The URLs of the GitHub instance to sign in to, each `None` for GitHub.com or to derive it from `base_url`.
+struct ApiUrls {
+    base_url: Option<String>,
+    graphql_url: Option<String>,
+    upload_url: Option<String>,
+}
+
+impl ApiUrls {
+    /// Set the URLs of an account.
+    fn apply(&self, mut github_account: GitHubAccount) -> GitHubAccount {
+        github_account.base_url = self.base_url.clone();
+        github_account.graphql_url = self.graphql_url.clone();
+        github_account.upload_url = self.upload_url.clone();
+        github_account
+    }
+}
+
+/// Ask for an optional value, `None` if nothing is entered.
+fn read_optional(prompt: &str) -> Option<String> {
+    print!("{}", prompt);
+    let mut user_input = String::new();
+    let _ = stdout().flush();
+    stdin().read_line(&mut user_input).unwrap_or(0);
+    match user_input.trim() {
+        "" => None,
+        value => Some(value.to_string()),
+    }
+}
+
+/// Sign in with the GitHub OAuth device flow of the GitHub instance to sign in to.
+async fn device_flow_login(urls: &ApiUrls) -> Result<(GitHubAccount, GitHubApi), Error> {
+    let flow = DeviceFlow::from_env(urls.base_url.as_deref())?;
+    let code = flow.request_code().await?;
+    println!("Please open {} in your browser and enter the code: {}", code.verification_uri, code.user_code);
+    println!("Waiting for the authorization...");
+    let token = flow.poll_token(&code).await?;
+    urls.apply(GitHubAccount::new(String::new(), token)).login_with_token().await
+}
+
+/// Sign in with a GitHub username and personal access token.
+async fn token_login(urls: &ApiUrls) -> Result<(GitHubAccount, GitHubApi), Error> {
+    let mut user_input = String::new();
+    print!("Please enter your GitHub username: ");
+    let _ = stdout().flush();
+    stdin().read_line(&mut user_input).unwrap_or(0);
+    let username = user_input.trim().to_string();
+    user_input.clear();
+    print!("Please enter your GitHub personal access token: ");
+    let _ = stdout().flush();
+    stdin().read_line(&mut user_input).unwrap_or(0);
+    let token = user_input.trim().to_string();
+    let github_account = urls.apply(GitHubAccount::new(username, token));
+    let github = github_account.login().await?;
+    Ok((github_account, github))
+}
+
+/// Sign in to a Gitea or Forgejo instance with a username and personal access token.
+async fn gitea_login() -> Result<(GitHubAccount, GiteaApi), Error> {
+    let base_url = loop {
+        match read_optional("Please enter the URL of the Gitea or Forgejo instance, e.g. https://forgejo.example.com: ") {
+            Some(base_url) => break base_url,
+            None => eprintln!("The URL of the instance is required."),
+        }
+    };
+    let username = read_optional("Please enter your username: ").unwrap_or_default();
+    let token = read_optional("Please enter your personal access token: ").unwrap_or_default();
+    let account = GitHubAccount::new(username, token).with_gitea(base_url);
+    let gitea = account.login_gitea().await?;
+    Ok((account, gitea))
+}
+
+/// The forge the user has signed in to.
+///
+/// # Variants
+/// - `GitHub`: GitHub.com or a GitHub Enterprise Server.
+/// - `Gitea`: A Gitea or Forgejo instance.
+// The user only ever signs in once, so its size does not matter.
+#[allow(clippy::large_enum_variant)]
+enum SignedIn {
+    GitHub(GitHubApi),
+    Gitea(GiteaApi),
+}
+
+/// Sign in with the saved account, exiting the program if the login fails.
+async fn saved_account_login(github_account: GitHubAccount) -> SignedIn {
+    let result = match github_account.forge {
+        ForgeKind::GitHub => github_account.login().await.map(|github| {
+            warn_token_capabilities(&github);
+            SignedIn::GitHub(github)
+        }),
+        ForgeKind::Gitea => github_account.login_gitea().await.map(SignedIn::Gitea),
+    };
+    match result {
+        Ok(signed_in) => signed_in,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
         }
     }
 }
 
-async fn login() -> GitHubApi {
-    let file_path = std::path::Path::new(".fosscope_toolkit/github_account.json");
+/// Warn about the capabilities the token lacks that did not prevent the login.
+fn warn_token_capabilities(github: &GitHubApi) {
+    match github.token_capabilities() {
+        Some(TokenCapabilities::Unknown) => {
+            println!("Warning: The permissions of a fine-grained token cannot be checked in advance. \
+            Make sure it can write the contents and pull requests of your fork.");
+        }
+        Some(capabilities) => {
+            for capability in capabilities.missing_optional() {
+                println!("Warning: The token cannot {}, which may be needed to synchronize your fork.", capability);
+            }
+        }
+        None => {}
+    }
+}
+
+async fn login() -> SignedIn {
+    let file_path = Path::new(WORKSPACE_DIR).join("github_account.json");
     if file_path.exists() {
         let file = std::fs::File::open(file_path);
         match file {
             Ok(file) => {
                 let reader = std::io::BufReader::new(file);
                 let github_account: GitHubAccount = serde_json::from_reader(reader).unwrap();
-                let github = github_account.login().await;
-                match github {
-                    Ok(github) => {
-                        return github;
-                    }
-                    Err(e) => {
-                        eprintln!("{}", e);
-                        std::process::exit(1);
-                    }
-                }
+                saved_account_login(github_account).await
             }
             Err(_) => {
                 eprintln!("Failed to read the GitHub account file.");
@@ -146,37 +352,59 @@ async fn login() -> GitHubApi {
             }
         }
     } else {
+        let forge = read_optional("Please select the forge you contribute on: 1. GitHub 2. Gitea / Forgejo (default: 1): ");
+        if forge.as_deref() == Some("2") {
+            loop {
+                match gitea_login().await {
+                    Ok((github_account, gitea)) => {
+                        save_github_account(&github_account);
+                        println!("Signed in to {} as {}", github_account.base_url.unwrap_or_default(), gitea.username());
+                        return SignedIn::Gitea(gitea);
+                    }
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        eprintln!("Please try again.");
+                    }
+                }
+            }
+        }
+
+        let base_url = read_optional(
+            "Please enter the GitHub API base URL, e.g. https://github.example.com/api/v3 for GitHub Enterprise Server \
+            (default: https://api.github.com): "
+        );
+        // The GraphQL and uploads APIs of GitHub Enterprise Server are derived from the base URL,
+        // other servers may serve them elsewhere.
+        let (graphql_url, upload_url) = match &base_url {
+            Some(_) => (
+                read_optional("Please enter the GitHub GraphQL API URL (default: derived from the base URL): "),
+                read_optional("Please enter the GitHub uploads API URL (default: derived from the base URL): "),
+            ),
+            None => (None, None),
+        };
+        let urls = ApiUrls { base_url, graphql_url, upload_url };
+
         loop {
+            println!("Please select how you want to sign in to GitHub:");
+            println!("1. 使用浏览器登录 (Sign in with your browser)");
+            println!("2. 使用个人访问令牌登录 (Sign in with a personal access token)");
             let mut user_input = String::new();
-            user_input.clear();
-            print!("Please enter your GitHub username: ");
-            let _ = stdout().flush();
-            stdin().read_line(&mut user_input).unwrap_or(0);
-            let username = user_input.trim().to_string();
-            user_input.clear();
-            print!("Please enter your GitHub personal access token: ");
             let _ = stdout().flush();
             stdin().read_line(&mut user_input).unwrap_or(0);
-            let token = user_input.trim().to_string();
-            let github_account = GitHubAccount::new(username, token);
-            let github = github_account.login().await;
-            match github {
-                Ok(github) => {
-                    let json_str = serde_json::to_string(&github_account);
-                    match json_str {
-                        Ok(json_str) => {
-                            std::fs::create_dir_all(".fosscope_toolkit").unwrap();
-                            let mut file = std::fs::File::create(
-                                ".fosscope_toolkit/github_account.json"
-                            ).unwrap();
-                            file.write_all(json_str.as_bytes()).unwrap();
-                            return github;
-                        }
-                        Err(_) => {
-                            eprintln!("Failed to save the GitHub account.");
-                            std::process::exit(1);
-                        }
-                    }
+            let result = match user_input.trim() {
+                "1" => device_flow_login(&urls).await,
+                "2" => token_login(&urls).await,
+                _ => {
+                    eprintln!("Invalid input. Please select an option from the list.");
+                    continue;
+                }
+            };
+            match result {
+                Ok((github_account, github)) => {
+                    save_github_account(&github_account);
+                    println!("Signed in to GitHub as {}", github.username());
+                    warn_token_capabilities(&github);
+                    return SignedIn::GitHub(github);
                 }
                 Err(e) => {
                     eprintln!("{}", e);
@@ -188,12 +416,59 @@ async fn login() -> GitHubApi {
     }
 }
 
+/// Start the sandbox mode, in which every repository and pull request only exists on this computer.
+///
+/// # Arguments
+/// - `workspace`: The runtime storage directory of the sandbox mode, which the sandbox is kept in.
+fn open_sandbox(workspace: &Path) -> SandboxForge {
+    match SandboxForge::start(workspace) {
+        Ok(sandbox) => {
+            println!("沙盒模式：所有仓库与 Pull Request 都只存在于您的电脑上，不会影响真实的 GitHub 仓库。");
+            sandbox
+        }
+        Err(e) => {
+            eprintln!("Failed to open the sandbox: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() {
-    let _ = initial_configuration_process();
+    // `validate [PATH]` checks a local repository rule before it is pushed, without signing in.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "validate") {
+        let path = args.get(1).map_or("REPORULE", String::as_str);
+        std::process::exit(if validate::validate(path) { 0 } else { 1 });
+    }
 
-    let github = login().await;
+    let sandbox = args.iter().any(|arg| arg == "--sandbox");
+    // The sandbox mode keeps its configuration, contributor repositories and clones apart from the real ones.
+    let workspace = Path::new(if sandbox { SANDBOX_DIR } else { WORKSPACE_DIR });
+    let sandbox = if sandbox { Some(open_sandbox(workspace)) } else { None };
 
+    if let Err(e) = initial_configuration_process(workspace) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+
+    match &sandbox {
+        Some(sandbox) => run(sandbox, workspace, Some(sandbox)).await,
+        None => match login().await {
+            SignedIn::GitHub(github) => run(&github, workspace, None).await,
+            SignedIn::Gitea(gitea) => run(&gitea, workspace, None).await,
+        },
+    }
+}
+
+/// Let the user choose an upstream repository and a contribution, and make it.
+///
+/// # Arguments
+/// - `github`: The forge to contribute with.
+/// - `workspace`: The runtime storage directory, [`WORKSPACE_DIR`](WORKSPACE_DIR) or [`SANDBOX_DIR`](SANDBOX_DIR).
+/// - `sandbox`: The sandbox in the sandbox mode, where `github` is the sandbox itself,
+///   so the user can merge their pull requests as a maintainer would and rehearse the next steps.
+async fn run(github: &impl Forge, workspace: &Path, sandbox: Option<&SandboxForge>) {
     println!("Please select the upstream repository you want to work with:");
     println!("1. FOSScope/Articles - 开源观察原创文章与中文转载文章源文件");
     println!("2. FOSScope/TranslateProject - 开源观察翻译项目");
@@ -225,37 +500,66 @@ async fn main() {
         std::process::exit(1);
     }
 
-    let contributor_repo = match get_contributor_repo(&upstream_repo) {
-        Some(repo) => repo,
-        None => {
-            fork_check(&github, upstream_repo.clone()).await;
-            get_contributor_repo(&upstream_repo).unwrap()
+    let contributor_repo = match get_contributor_repo(workspace, &upstream_repo) {
+        Ok(Some(repo)) => repo,
+        Ok(None) => {
+            fork_check(github, workspace, upstream_repo.clone()).await;
+            get_contributor_repo(workspace, &upstream_repo).ok().flatten().unwrap()
+        }
+        Err(e) => {
+            eprintln!("Failed to read the contributor repository: {}", e);
+            std::process::exit(1);
         }
     };
+    fork_sync_check(github, &upstream_repo, &contributor_repo).await;
 
-    println!(
-        r#"请选择当前要进行的贡献
-        1. 选题
-        2. 翻译
-        3. 校对
-        4. 发布"#
-    );
+    let repo_rule = match get_repo_rule(&upstream_repo, github).await {
+        Ok(repo_rule) => repo_rule,
+        Err(e) => {
+            eprintln!("Failed to get repository rule: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // The contributions are the actions of the repository rule, followed by the management of the pull requests and branches.
+    println!("请选择当前要进行的贡献");
+    for (i, action) in repo_rule.actions.iter().enumerate() {
+        println!("        {}. {}", i + 1, action.description);
+    }
+    let pull_requests_option = repo_rule.actions.len() + 1;
+    let clean_up_option = repo_rule.actions.len() + 2;
+    let merge_option = repo_rule.actions.len() + 3;
+    println!("        {}. 查看我的 Pull Request", pull_requests_option);
+    println!("        {}. 清理已完成的分支", clean_up_option);
+    if sandbox.is_some() {
+        println!("        {}. 合并我的 Pull Request（沙盒）", merge_option);
+    }
 
     let mut user_input = String::new();
-    let mut valid_input = false;
-    while !valid_input {
+    loop {
         stdin().read_line(&mut user_input).unwrap_or(0);
-        match user_input.trim() {
-            "1" => {
-                valid_input = true;
-                workflow::translate::select::select(
-                    &github, &upstream_repo, &contributor_repo
-                ).await;
+        match user_input.trim().parse::<usize>() {
+            Ok(option) if option == pull_requests_option => {
+                pull_request::pull_requests(github, &upstream_repo).await;
             }
-            "2" | "3" | "4" => {
-                eprintln!("Not implemented yet.");
-                user_input.clear();
-                continue;
+            Ok(option) if option == clean_up_option => {
+                branches::clean_up_branches(github, &upstream_repo, &contributor_repo).await;
+            }
+            Ok(option) if option == merge_option && sandbox.is_some() => {
+                pull_request::merge_sandbox_pull_request(sandbox.unwrap(), &upstream_repo).await;
+            }
+            Ok(option) if (1..pull_requests_option).contains(&option) => {
+                let action = &repo_rule.actions[option - 1];
+                // Selecting creates the article from its URL, the other actions work on the articles in the repository.
+                if action.name == SELECT {
+                    workflow::translate::select::select(
+                        github, workspace, &upstream_repo, &contributor_repo, sandbox.is_some()
+                    ).await;
+                } else {
+                    workflow::action::perform(
+                        github, workspace, &upstream_repo, &contributor_repo, &repo_rule, action, sandbox.is_some()
+                    ).await;
+                }
             }
             _ => {
                 user_input.clear();
@@ -263,5 +567,6 @@ async fn main() {
                 continue;
             }
         }
+        break;
     }
 }
@@ -0,0 +1,123 @@
+use std::io::{stdin, stdout, Write};
+
+use fosscopetoolkit_core::apis::{Forge, SandboxForge};
+use fosscopetoolkit_core::models::{GitHubRepo, PullRequestState, PullRequestStatus, ReviewDecision};
+
+/// Print the status of a pull request in a human-readable form.
+fn print_status(status: &PullRequestStatus) {
+    let pr = &status.pull_request;
+    let state = match pr.state {
+        PullRequestState::Open if pr.draft => "草稿",
+        PullRequestState::Open => "开放",
+        PullRequestState::Closed => "已关闭",
+        PullRequestState::Merged => "已合并",
+    };
+    let review = match status.review_decision {
+        ReviewDecision::Pending => "等待审核",
+        ReviewDecision::Approved => "已批准",
+        ReviewDecision::ChangesRequested => "需要修改",
+    };
+    let mergeable = match status.mergeable {
+        Some(true) => "可合并",
+        Some(false) => "存在冲突",
+        None => "未知",
+    };
+
+    println!("#{} {} ({})", pr.number, pr.title, pr.url);
+    println!("    状态：{}，审核：{}，合并：{}", state, review, mergeable);
+    for check in &status.checks {
+        println!(
+            "    检查 {}：{}",
+            check.name,
+            check.conclusion.as_deref().unwrap_or(&check.status)
+        );
+    }
+}
+
+/// List the open pull requests of the user, and let them close a pull request or mark a draft as ready for review.
+pub async fn pull_requests(github: &impl Forge, upstream_repo: &GitHubRepo) {
+    let pull_requests = match github.list_user_pull_requests(upstream_repo).await {
+        Ok(pull_requests) => pull_requests,
+        Err(e) => {
+            eprintln!("Failed to list your pull requests: {}", e);
+            return;
+        }
+    };
+    if pull_requests.is_empty() {
+        println!("您在 {} 中没有开放的 Pull Request。", upstream_repo.get_full_name());
+        return;
+    }
+
+    for pr in &pull_requests {
+        match github.pull_request_status(upstream_repo, pr.number).await {
+            Ok(status) => print_status(&status),
+            Err(e) => eprintln!("Failed to get the status of #{}: {}", pr.number, e),
+        }
+    }
+
+    print!("输入 Pull Request 编号以进行操作，或直接回车返回：");
+    let _ = stdout().flush();
+    let mut user_input = String::new();
+    stdin().read_line(&mut user_input).unwrap_or(0);
+    let Ok(number) = user_input.trim().parse::<u64>() else {
+        return;
+    };
+    let Some(pr) = pull_requests.iter().find(|pr| pr.number == number) else {
+        eprintln!("Pull request #{} is not one of your open pull requests.", number);
+        return;
+    };
+
+    println!("1. 关闭");
+    if pr.draft {
+        println!("2. 标记为可审核");
+    }
+    user_input.clear();
+    stdin().read_line(&mut user_input).unwrap_or(0);
+    match user_input.trim() {
+        "1" => match github.close_pull_request(upstream_repo, number).await {
+            Ok(_) => println!("Pull Request #{} 已关闭。", number),
+            Err(e) => eprintln!("Failed to close the pull request: {}", e),
+        },
+        "2" if pr.draft => match github.mark_ready_for_review(upstream_repo, pr).await {
+            Ok(_) => println!("Pull Request #{} 已标记为可审核。", number),
+            Err(e) => eprintln!("Failed to mark the pull request as ready for review: {}", e),
+        },
+        _ => eprintln!("Invalid input."),
+    }
+}
+
+/// Merge one of the open pull requests of the user in the sandbox, as a maintainer would,
+/// so the article reaches the next step of the workflow and the next actions can be rehearsed.
+pub async fn merge_sandbox_pull_request(sandbox: &SandboxForge, upstream_repo: &GitHubRepo) {
+    let pull_requests = match sandbox.list_user_pull_requests(upstream_repo).await {
+        Ok(pull_requests) => pull_requests,
+        Err(e) => {
+            eprintln!("Failed to list your pull requests: {}", e);
+            return;
+        }
+    };
+    if pull_requests.is_empty() {
+        println!("您在 {} 中没有开放的 Pull Request。", upstream_repo.get_full_name());
+        return;
+    }
+
+    for pr in &pull_requests {
+        println!("#{} {}", pr.number, pr.title);
+    }
+    print!("输入要合并的 Pull Request 编号，或直接回车返回：");
+    let _ = stdout().flush();
+    let mut user_input = String::new();
+    stdin().read_line(&mut user_input).unwrap_or(0);
+    let Ok(number) = user_input.trim().parse::<u64>() else {
+        return;
+    };
+    if !pull_requests.iter().any(|pr| pr.number == number) {
+        eprintln!("Pull request #{} is not one of your open pull requests.", number);
+        return;
+    }
+
+    match sandbox.merge_pull_request(upstream_repo, number) {
+        Ok(_) => println!("Pull Request #{} 已合并，可以进行下一步了。", number),
+        Err(e) => eprintln!("Failed to merge the pull request: {}", e),
+    }
+}
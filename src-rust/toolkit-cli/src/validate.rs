@@ -0,0 +1,33 @@
+use std::fs;
+
+use fosscopetoolkit_core::models::repo_rule::validate_repo_rule;
+
+/// Validate a local `REPORULE` file, printing every problem found with its position in the file.
+///
+/// # Arguments
+/// - `path`: The path of the `REPORULE` file.
+///
+/// # Returns
+/// - `bool`: Whether the file is a usable repository rule, i.e. no error has been found. Warnings are printed but allowed.
+pub fn validate(path: &str) -> bool {
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("无法读取 {}：{}", path, e);
+            return false;
+        }
+    };
+
+    let diagnostics = validate_repo_rule(&source);
+    for diagnostic in &diagnostics {
+        eprintln!("{}:{}", path, diagnostic);
+    }
+    let errors = diagnostics.iter().filter(|diagnostic| diagnostic.is_error()).count();
+    let warnings = diagnostics.len() - errors;
+    if errors == 0 {
+        println!("{} 是有效的仓库规则（{} 个警告）。", path, warnings);
+    } else {
+        eprintln!("{} 不是有效的仓库规则：{} 个错误，{} 个警告。", path, errors, warnings);
+    }
+    errors == 0
+}
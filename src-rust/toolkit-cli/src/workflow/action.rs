@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{stdin, stdout, Write};
+use std::path::Path;
+
+use fosscopetoolkit_core::apis::{Forge, LocalGitRepo};
+use fosscopetoolkit_core::config::config::get_config;
+use fosscopetoolkit_core::Error;
+use fosscopetoolkit_core::models::{GitHubRepo, RepoRule};
+use fosscopetoolkit_core::models::repo_rule::{Action, Article};
+use fosscopetoolkit_core::workflow::action;
+
+use crate::workflow::variables::prompt_variables;
+
+/// Commit the article in the local clone of the contributor repository, cloning it first if needed, and push it.
+///
+/// Only used with forges whose repositories can be cloned, i.e. never with the sandbox.
+#[allow(clippy::too_many_arguments)]
+pub async fn commit_locally(
+    github: &impl Forge,
+    workspace: &Path,
+    upstream_repo: &GitHubRepo,
+    contributor_repo: &GitHubRepo,
+    action: &Action,
+    file_name: &str,
+    content: &str,
+    article_type: &Article,
+    repo_rule: &RepoRule,
+    vars: &HashMap<&str, String>,
+) -> Result<(), Error> {
+    let contributor_url = github.get_clone_url(contributor_repo).await?;
+    let upstream_url = github.get_clone_url(upstream_repo).await?;
+    let local_repo = LocalGitRepo::open_or_clone(
+        &contributor_url, LocalGitRepo::workspace_path(workspace, contributor_repo),
+    ).await?;
+    println!("正在使用本地仓库提交：{}", local_repo.path().display());
+
+    let sha = action::commit_locally(
+        &local_repo, &upstream_url, action, file_name, content, article_type, repo_rule, vars
+    ).await?;
+    println!("本地提交 {} 已推送。", sha);
+    Ok(())
+}
+
+/// Write the article to a file and open it in the user's text editor, returning its content once the user is done.
+///
+/// # Arguments
+/// - `file_name`: The file the article is written to, in the current directory.
+/// - `content`: The content of the article.
+/// - `editor`: The command launching the text editor of the user.
+pub fn edit_article(file_name: &str, content: &str, editor: &str) -> String {
+    fs::write(file_name, content).expect("无法写入文件");
+    println!("已将文章写入文件：{}", file_name);
+
+    let path_to_file = std::env::current_dir().unwrap().join(file_name);
+    let _ = std::process::Command::new(editor)
+        .arg(path_to_file)
+        .status()
+        .expect("无法打开编辑器");
+    // The exit status of editors that only launch a window (e.g. Visual Studio Code) cannot be detected,
+    // so the user confirms when the editing is done.
+    println!("如果您已经完成了编辑，请输入任何内容以继续。");
+    let _ = stdin().read_line(&mut String::new());
+
+    fs::read_to_string(file_name).expect("无法读取文章文件")
+}
+
+/// Get the `title` field of the front matter of an article, if there is one.
+fn front_matter_title(article: &str) -> Option<String> {
+    let rest = article.trim_start_matches('\u{feff}').strip_prefix("---")?;
+    let front_matter = &rest[..rest.find("\n---")?];
+    front_matter.lines()
+        .find_map(|line| line.strip_prefix("title:"))
+        .map(|title| title.trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+        .filter(|title| !title.is_empty())
+}
+
+/// Perform an action of the repository rule on an article waiting in its source step,
+/// e.g. translate a selected article.
+///
+/// # Arguments
+/// - `github`: A pointer to the GitHub API wrapper.
+/// - `workspace`: The runtime storage directory, holding the configuration and the local clones.
+/// - `upstream_repo`: The repository the article is in.
+/// - `contributor_repo`: The repository that the user is contributing to.
+/// - `repo_rule`: The rule of the upstream repository.
+/// - `action`: The action to perform.
+/// - `sandbox`: Whether `github` is the sandbox, whose repositories cannot be cloned.
+pub async fn perform(
+    github: &impl Forge,
+    workspace: &Path,
+    upstream_repo: &GitHubRepo,
+    contributor_repo: &GitHubRepo,
+    repo_rule: &RepoRule,
+    action: &Action,
+    sandbox: bool,
+) {
+    // Get the username of the currently signed in GitHub user.
+    let user = match github.get_user().await {
+        Ok(user) => user,
+        Err(e) => {
+            eprintln!("Failed to get GitHub user: {}", e);
+            return;
+        }
+    };
+
+    // Get the configuration from the configuration file.
+    let config = match get_config(workspace) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+
+    // Let the user choose one of the articles waiting in the source step of the action.
+    let articles = match action::list_step_articles(github, upstream_repo, action, repo_rule).await {
+        Ok(articles) => articles,
+        Err(e) => {
+            eprintln!("Failed to list the articles: {}", e);
+            return;
+        }
+    };
+    if articles.is_empty() {
+        println!("没有可以{}的文章。", action.description);
+        return;
+    }
+    println!("请选择要{}的文章：", action.description);
+    for (i, article) in articles.iter().enumerate() {
+        println!("{}. {}", i + 1, article.path);
+    }
+    let article = loop {
+        print!("请输入文章的编号：");
+        let _ = stdout().flush();
+        let mut user_input = String::new();
+        stdin().read_line(&mut user_input).unwrap_or(0);
+        match user_input.trim().parse::<usize>() {
+            Ok(i) if (1..=articles.len()).contains(&i) => break &articles[i - 1],
+            _ => eprintln!("Invalid input. Please select an option from the list."),
+        }
+    };
+    let Some(article_type) = repo_rule.articles.iter().find(|a| a.r#type == article.article_type) else {
+        eprintln!("Unknown article type: {}", article.article_type);
+        return;
+    };
+
+    let content = match github.get_blob(upstream_repo, &article.sha).await {
+        Ok(content) => String::from_utf8_lossy(&content).into_owned(),
+        Err(e) => {
+            eprintln!("Failed to get the article: {}", e);
+            return;
+        }
+    };
+
+    // Open the article in the user's text editor for them to work on it.
+    let file_name = &article.file_name;
+    let content = edit_article(file_name, &content, &config.editor);
+
+    // Add information to the variables to be used in the Handlebars template rendering.
+    let mut vars = HashMap::new();
+    vars.insert("contributor", user);
+    vars.insert("type_name", article_type.r#type.clone());
+    vars.insert("type_desc", article_type.description.clone());
+    vars.insert("article_id", file_name.trim_end_matches(".md").to_string());
+    if let Some(title) = front_matter_title(&content) {
+        vars.insert("article_title", title);
+    }
+    // Ask for the variables the repository rule declares for the action, e.g. the translator.
+    prompt_variables(repo_rule, &action.name, &mut vars);
+    // Ask for the variables the action requires that are not known yet.
+    for var in action.missing_vars(&vars) {
+        print!("请输入 {}：", var);
+        let _ = stdout().flush();
+        let mut value = String::new();
+        stdin().read_line(&mut value).unwrap_or(0);
+        vars.insert(var, value.trim().to_string());
+    }
+
+    // Commit the article to the user's fork of the upstream repository.
+    // The repositories of the sandbox only exist in the sandbox itself, so they are always committed to through it.
+    let commit = if config.local_git && !sandbox {
+        commit_locally(
+            github, workspace, upstream_repo, contributor_repo, action, file_name, &content, article_type, repo_rule, &vars,
+        ).await
+    } else {
+        action::commit(
+            github, upstream_repo, contributor_repo, action, file_name, &content, article_type, repo_rule, &vars,
+        ).await
+    };
+    if let Err(e) = commit {
+        eprintln!("Failed to commit article: {}", e);
+        return;
+    }
+    println!("文章已提交到您的分支。");
+
+    // Create a pull request to the upstream repository.
+    match action::create_pr(github, upstream_repo, contributor_repo, action, repo_rule, &vars).await {
+        Ok(pr) => println!("Pull Request #{} 已创建：{}", pr.number, pr.url),
+        Err(e) => {
+            eprintln!("Failed to create pull request: {}", e);
+            return;
+        }
+    }
+
+    println!("您的{}已提交成功！", action.description);
+}
@@ -1 +1,3 @@
+pub mod action;
 pub mod translate;
+pub mod variables;
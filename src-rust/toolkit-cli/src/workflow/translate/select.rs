@@ -1,61 +1,118 @@
 use std::collections::HashMap;
-use std::fs;
 use std::io::{stdin, stdout, Write};
+use std::path::Path;
 
-use fosscopetoolkit_core::apis::GitHubApi;
+use fosscopetoolkit_core::apis::Forge;
 use fosscopetoolkit_core::config::config::get_config;
 use fosscopetoolkit_core::models::GitHubRepo;
-use fosscopetoolkit_core::models::repo_rule::get_repo_rule;
+use fosscopetoolkit_core::models::repo_rule::{get_repo_rule, Variable, VariableType};
 use fosscopetoolkit_core::workflow;
+use fosscopetoolkit_core::workflow::translate::select::submit::SELECT;
+
+use crate::workflow::action::{commit_locally, edit_article};
+use crate::workflow::variables::{prompt_variable, prompt_variables};
 
 /// Select an article to translate.
 ///
 /// # Arguments
 /// - `contributor_repo`: The repository that the user is contributing to.
 /// - `github`: A pointer to the GitHub API wrapper.
+/// - `workspace`: The runtime storage directory, holding the configuration, the local clones and the index of the articles.
+/// - `sandbox`: Whether `github` is the sandbox, whose repositories cannot be cloned.
 pub async fn select(
-    github: &GitHubApi,
+    github: &impl Forge,
+    workspace: &Path,
     upstream_repo: &GitHubRepo,
     contributor_repo: &GitHubRepo,
+    sandbox: bool,
 ) {
     // Get the username of the currently signed in GitHub user.
     let user = github.get_user().await;
     let user = match user {
         Ok(user) => user,
         Err(e) => {
-            eprintln!("Failed to get GitHub user: {:?}", e);
+            eprintln!("Failed to get GitHub user: {}", e);
             return;
         }
     };
 
     // Fetch the Translation Project repository's rule.
     let repo_rule = get_repo_rule(upstream_repo, github).await;
-
-    if repo_rule.is_err() {
-        eprintln!("Failed to get repository rule: {:?}", repo_rule.err());
-        return;
-    }
-    let repo_rule = repo_rule.unwrap();
+    let repo_rule = match repo_rule {
+        Ok(repo_rule) => repo_rule,
+        Err(e) => {
+            eprintln!("Failed to get repository rule: {}", e);
+            return;
+        }
+    };
+    let select_action = match repo_rule.action(SELECT) {
+        Ok(select_action) => select_action,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
 
     // Get the configuration from the configuration file.
-    let config = get_config();
+    let config = match get_config(workspace) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
 
     // Ask the user to input the URL of the article they want to select.
     println!("欢迎参与开源观察翻译项目！");
-    print!("请输入要选题的文章的 URL：");
-    let _ = stdout().flush();
-    let mut url = String::new();
-    stdin().read_line(&mut url).unwrap_or(0);
-    let url = url.trim().to_string();
+    // The repository rule may declare how the URL is asked for and validated, otherwise any HTTP(S) URL is accepted.
+    let url_variable = repo_rule.variable("via").cloned().unwrap_or_else(|| Variable::new(
+        "via".to_string(), "请输入要选题的文章的 URL".to_string(), VariableType::Url,
+    ));
+    let url = prompt_variable(&url_variable);
     println!("您选择的文章 URL 是：{}", url);
 
+    // Check that nobody has selected the article before, so it is not translated twice.
+    // The articles read by the previous selections are not read again.
+    let mut index = workflow::translate::select::ArticleIndex::load(workspace, upstream_repo).unwrap_or_default();
+    let selections = workflow::translate::select::find_existing_selections(github, upstream_repo, &repo_rule, &url, &mut index).await;
+    if selections.is_ok() {
+        if let Err(e) = index.save(workspace, upstream_repo) {
+            eprintln!("Failed to save the index of the articles: {}", e);
+        }
+    }
+    match selections {
+        Ok(selections) if !selections.is_empty() => {
+            println!("这篇文章已经被选过题了：");
+            for selection in &selections {
+                match &selection.pull_request {
+                    Some(pr) => println!("- Pull Request #{} ({}) 中的 {}", pr.number, pr.url, selection.path),
+                    None => println!("- {}:{} 中的 {}", upstream_repo.get_full_name(), repo_rule.git.base, selection.path),
+                }
+            }
+            print!("是否仍要继续选题？(y/N)：");
+            let _ = stdout().flush();
+            let mut user_input = String::new();
+            stdin().read_line(&mut user_input).unwrap_or(0);
+            if !matches!(user_input.trim().to_lowercase().as_str(), "y" | "yes") {
+                println!("已取消选题。");
+                return;
+            }
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("Failed to check whether the article has been selected before: {}", e),
+    }
+
     // Ask the user to enter the original publishing date of the article.
     // TODO: Automatically fetch the original publishing date from the article, if possible.
-    print!("请输入文章的原始发布日期 (格式：YYYYMMDD（例如：20240715)): ");
-    let _ = stdout().flush();
-    let mut publishing_date = String::new();
-    stdin().read_line(&mut publishing_date).unwrap_or(0);
-    let publishing_date = publishing_date.trim();
+    let date_variable = repo_rule.variable("publishing_date").cloned().unwrap_or_else(|| Variable {
+        pattern: Some(r"\d{8}".to_string()),
+        ..Variable::new(
+            "publishing_date".to_string(),
+            "请输入文章的原始发布日期 (格式：YYYYMMDD（例如：20240715))".to_string(),
+            VariableType::Date,
+        )
+    });
+    let publishing_date = prompt_variable(&date_variable);
     println!("您输入的文章发布日期是：{}", publishing_date);
 
     // Get the type of the article
@@ -78,74 +135,77 @@ pub async fn select(
     vars.insert("selector", user); // The username of the currently signed in GitHub user (as the article selector)
     vars.insert("type_name", article_type.r#type.clone());
     vars.insert("type_desc", article_type.description.clone());
+    vars.insert("publishing_date", publishing_date.clone());
+    // Ask for the other variables the repository rule declares for selecting, e.g. the tags of the article.
+    prompt_variables(&repo_rule, &select_action.name, &mut vars);
 
     // Get the article content in Markdown format, rendered using the data in the variables.
     let article = workflow::translate::select::fetch(
         &repo_rule, article_type, &vars
     ).await;
-    if article.is_err() {
-        eprintln!("Failed to select article: {:?}", article.err());
-        return;
-    }
-    let article = article.unwrap();
+    let article = match article {
+        Ok(article) => article,
+        Err(e) => {
+            eprintln!("Failed to select article: {}", e);
+            return;
+        }
+    };
 
     let title = article.1;
     let content = article.0;
     vars.insert("article_title", title.clone());
 
-    // The article ID is the original publishing date, dash (`-`), followed by the
-    // title in all lowercase, with spaces replaced by dashes (`-`), and with all non-alphanumeric
-    // characters removed.
-    let article_id = format!(
-        "{}-{}",
-        publishing_date,
-        title.to_lowercase().replace(" ", "-").chars().filter(
-            |c| c.is_alphanumeric() || *c == '-'
-        ).collect::<String>()
-    );
+    // The article ID is generated from the title as the repository rule defines, e.g. the original publishing date,
+    // dash (`-`), followed by the title in lowercase with its words separated by dashes (`-`).
+    let article_id = workflow::article_id::generate(
+        github, upstream_repo, contributor_repo, article_type, &repo_rule, &title, &vars
+    ).await;
+    let article_id = match article_id {
+        Ok(generated) => {
+            if let Some(e) = generated.unchecked {
+                eprintln!("Failed to check whether the article ID is used by another article: {}", e);
+            }
+            generated.id
+        }
+        Err(e) => {
+            eprintln!("Failed to generate the article ID: {}", e);
+            return;
+        }
+    };
+    println!("文章 ID 是：{}", article_id);
     vars.insert("article_id", article_id.clone());
 
+    // Open the article in the user's text editor for them to edit it if needed.
     let file_name = format!("{}.md", article_id);
-    // Write the article content to a file.
-    fs::write(&file_name, content).expect("无法写入文件");
-    println!("已将 Markdown 内容写入文件：{}", &file_name);
-
-    // Open the file in the user's text editor for them to edit the article if needed.
-    let path_to_file = std::env::current_dir().unwrap().join(&file_name);
-    let editor = &config.editor;
-    let _ = std::process::Command::new(editor)
-        .arg(path_to_file)
-        .status()
-        .expect("无法打开编辑器");
-    // We would not be able to detect the program's exit status if it is
-    // a program that only uses the terminal to launch the editor.
-    // e.g. Visual Studio Code.
-    // So the following message is displayed to the user to continue the process.
-    println!("如果您已经完成了编辑，请输入任何内容以继续。");
-    let _ = stdin().read_line(&mut String::new());
-
-    // Get the content of the file after the user has edited it.
-    let content = fs::read_to_string(&file_name).expect("无法读取文章源文件");
+    let content = edit_article(&file_name, &content, &config.editor);
 
     // Commit the article to the user's fork of the Translation Project repository.
-    let commit = workflow::translate::select::submit::commit(
-        github, contributor_repo, &file_name, &content, article_type, &repo_rule, &vars
-    ).await;
-    if commit.is_err() {
-        eprintln!("Failed to commit article: {:?}", commit.err());
+    let commit = if config.local_git && !sandbox {
+        commit_locally(
+            github, workspace, upstream_repo, contributor_repo, select_action, &file_name, &content, article_type, &repo_rule, &vars,
+        ).await
+    } else {
+        workflow::action::commit(
+            github, upstream_repo, contributor_repo, select_action, &file_name, &content, article_type, &repo_rule, &vars,
+        ).await
+    };
+    if let Err(e) = commit {
+        eprintln!("Failed to commit article: {}", e);
         return;
     }
     println!("文章已提交到您的分支。");
 
     // Create a pull request to the Translation Project repository.
-    let pr = workflow::translate::select::submit::create_pr(
-        github, upstream_repo, contributor_repo, &repo_rule, &vars
+    let pr = workflow::action::create_pr(
+        github, upstream_repo, contributor_repo, select_action, &repo_rule, &vars
     ).await;
-    if pr.is_err() {
-        eprintln!("Failed to create pull request: {:?}", pr.err());
-        return;
+    match pr {
+        Ok(pr) => println!("Pull Request #{} 已创建：{}", pr.number, pr.url),
+        Err(e) => {
+            eprintln!("Failed to create pull request: {}", e);
+            return;
+        }
     }
-    println!("Pull Request 已创建。");
 
     println!("您的选题已提交成功！");
 }
@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+use std::io::{stdin, stdout, Write};
+
+use fosscopetoolkit_core::models::repo_rule::{Variable, VariableType};
+use fosscopetoolkit_core::models::RepoRule;
+
+/// Ask the user for the value of a variable until a valid one is given, returning the normalized value.
+pub fn prompt_variable(variable: &Variable) -> String {
+    loop {
+        print!("{}", variable.prompt);
+        if variable.r#type == VariableType::Enum {
+            print!("（可选：{}）", variable.options.join(" / "));
+        }
+        if variable.r#type == VariableType::List {
+            print!("（以逗号分隔）");
+        }
+        if let Some(default) = &variable.default {
+            print!("（默认：{}）", default);
+        }
+        print!("：");
+        let _ = stdout().flush();
+
+        let mut input = String::new();
+        stdin().read_line(&mut input).unwrap_or(0);
+        match variable.parse(&input) {
+            Ok(value) => return value,
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+}
+
+/// Ask the user for the variables declared in the repository rule for an action, apart from those already given.
+///
+/// # Arguments
+/// - `repo_rule`: The rule of the repository, declaring the variables.
+/// - `action_name`: The name of the action being performed. e.g. `select`.
+/// - `vars`: The variables given so far, which the values are added to.
+pub fn prompt_variables<'a>(repo_rule: &'a RepoRule, action_name: &str, vars: &mut HashMap<&'a str, String>) {
+    for variable in repo_rule.variables_of(action_name) {
+        if !vars.contains_key(variable.name.as_str()) {
+            let value = prompt_variable(variable);
+            vars.insert(variable.name.as_str(), value);
+        }
+    }
+}
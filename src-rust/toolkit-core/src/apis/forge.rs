@@ -0,0 +1,366 @@
+use std::future::Future;
+use std::time::Duration;
+
+use crate::apis::GitHubApi;
+use crate::error::{Error, Result};
+use crate::models::{Branch, FileChange, ForkStatus, GitHubRepo, PullRequest, PullRequestFile, PullRequestStatus, RepoTree};
+
+/// The error of an operation that a forge does not support.
+fn unsupported(operation: &str) -> Error {
+    Error::Forge(format!("{} is not supported by this forge", operation))
+}
+
+/// A code hosting platform (GitHub, Gitea, Forgejo, ...) that hosts the upstream and contributor repositories.
+///
+/// The workflows of the toolkit (getting the repository rule, handling the fork and submitting articles)
+/// only use the methods of this trait, so they work with any forge that implements it.
+///
+/// Repositories are identified by their owner and name as a [GitHubRepo](GitHubRepo) on every forge.
+///
+/// The methods with a default implementation are not needed by the workflows themselves,
+/// and fail with [`Error::Forge`](Error::Forge) on forges that do not implement them.
+pub trait Forge: Send + Sync {
+    /// Get the username of the signed in user.
+    fn username(&self) -> &str;
+
+    /// Get the username (login) the token of the signed in user belongs to.
+    fn get_user(&self) -> impl Future<Output = Result<String>> + Send;
+
+    /// Check that `repo` is a fork of `upstream`.
+    ///
+    /// # Returns
+    /// - `Result<()>`
+    ///     - `Ok(())`: If `repo` is a fork of `upstream`.
+    ///     - `Err(Error)`: [`Error::NotAFork`](crate::Error::NotAFork) or [`Error::WrongParent`](crate::Error::WrongParent)
+    ///       if it is not, or another error indicating why the method failed.
+    fn validate_repo(&self, repo: &GitHubRepo, upstream: &GitHubRepo) -> impl Future<Output = Result<()>> + Send;
+
+    /// Get the forks of `upstream` owned by the user or by an organization the user is a member of,
+    /// with the forks owned by the user first.
+    fn get_user_forks(&self, upstream: &GitHubRepo) -> impl Future<Output = Result<Vec<GitHubRepo>>> + Send;
+
+    /// Fork `upstream` as `repo`, returning the created fork once it can be used.
+    ///
+    /// Fails with [`Error::ForkTimeout`](crate::Error::ForkTimeout) if the forge is still creating the fork after waiting for it.
+    fn create_fork(&self, repo: &GitHubRepo, upstream: &GitHubRepo) -> impl Future<Output = Result<GitHubRepo>> + Send;
+
+    /// Fork `upstream` as `repo` like [`create_fork`](Forge::create_fork), calling `progress` with the time waited so far
+    /// while the forge is still creating the fork.
+    ///
+    /// Forges that create forks synchronously (e.g. Gitea and Forgejo) never call `progress`.
+    fn create_fork_with_progress(
+        &self,
+        repo: &GitHubRepo,
+        upstream: &GitHubRepo,
+        _progress: impl Fn(Duration) + Send + Sync,
+    ) -> impl Future<Output = Result<GitHubRepo>> + Send {
+        self.create_fork(repo, upstream)
+    }
+
+    /// Get the **decoded** content of a file on the default branch of a repository.
+    fn get_file_content(&self, repo: &GitHubRepo, path: &str) -> impl Future<Output = Result<String>> + Send;
+
+    /// Get the SHA of the latest commit of a branch.
+    fn get_branch_sha(&self, repo: &GitHubRepo, branch: &str) -> impl Future<Output = Result<String>> + Send;
+
+    /// Create the branch `branch` in `repo`, pointing at the head of the branch `base` of `upstream`.
+    fn create_reference_from(
+        &self,
+        repo: &GitHubRepo,
+        branch: &str,
+        upstream: &GitHubRepo,
+        base: &str,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// Get the blob SHA of a file on a branch.
+    fn get_file_sha(&self, repo: &GitHubRepo, branch: &str, path: &str) -> impl Future<Output = Result<String>> + Send;
+
+    /// Create a new file on a branch.
+    fn create_file(
+        &self,
+        repo: &GitHubRepo,
+        branch: &str,
+        path: &str,
+        content: &str,
+        message: &str,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// Update an existing file on a branch, whose current blob SHA is `sha`.
+    fn update_file(
+        &self,
+        repo: &GitHubRepo,
+        branch: &str,
+        path: &str,
+        content: &str,
+        sha: &str,
+        message: &str,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// Commit several [FileChange](FileChange)s to a branch as a single commit, returning the SHA of the commit.
+    fn commit_files(
+        &self,
+        repo: &GitHubRepo,
+        branch: &str,
+        changes: &[FileChange],
+        commit_message: &str,
+    ) -> impl Future<Output = Result<String>> + Send;
+
+    /// Open a pull request (or merge request) from the branch `head` of `contributor` to the branch `base` of `upstream`.
+    #[allow(clippy::too_many_arguments)]
+    fn create_pull_request(
+        &self,
+        upstream: &GitHubRepo,
+        contributor: &GitHubRepo,
+        title: &str,
+        body: &str,
+        head: &str,
+        base: &str,
+        draft: bool,
+    ) -> impl Future<Output = Result<PullRequest>> + Send;
+
+    /// Add labels to a pull request.
+    fn add_labels(&self, upstream: &GitHubRepo, number: u64, labels: &[String]) -> impl Future<Output = Result<()>> + Send;
+
+    /// Request reviews of a pull request.
+    fn request_reviewers(&self, upstream: &GitHubRepo, number: u64, reviewers: &[String]) -> impl Future<Output = Result<()>> + Send;
+
+    /// Assign users to a pull request.
+    fn add_assignees(&self, upstream: &GitHubRepo, number: u64, assignees: &[String]) -> impl Future<Output = Result<()>> + Send;
+
+    /// Get the URL to clone a repository from with `git`.
+    fn get_clone_url(&self, _repo: &GitHubRepo) -> impl Future<Output = Result<String>> + Send {
+        async { Err(unsupported("Cloning repositories")) }
+    }
+
+    /// Compare the branch `branch` of `fork` with the branch of the same name in `upstream`.
+    fn fork_status(
+        &self,
+        _fork: &GitHubRepo,
+        _upstream: &GitHubRepo,
+        _branch: &str,
+    ) -> impl Future<Output = Result<ForkStatus>> + Send {
+        async { Err(unsupported("Comparing forks")) }
+    }
+
+    /// Synchronize the branch `branch` of `fork` with the branch of the same name in its upstream repository.
+    fn sync_fork(&self, _fork: &GitHubRepo, _branch: &str) -> impl Future<Output = Result<()>> + Send {
+        async { Err(unsupported("Synchronizing forks")) }
+    }
+
+    /// List every branch of a repository, sorted by name.
+    fn list_branches(&self, _repo: &GitHubRepo) -> impl Future<Output = Result<Vec<Branch>>> + Send {
+        async { Err(unsupported("Listing branches")) }
+    }
+
+    /// Delete a branch of a repository.
+    fn delete_branch(&self, _repo: &GitHubRepo, _branch: &str) -> impl Future<Output = Result<()>> + Send {
+        async { Err(unsupported("Deleting branches")) }
+    }
+
+    /// List the files and directories of a repository at a reference (a branch or a commit SHA), without their content.
+    fn get_tree(&self, _repo: &GitHubRepo, _ref: &str, _recursive: bool) -> impl Future<Output = Result<RepoTree>> + Send {
+        async { Err(unsupported("Listing repository trees")) }
+    }
+
+    /// Get the raw content of a file from its blob SHA, e.g. the SHA of a [TreeEntry](crate::models::TreeEntry).
+    fn get_blob(&self, _repo: &GitHubRepo, _sha: &str) -> impl Future<Output = Result<Vec<u8>>> + Send {
+        async { Err(unsupported("Getting blobs")) }
+    }
+
+    /// List every open pull request against `upstream`, whoever opened it, from the newest to the oldest.
+    fn list_open_pull_requests(&self, _upstream: &GitHubRepo) -> impl Future<Output = Result<Vec<PullRequest>>> + Send {
+        async { Err(unsupported("Listing pull requests")) }
+    }
+
+    /// List the open pull requests opened by the signed in user against `upstream`, from the newest to the oldest.
+    fn list_user_pull_requests(&self, _upstream: &GitHubRepo) -> impl Future<Output = Result<Vec<PullRequest>>> + Send {
+        async { Err(unsupported("Listing pull requests")) }
+    }
+
+    /// List every pull request from the branch `branch` of `contributor` against `upstream`, whatever its state,
+    /// from the newest to the oldest.
+    fn list_branch_pull_requests(
+        &self,
+        _upstream: &GitHubRepo,
+        _contributor: &GitHubRepo,
+        _branch: &str,
+    ) -> impl Future<Output = Result<Vec<PullRequest>>> + Send {
+        async { Err(unsupported("Listing pull requests")) }
+    }
+
+    /// List the files added, changed or removed by a pull request, sorted by path.
+    fn list_pull_request_files(
+        &self,
+        _upstream: &GitHubRepo,
+        _number: u64,
+    ) -> impl Future<Output = Result<Vec<PullRequestFile>>> + Send {
+        async { Err(unsupported("Listing the files of pull requests")) }
+    }
+
+    /// Get the review decision, mergeability and checks of a pull request.
+    fn pull_request_status(&self, _upstream: &GitHubRepo, _number: u64) -> impl Future<Output = Result<PullRequestStatus>> + Send {
+        async { Err(unsupported("Getting the status of pull requests")) }
+    }
+
+    /// Close a pull request without merging it, returning the closed pull request.
+    fn close_pull_request(&self, _upstream: &GitHubRepo, _number: u64) -> impl Future<Output = Result<PullRequest>> + Send {
+        async { Err(unsupported("Closing pull requests")) }
+    }
+
+    /// Mark a draft pull request against `upstream` as ready for review.
+    fn mark_ready_for_review(
+        &self,
+        _upstream: &GitHubRepo,
+        _pull_request: &PullRequest,
+    ) -> impl Future<Output = Result<()>> + Send {
+        async { Err(unsupported("Marking pull requests as ready for review")) }
+    }
+}
+
+impl Forge for GitHubApi {
+    fn username(&self) -> &str {
+        GitHubApi::username(self)
+    }
+
+    async fn get_user(&self) -> Result<String> {
+        GitHubApi::get_user(self).await
+    }
+
+    async fn validate_repo(&self, repo: &GitHubRepo, upstream: &GitHubRepo) -> Result<()> {
+        GitHubApi::validate_repo(self, repo.clone(), upstream.clone()).await
+    }
+
+    async fn get_user_forks(&self, upstream: &GitHubRepo) -> Result<Vec<GitHubRepo>> {
+        GitHubApi::get_user_forks(self, upstream).await
+    }
+
+    async fn create_fork(&self, repo: &GitHubRepo, upstream: &GitHubRepo) -> Result<GitHubRepo> {
+        GitHubApi::create_fork(self, repo.clone(), upstream.clone()).await
+    }
+
+    async fn create_fork_with_progress(
+        &self,
+        repo: &GitHubRepo,
+        upstream: &GitHubRepo,
+        progress: impl Fn(Duration) + Send + Sync,
+    ) -> Result<GitHubRepo> {
+        GitHubApi::create_fork_with_progress(self, repo.clone(), upstream.clone(), progress).await
+    }
+
+    async fn get_file_content(&self, repo: &GitHubRepo, path: &str) -> Result<String> {
+        GitHubApi::get_file_content(self, repo, path).await
+    }
+
+    async fn get_branch_sha(&self, repo: &GitHubRepo, branch: &str) -> Result<String> {
+        GitHubApi::get_branch_sha(self, repo, branch).await
+    }
+
+    async fn create_reference_from(&self, repo: &GitHubRepo, branch: &str, upstream: &GitHubRepo, base: &str) -> Result<()> {
+        GitHubApi::create_reference_from(self, repo, branch, upstream, base).await
+    }
+
+    async fn get_file_sha(&self, repo: &GitHubRepo, branch: &str, path: &str) -> Result<String> {
+        GitHubApi::get_file_sha(self, repo, branch, path).await
+    }
+
+    async fn create_file(&self, repo: &GitHubRepo, branch: &str, path: &str, content: &str, message: &str) -> Result<()> {
+        GitHubApi::create_file(self, repo, branch, path, content, message).await
+    }
+
+    async fn update_file(
+        &self,
+        repo: &GitHubRepo,
+        branch: &str,
+        path: &str,
+        content: &str,
+        sha: &str,
+        message: &str,
+    ) -> Result<()> {
+        GitHubApi::update_file(self, repo, branch, path, content, sha, message).await
+    }
+
+    async fn commit_files(&self, repo: &GitHubRepo, branch: &str, changes: &[FileChange], commit_message: &str) -> Result<String> {
+        GitHubApi::commit_files(self, repo, branch, changes, commit_message).await
+    }
+
+    async fn create_pull_request(
+        &self,
+        upstream: &GitHubRepo,
+        contributor: &GitHubRepo,
+        title: &str,
+        body: &str,
+        head: &str,
+        base: &str,
+        draft: bool,
+    ) -> Result<PullRequest> {
+        GitHubApi::create_pull_request(self, upstream, contributor, title, body, head, base, draft).await
+    }
+
+    async fn add_labels(&self, upstream: &GitHubRepo, number: u64, labels: &[String]) -> Result<()> {
+        GitHubApi::add_labels(self, upstream, number, labels).await
+    }
+
+    async fn request_reviewers(&self, upstream: &GitHubRepo, number: u64, reviewers: &[String]) -> Result<()> {
+        GitHubApi::request_reviewers(self, upstream, number, reviewers).await
+    }
+
+    async fn add_assignees(&self, upstream: &GitHubRepo, number: u64, assignees: &[String]) -> Result<()> {
+        GitHubApi::add_assignees(self, upstream, number, assignees).await
+    }
+
+    async fn get_clone_url(&self, repo: &GitHubRepo) -> Result<String> {
+        GitHubApi::get_clone_url(self, repo).await
+    }
+
+    async fn fork_status(&self, fork: &GitHubRepo, upstream: &GitHubRepo, branch: &str) -> Result<ForkStatus> {
+        GitHubApi::fork_status(self, fork, upstream, branch).await
+    }
+
+    async fn sync_fork(&self, fork: &GitHubRepo, branch: &str) -> Result<()> {
+        GitHubApi::sync_fork(self, fork, branch).await
+    }
+
+    async fn list_branches(&self, repo: &GitHubRepo) -> Result<Vec<Branch>> {
+        GitHubApi::list_branches(self, repo).await
+    }
+
+    async fn delete_branch(&self, repo: &GitHubRepo, branch: &str) -> Result<()> {
+        GitHubApi::delete_branch(self, repo, branch).await
+    }
+
+    async fn get_tree(&self, repo: &GitHubRepo, r#ref: &str, recursive: bool) -> Result<RepoTree> {
+        GitHubApi::get_tree(self, repo, r#ref, recursive).await
+    }
+
+    async fn get_blob(&self, repo: &GitHubRepo, sha: &str) -> Result<Vec<u8>> {
+        GitHubApi::get_blob(self, repo, sha).await
+    }
+
+    async fn list_open_pull_requests(&self, upstream: &GitHubRepo) -> Result<Vec<PullRequest>> {
+        GitHubApi::list_open_pull_requests(self, upstream).await
+    }
+
+    async fn list_user_pull_requests(&self, upstream: &GitHubRepo) -> Result<Vec<PullRequest>> {
+        GitHubApi::list_user_pull_requests(self, upstream).await
+    }
+
+    async fn list_branch_pull_requests(&self, upstream: &GitHubRepo, contributor: &GitHubRepo, branch: &str) -> Result<Vec<PullRequest>> {
+        GitHubApi::list_branch_pull_requests(self, upstream, contributor, branch).await
+    }
+
+    async fn list_pull_request_files(&self, upstream: &GitHubRepo, number: u64) -> Result<Vec<PullRequestFile>> {
+        GitHubApi::list_pull_request_files(self, upstream, number).await
+    }
+
+    async fn pull_request_status(&self, upstream: &GitHubRepo, number: u64) -> Result<PullRequestStatus> {
+        GitHubApi::pull_request_status(self, upstream, number).await
+    }
+
+    async fn close_pull_request(&self, upstream: &GitHubRepo, number: u64) -> Result<PullRequest> {
+        GitHubApi::close_pull_request(self, upstream, number).await
+    }
+
+    async fn mark_ready_for_review(&self, _upstream: &GitHubRepo, pull_request: &PullRequest) -> Result<()> {
+        GitHubApi::mark_ready_for_review(self, pull_request).await
+    }
+}
@@ -0,0 +1,669 @@
+use std::collections::HashMap;
+
+use base64::Engine;
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+
+use crate::apis::Forge;
+use crate::error::{Error, Result};
+use crate::models::gitea_api_responses::{branch, contents, git_data, issue, pull_request, repository};
+use crate::models::{
+    Branch, CheckRun, FileChange, ForkStatus, GitHubRepo, PullRequest, PullRequestFile, PullRequestStatus, RepoTree, ReviewDecision,
+    TreeEntry,
+};
+
+/// The number of items requested per page from list endpoints.
+const PAGE_SIZE: usize = 50;
+
+/// The title prefixes Gitea and Forgejo mark draft (work in progress) pull requests with by default.
+const DRAFT_PREFIXES: [&str; 2] = ["WIP:", "[WIP]"];
+
+/// A wrapper around the API of a Gitea or Forgejo instance, implementing the [Forge](Forge) trait.
+///
+/// # Fields
+/// - `username`: The username of the signed in user.
+/// - `base_url`: The URL of the instance, e.g. `https://forgejo.example.com`. The API is served under `/api/v1`.
+/// - `token`: The access token of the signed in user.
+/// - `client`: The HTTP client that is used to send requests.
+pub struct GiteaApi {
+    username: String,
+    base_url: String,
+    token: String,
+    client: reqwest::Client,
+}
+
+impl GiteaApi {
+    pub fn new(username: String, base_url: String, token: String) -> Self {
+        Self {
+            username,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Send a request to a route of the API, deserializing the response.
+    ///
+    /// Failed requests are mapped to the matching [`Error`](Error) variant based on the status code.
+    async fn send<T: DeserializeOwned>(&self, method: Method, route: &str, body: Option<&serde_json::Value>) -> Result<T> {
+        let mut request = self.client.request(method, format!("{}/api/v1{}", self.base_url, route))
+            .header(reqwest::header::AUTHORIZATION, format!("token {}", self.token))
+            .header(reqwest::header::ACCEPT, "application/json");
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        let response = request.send().await?;
+
+        let status = response.status();
+        let text = response.text().await?;
+        if !status.is_success() {
+            // The error message is in the `message` field of the body, if there is one.
+            let message = serde_json::from_str::<serde_json::Value>(&text).ok()
+                .and_then(|body| body.get("message").and_then(|message| message.as_str()).map(str::to_string))
+                .unwrap_or(text);
+            return Err(match status {
+                StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Error::Unauthorized(message),
+                StatusCode::NOT_FOUND => Error::NotFound(message),
+                StatusCode::CONFLICT => Error::Conflict(message),
+                StatusCode::TOO_MANY_REQUESTS => Error::RateLimited(message),
+                _ => Error::Forge(format!("{}: {}", status, message)),
+            });
+        }
+
+        // Responses without a body (e.g. `204 No Content`) are deserialized from `null`.
+        let text = if text.trim().is_empty() { "null" } else { &text };
+        serde_json::from_str(text).map_err(|e| Error::parse("the Gitea API response", e))
+    }
+
+    async fn get<T: DeserializeOwned>(&self, route: impl AsRef<str>) -> Result<T> {
+        self.send(Method::GET, route.as_ref(), None).await
+    }
+
+    async fn post<T: DeserializeOwned>(&self, route: impl AsRef<str>, body: &serde_json::Value) -> Result<T> {
+        self.send(Method::POST, route.as_ref(), Some(body)).await
+    }
+
+    async fn put<T: DeserializeOwned>(&self, route: impl AsRef<str>, body: &serde_json::Value) -> Result<T> {
+        self.send(Method::PUT, route.as_ref(), Some(body)).await
+    }
+
+    async fn patch<T: DeserializeOwned>(&self, route: impl AsRef<str>, body: &serde_json::Value) -> Result<T> {
+        self.send(Method::PATCH, route.as_ref(), Some(body)).await
+    }
+
+    async fn delete(&self, route: impl AsRef<str>) -> Result<()> {
+        let _: serde_json::Value = self.send(Method::DELETE, route.as_ref(), None).await?;
+        Ok(())
+    }
+
+    /// Get the content of a file on a branch, `None` for the default branch.
+    async fn get_contents(&self, repo: &GitHubRepo, branch: Option<&str>, path: &str) -> Result<contents::ContentsResponse> {
+        let route = format!("/repos/{}/{}/contents/{}", repo.owner, repo.name, path.trim_start_matches('/'));
+        let route = match branch {
+            Some(branch) => format!("{}?ref={}", route, branch),
+            None => route,
+        };
+        self.get(route).await.map_err(|e| match e {
+            Error::NotFound(_) => Error::NotFound(format!("file {} in {}", path, repo.get_full_name())),
+            e => e,
+        })
+    }
+
+    /// Check whether a repository contains a commit.
+    async fn has_commit(&self, repo: &GitHubRepo, sha: &str) -> Result<bool> {
+        match self.get::<serde_json::Value>(format!("/repos/{}/{}/git/commits/{}", repo.owner, repo.name, sha)).await {
+            Ok(_) => Ok(true),
+            Err(Error::NotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get a pull request against `upstream`.
+    async fn get_pull_request(&self, upstream: &GitHubRepo, number: u64) -> Result<pull_request::PullRequest> {
+        self.get(format!("/repos/{}/{}/pulls/{}", upstream.owner, upstream.name, number)).await
+            .map_err(|e| match e {
+                Error::NotFound(_) => Error::NotFound(format!("pull request #{} in {}", number, upstream.get_full_name())),
+                e => e,
+            })
+    }
+
+    /// Count the commits of the branch `head_branch` of `head` that are not in the branch `base_branch` of `base`.
+    ///
+    /// `head` has to be `base`, a fork of it or its parent.
+    async fn count_commits_ahead(&self, base: &GitHubRepo, base_branch: &str, head: &GitHubRepo, head_branch: &str) -> Result<u64> {
+        let compare: git_data::Compare = self.get(format!(
+            "/repos/{}/{}/compare/{}...{}:{}", base.owner, base.name, base_branch, head.owner, head_branch,
+        )).await?;
+        Ok(compare.total_commits)
+    }
+}
+
+impl Forge for GiteaApi {
+    fn username(&self) -> &str {
+        &self.username
+    }
+
+    async fn get_user(&self) -> Result<String> {
+        let user: repository::User = self.get("/user").await?;
+        Ok(user.login)
+    }
+
+    async fn validate_repo(&self, repo: &GitHubRepo, upstream: &GitHubRepo) -> Result<()> {
+        let full_name = repo.get_full_name();
+        let repository: repository::Repository = self.get(format!("/repos/{}/{}", repo.owner, repo.name))
+            .await
+            .map_err(|e| match e {
+                Error::NotFound(_) => Error::NotFound(format!("repository {}", full_name)),
+                e => e,
+            })?;
+
+        let parent = match repository.parent {
+            Some(parent) if repository.fork => parent,
+            _ => return Err(Error::NotAFork(full_name)),
+        };
+        if !parent.full_name.eq_ignore_ascii_case(&upstream.get_full_name()) {
+            return Err(Error::WrongParent {
+                repo: full_name,
+                expected: upstream.get_full_name(),
+                actual: parent.full_name,
+            });
+        }
+        Ok(())
+    }
+
+    async fn get_user_forks(&self, upstream: &GitHubRepo) -> Result<Vec<GitHubRepo>> {
+        let upstream_full_name = upstream.get_full_name();
+        let mut forks = Vec::new();
+
+        // The repositories of the user include the repositories of the organizations the user is a member of.
+        for page in 1.. {
+            let repositories: Vec<repository::Repository> = self.get(
+                format!("/user/repos?page={}&limit={}", page, PAGE_SIZE),
+            ).await?;
+            let last_page = repositories.len() < PAGE_SIZE;
+
+            forks.extend(repositories.into_iter()
+                .filter(|repo| repo.fork && repo.parent.as_ref().is_some_and(
+                    |parent| parent.full_name.eq_ignore_ascii_case(&upstream_full_name)
+                ))
+                .map(|repo| GitHubRepo::new(repo.owner.login, repo.name)));
+
+            if last_page {
+                break;
+            }
+        }
+
+        forks.sort_by_key(|fork| (fork.owner != self.username, fork.name != upstream.name));
+        Ok(forks)
+    }
+
+    async fn create_fork(&self, repo: &GitHubRepo, upstream: &GitHubRepo) -> Result<GitHubRepo> {
+        // Forks are created in the account of the user, unless an organization is given.
+        let mut body = serde_json::json!({ "name": repo.name });
+        if repo.owner != self.username {
+            body["organization"] = serde_json::json!(repo.owner);
+        }
+        let fork: repository::Repository = self.post(
+            format!("/repos/{}/{}/forks", upstream.owner, upstream.name), &body,
+        ).await?;
+        Ok(GitHubRepo::new(fork.owner.login, fork.name))
+    }
+
+    async fn get_file_content(&self, repo: &GitHubRepo, path: &str) -> Result<String> {
+        let file = self.get_contents(repo, None, path).await?;
+        let content = file.content
+            .ok_or_else(|| Error::NotFound(format!("content of the file {} in {}", path, repo.get_full_name())))?;
+        // The encoded content may be wrapped in lines.
+        let content: String = content.split_whitespace().collect();
+        let content = base64::prelude::BASE64_STANDARD.decode(content)
+            .map_err(|e| Error::parse(format!("the content of the file {}", path), e))?;
+        String::from_utf8(content).map_err(|e| Error::parse(format!("the content of the file {}", path), e))
+    }
+
+    async fn get_branch_sha(&self, repo: &GitHubRepo, branch: &str) -> Result<String> {
+        let branch_info: branch::Branch = self.get(format!("/repos/{}/{}/branches/{}", repo.owner, repo.name, branch))
+            .await
+            .map_err(|e| match e {
+                Error::NotFound(_) => Error::NotFound(format!("branch {} in {}", branch, repo.get_full_name())),
+                e => e,
+            })?;
+        Ok(branch_info.commit.id)
+    }
+
+    async fn create_reference_from(&self, repo: &GitHubRepo, branch: &str, upstream: &GitHubRepo, base: &str) -> Result<()> {
+        let sha = self.get_branch_sha(upstream, base).await?;
+        // A fork is a repository of its own on Gitea and Forgejo, which only has the commits of the upstream repository
+        // it was forked or synchronized with, so its base branch is synchronized first if the commit is missing.
+        if repo != upstream && !self.has_commit(repo, &sha).await? {
+            self.sync_fork(repo, base).await?;
+        }
+        let _: branch::Branch = self.post(
+            format!("/repos/{}/{}/branches", repo.owner, repo.name),
+            &serde_json::json!({
+                "new_branch_name": branch,
+                "old_ref_name": sha,
+            }),
+        ).await?;
+        Ok(())
+    }
+
+    async fn get_file_sha(&self, repo: &GitHubRepo, branch: &str, path: &str) -> Result<String> {
+        Ok(self.get_contents(repo, Some(branch), path).await?.sha)
+    }
+
+    async fn create_file(&self, repo: &GitHubRepo, branch: &str, path: &str, content: &str, message: &str) -> Result<()> {
+        let _: serde_json::Value = self.post(
+            format!("/repos/{}/{}/contents/{}", repo.owner, repo.name, path),
+            &serde_json::json!({
+                "branch": branch,
+                "content": base64::prelude::BASE64_STANDARD.encode(content),
+                "message": message,
+            }),
+        ).await?;
+        Ok(())
+    }
+
+    async fn update_file(
+        &self,
+        repo: &GitHubRepo,
+        branch: &str,
+        path: &str,
+        content: &str,
+        sha: &str,
+        message: &str,
+    ) -> Result<()> {
+        let _: serde_json::Value = self.put(
+            format!("/repos/{}/{}/contents/{}", repo.owner, repo.name, path),
+            &serde_json::json!({
+                "branch": branch,
+                "content": base64::prelude::BASE64_STANDARD.encode(content),
+                "sha": sha,
+                "message": message,
+            }),
+        ).await?;
+        Ok(())
+    }
+
+    async fn commit_files(&self, repo: &GitHubRepo, branch: &str, changes: &[FileChange], commit_message: &str) -> Result<String> {
+        // Updating, deleting and moving a file require its current SHA, and moving it requires its content as well.
+        let mut files = Vec::new();
+        for change in changes {
+            match change {
+                FileChange::Write { path, content } => {
+                    let file = match self.get_contents(repo, Some(branch), path).await {
+                        Ok(file) => Some(file),
+                        Err(Error::NotFound(_)) => None,
+                        Err(e) => return Err(e),
+                    };
+                    files.push(serde_json::json!({
+                        "operation": if file.is_some() { "update" } else { "create" },
+                        "path": path,
+                        "content": base64::prelude::BASE64_STANDARD.encode(content),
+                        "sha": file.map(|file| file.sha),
+                    }));
+                }
+                FileChange::Delete { path } => {
+                    let file = self.get_contents(repo, Some(branch), path).await?;
+                    files.push(serde_json::json!({
+                        "operation": "delete",
+                        "path": path,
+                        "sha": file.sha,
+                    }));
+                }
+                FileChange::Rename { from, to } => {
+                    let file = self.get_contents(repo, Some(branch), from).await?;
+                    let content: String = file.content.unwrap_or_default().split_whitespace().collect();
+                    files.push(serde_json::json!({
+                        "operation": "update",
+                        "from_path": from,
+                        "path": to,
+                        "content": content,
+                        "sha": file.sha,
+                    }));
+                }
+            }
+        }
+
+        let response: contents::FilesResponse = self.post(
+            format!("/repos/{}/{}/contents", repo.owner, repo.name),
+            &serde_json::json!({
+                "branch": branch,
+                "message": commit_message,
+                "files": files,
+            }),
+        ).await?;
+        Ok(response.commit.sha)
+    }
+
+    async fn create_pull_request(
+        &self,
+        upstream: &GitHubRepo,
+        contributor: &GitHubRepo,
+        title: &str,
+        body: &str,
+        head: &str,
+        base: &str,
+        draft: bool,
+    ) -> Result<PullRequest> {
+        // Gitea and Forgejo mark a pull request as a draft (work in progress) with a prefix of its title.
+        let title = if draft { format!("WIP: {}", title) } else { title.to_string() };
+        let pull_request: pull_request::PullRequest = self.post(
+            format!("/repos/{}/{}/pulls", upstream.owner, upstream.name),
+            &serde_json::json!({
+                "title": title,
+                "body": body,
+                "head": format!("{}:{}", contributor.owner, head),
+                "base": base,
+            }),
+        ).await?;
+        Ok(pull_request.into())
+    }
+
+    async fn list_branch_pull_requests(&self, upstream: &GitHubRepo, contributor: &GitHubRepo, branch: &str) -> Result<Vec<PullRequest>> {
+        let mut pull_requests = Vec::new();
+
+        // Gitea and Forgejo cannot filter the pull requests by their head branch.
+        for page in 1.. {
+            let page_pull_requests: Vec<pull_request::PullRequest> = self.get(
+                format!("/repos/{}/{}/pulls?state=all&page={}&limit={}", upstream.owner, upstream.name, page, PAGE_SIZE),
+            ).await?;
+            let last_page = page_pull_requests.len() < PAGE_SIZE;
+
+            pull_requests.extend(page_pull_requests.into_iter()
+                .filter(|pr| pr.head.r#ref == branch && pr.head.repo.as_ref().is_some_and(|repo| {
+                    repo.owner.login.eq_ignore_ascii_case(&contributor.owner) && repo.name.eq_ignore_ascii_case(&contributor.name)
+                }))
+                .map(PullRequest::from));
+
+            if last_page {
+                break;
+            }
+        }
+
+        pull_requests.sort_by_key(|pr| std::cmp::Reverse(pr.number));
+        Ok(pull_requests)
+    }
+
+    async fn add_labels(&self, upstream: &GitHubRepo, number: u64, labels: &[String]) -> Result<()> {
+        let _: serde_json::Value = self.post(
+            format!("/repos/{}/{}/issues/{}/labels", upstream.owner, upstream.name, number),
+            &serde_json::json!({ "labels": labels }),
+        ).await?;
+        Ok(())
+    }
+
+    async fn request_reviewers(&self, upstream: &GitHubRepo, number: u64, reviewers: &[String]) -> Result<()> {
+        let _: serde_json::Value = self.post(
+            format!("/repos/{}/{}/pulls/{}/requested_reviewers", upstream.owner, upstream.name, number),
+            &serde_json::json!({ "reviewers": reviewers }),
+        ).await?;
+        Ok(())
+    }
+
+    async fn add_assignees(&self, upstream: &GitHubRepo, number: u64, assignees: &[String]) -> Result<()> {
+        let route = format!("/repos/{}/{}/issues/{}", upstream.owner, upstream.name, number);
+
+        // Editing the issue replaces its assignees, so the users that are already assigned are kept.
+        let issue: issue::Issue = self.get(route.clone()).await?;
+        let mut all_assignees: Vec<String> = issue.assignees.unwrap_or_default().into_iter()
+            .map(|user| user.login)
+            .collect();
+        for assignee in assignees {
+            if !all_assignees.iter().any(|login| login.eq_ignore_ascii_case(assignee)) {
+                all_assignees.push(assignee.clone());
+            }
+        }
+
+        let _: serde_json::Value = self.patch(route, &serde_json::json!({ "assignees": all_assignees })).await?;
+        Ok(())
+    }
+
+    async fn get_clone_url(&self, repo: &GitHubRepo) -> Result<String> {
+        let repository: repository::Repository = self.get(format!("/repos/{}/{}", repo.owner, repo.name)).await?;
+        repository.clone_url.ok_or_else(|| Error::NotFound(format!("clone URL of {}", repo.get_full_name())))
+    }
+
+    async fn fork_status(&self, fork: &GitHubRepo, upstream: &GitHubRepo, branch: &str) -> Result<ForkStatus> {
+        let ahead_by = self.count_commits_ahead(upstream, branch, fork, branch).await?;
+        let behind_by = self.count_commits_ahead(fork, branch, upstream, branch).await?;
+        Ok(ForkStatus::new(ahead_by, behind_by))
+    }
+
+    async fn sync_fork(&self, fork: &GitHubRepo, branch: &str) -> Result<()> {
+        let response: Result<git_data::MergeUpstream> = self.post(
+            format!("/repos/{}/{}/merge-upstream", fork.owner, fork.name),
+            &serde_json::json!({ "branch": branch }),
+        ).await;
+
+        match response {
+            Ok(_) => Ok(()),
+            Err(Error::Conflict(_)) => Err(Error::ForkDiverged(fork.get_full_name())),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn list_branches(&self, repo: &GitHubRepo) -> Result<Vec<Branch>> {
+        let mut branches = Vec::new();
+
+        for page in 1.. {
+            let page_branches: Vec<branch::Branch> = self.get(
+                format!("/repos/{}/{}/branches?page={}&limit={}", repo.owner, repo.name, page, PAGE_SIZE),
+            ).await?;
+            let last_page = page_branches.len() < PAGE_SIZE;
+
+            branches.extend(page_branches.into_iter().map(Branch::from));
+
+            if last_page {
+                break;
+            }
+        }
+
+        branches.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(branches)
+    }
+
+    async fn delete_branch(&self, repo: &GitHubRepo, branch: &str) -> Result<()> {
+        self.delete(format!("/repos/{}/{}/branches/{}", repo.owner, repo.name, branch)).await
+            .map_err(|e| match e {
+                Error::NotFound(_) => Error::NotFound(format!("branch {} in {}", branch, repo.get_full_name())),
+                e => e,
+            })
+    }
+
+    async fn get_tree(&self, repo: &GitHubRepo, r#ref: &str, recursive: bool) -> Result<RepoTree> {
+        // The route only takes a SHA or a reference without slashes, so the branches are resolved first.
+        let is_sha = r#ref.len() >= 40 && r#ref.chars().all(|c| c.is_ascii_hexdigit());
+        let sha = if is_sha { r#ref.to_string() } else { self.get_branch_sha(repo, r#ref).await? };
+
+        // Gitea and Forgejo list the entries of a tree in pages, the tree is truncated until the last page.
+        let mut entries = Vec::new();
+        let mut page = 1;
+        loop {
+            let tree: git_data::Tree = self.get(format!(
+                "/repos/{}/{}/git/trees/{}?recursive={}&page={}", repo.owner, repo.name, sha, recursive, page,
+            )).await?;
+            entries.extend(tree.tree.into_iter().map(TreeEntry::from));
+
+            if !tree.truncated {
+                return Ok(RepoTree::new(tree.sha, entries));
+            }
+            page += 1;
+        }
+    }
+
+    async fn get_blob(&self, repo: &GitHubRepo, sha: &str) -> Result<Vec<u8>> {
+        let blob: git_data::BlobContent = self.get(format!("/repos/{}/{}/git/blobs/{}", repo.owner, repo.name, sha))
+            .await
+            .map_err(|e| match e {
+                Error::NotFound(_) => Error::NotFound(format!("blob {} in {}", sha, repo.get_full_name())),
+                e => e,
+            })?;
+
+        let content = blob.content.unwrap_or_default();
+        match blob.encoding.as_deref() {
+            Some("base64") => {
+                // The encoded content may be wrapped in lines.
+                let content: String = content.split_whitespace().collect();
+                base64::prelude::BASE64_STANDARD.decode(content)
+                    .map_err(|e| Error::parse(format!("the content of the blob {}", sha), e))
+            }
+            _ => Ok(content.into_bytes()),
+        }
+    }
+
+    async fn list_open_pull_requests(&self, upstream: &GitHubRepo) -> Result<Vec<PullRequest>> {
+        let mut pull_requests = Vec::new();
+
+        for page in 1.. {
+            let page_pull_requests: Vec<pull_request::PullRequest> = self.get(
+                format!("/repos/{}/{}/pulls?state=open&page={}&limit={}", upstream.owner, upstream.name, page, PAGE_SIZE),
+            ).await?;
+            let last_page = page_pull_requests.len() < PAGE_SIZE;
+
+            pull_requests.extend(page_pull_requests.into_iter().map(PullRequest::from));
+
+            if last_page {
+                break;
+            }
+        }
+
+        pull_requests.sort_by_key(|pr| std::cmp::Reverse(pr.number));
+        Ok(pull_requests)
+    }
+
+    async fn list_user_pull_requests(&self, upstream: &GitHubRepo) -> Result<Vec<PullRequest>> {
+        let upstream_full_name = upstream.get_full_name();
+        let mut numbers = Vec::new();
+
+        // The search only returns the pull requests as issues, which do not have their branches.
+        for page in 1.. {
+            let issues: Vec<issue::Issue> = self.get(format!(
+                "/repos/issues/search?type=pulls&created=true&state=open&owner={}&page={}&limit={}",
+                upstream.owner, page, PAGE_SIZE,
+            )).await?;
+            let last_page = issues.len() < PAGE_SIZE;
+
+            numbers.extend(issues.into_iter()
+                .filter(|issue| issue.repository.as_ref().is_some_and(
+                    |repo| repo.full_name.eq_ignore_ascii_case(&upstream_full_name)
+                ))
+                .map(|issue| issue.number));
+
+            if last_page {
+                break;
+            }
+        }
+
+        let mut pull_requests = Vec::new();
+        for number in numbers {
+            pull_requests.push(PullRequest::from(self.get_pull_request(upstream, number).await?));
+        }
+        pull_requests.sort_by_key(|pr| std::cmp::Reverse(pr.number));
+        Ok(pull_requests)
+    }
+
+    async fn list_pull_request_files(&self, upstream: &GitHubRepo, number: u64) -> Result<Vec<PullRequestFile>> {
+        let pull_request = PullRequest::from(self.get_pull_request(upstream, number).await?);
+        let head_repo = pull_request.head_repo.as_ref().unwrap_or(upstream);
+
+        let mut changed_files = Vec::new();
+        for page in 1.. {
+            let page_files: Vec<pull_request::ChangedFile> = self.get(format!(
+                "/repos/{}/{}/pulls/{}/files?page={}&limit={}", upstream.owner, upstream.name, number, page, PAGE_SIZE,
+            )).await?;
+            let last_page = page_files.len() < PAGE_SIZE;
+            changed_files.extend(page_files);
+            if last_page {
+                break;
+            }
+        }
+
+        // The changed files do not have their blob SHA, which is read from the head commit of the pull request.
+        let mut files = Vec::new();
+        for file in changed_files {
+            let sha = if file.status == "deleted" {
+                None
+            } else {
+                Some(self.get_contents(head_repo, Some(&pull_request.head_sha), &file.filename).await?.sha)
+            };
+            files.push(PullRequestFile { path: file.filename, sha });
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
+    async fn pull_request_status(&self, upstream: &GitHubRepo, number: u64) -> Result<PullRequestStatus> {
+        let pull_request = self.get_pull_request(upstream, number).await?;
+        let mergeable = pull_request.mergeable;
+        let pull_request = PullRequest::from(pull_request);
+
+        let reviews: Vec<pull_request::PullReview> = self.get(
+            format!("/repos/{}/{}/pulls/{}/reviews", upstream.owner, upstream.name, number),
+        ).await?;
+        // Only the latest approval or change request of each reviewer counts, just like on GitHub.
+        let mut decisions: HashMap<String, ReviewDecision> = HashMap::new();
+        for review in reviews {
+            let Some(user) = review.user else {
+                continue;
+            };
+            let decision = match review.state.as_str() {
+                _ if review.dismissed => ReviewDecision::Pending,
+                "APPROVED" => ReviewDecision::Approved,
+                "REQUEST_CHANGES" => ReviewDecision::ChangesRequested,
+                _ => continue,
+            };
+            decisions.insert(user.login, decision);
+        }
+        let review_decision = if decisions.values().any(|decision| *decision == ReviewDecision::ChangesRequested) {
+            ReviewDecision::ChangesRequested
+        } else if decisions.values().any(|decision| *decision == ReviewDecision::Approved) {
+            ReviewDecision::Approved
+        } else {
+            ReviewDecision::Pending
+        };
+
+        // Gitea and Forgejo report the checks (e.g. Actions jobs) as commit statuses.
+        let status: pull_request::CombinedStatus = self.get(format!(
+            "/repos/{}/{}/commits/{}/status", upstream.owner, upstream.name, pull_request.head_sha,
+        )).await?;
+        let checks = status.statuses.unwrap_or_default().into_iter()
+            .map(|status| {
+                let completed = status.status != "pending";
+                CheckRun {
+                    name: status.context,
+                    status: if completed { "completed" } else { "in_progress" }.to_string(),
+                    conclusion: completed.then_some(status.status),
+                }
+            })
+            .collect();
+
+        Ok(PullRequestStatus {
+            pull_request,
+            review_decision,
+            mergeable,
+            // Gitea and Forgejo do not report why a pull request cannot be merged.
+            mergeable_state: None,
+            checks,
+        })
+    }
+
+    async fn close_pull_request(&self, upstream: &GitHubRepo, number: u64) -> Result<PullRequest> {
+        let pull_request: pull_request::PullRequest = self.patch(
+            format!("/repos/{}/{}/pulls/{}", upstream.owner, upstream.name, number),
+            &serde_json::json!({ "state": "closed" }),
+        ).await?;
+        Ok(pull_request.into())
+    }
+
+    async fn mark_ready_for_review(&self, upstream: &GitHubRepo, pull_request: &PullRequest) -> Result<()> {
+        // A pull request is marked as ready for review by removing the draft prefix from its title.
+        let Some(title) = DRAFT_PREFIXES.iter().find_map(|prefix| pull_request.title.strip_prefix(prefix)) else {
+            return Ok(());
+        };
+        let _: pull_request::PullRequest = self.patch(
+            format!("/repos/{}/{}/pulls/{}", upstream.owner, upstream.name, pull_request.number),
+            &serde_json::json!({ "title": title.trim_start() }),
+        ).await?;
+        Ok(())
+    }
+}
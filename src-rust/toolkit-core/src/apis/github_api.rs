@@ -1,47 +1,449 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+
 use futures::future::{BoxFuture, FutureExt};
+use futures::stream::{self, StreamExt, TryStreamExt};
 
+use octocrab::models::pulls::ReviewState;
 use octocrab::models::Repository;
 use octocrab::Octocrab;
 use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
 use serde_json;
+use crate::apis::{ForkWaitPolicy, RetryPolicy};
+use crate::error::{Error, Result};
 use crate::models::github_api_responses;
-use crate::models::GitHubRepo;
+use crate::models::{
+    Branch, CheckRun, FileChange, ForkStatus, GitHubRepo, PullRequest, PullRequestFile, PullRequestState, PullRequestStatus,
+    RateLimit, RepoTree, ReviewDecision, TokenCapabilities, TokenCapability, TreeEntry, TreeEntryKind,
+};
+
+/// The base URL of the GitHub.com uploads API.
+const UPLOAD_URL: &str = "https://uploads.github.com";
+/// The number of requests sent concurrently when walking the subtrees of a large tree.
+const TREE_WALK_CONCURRENCY: usize = 8;
 
 /// A wrapper around the GitHub API that provides a set of methods to interact with the GitHub API.
 ///
 /// # Fields
 /// - `username`: The username of the currently signed in GitHub user.
 /// - `octocrab`: The Octocrab client that is used to interact with the GitHub API.
+/// - `graphql`: The Octocrab client and the route of the GitHub GraphQL API, which is not under the REST API base URL on GitHub Enterprise Server.
+/// - `upload_url`: The base URL of the GitHub uploads API, which is not under the REST API base URL either.
+/// - `retry_policy`: The [RetryPolicy](RetryPolicy) of requests failing with a transient error.
+/// - `fork_wait_policy`: The [ForkWaitPolicy](ForkWaitPolicy) of waiting for a new fork to become available.
+/// - `rate_limit`: The latest known [RateLimit](RateLimit) of the user, updated from the headers of every response.
+/// - `token_capabilities`: The [TokenCapabilities](TokenCapabilities) of the token, `None` until they are checked.
 pub struct GitHubApi {
     username: String,
     octocrab: Octocrab,
+    graphql: (Octocrab, String),
+    upload_url: String,
+    retry_policy: RetryPolicy,
+    fork_wait_policy: ForkWaitPolicy,
+    rate_limit: Mutex<Option<RateLimit>>,
+    token_capabilities: Mutex<Option<TokenCapabilities>>,
+}
+
+/// The HTTP methods used by the request layer of [GitHubApi](GitHubApi).
+enum Method {
+    Get,
+    Post,
+    Patch,
+}
+
+/// Whether a failed request may have been handled by GitHub anyway, i.e. on a network error or a server error,
+/// which may happen after the change requested is made (e.g. if the connection drops before the response arrives).
+fn may_have_been_handled(error: &Error) -> bool {
+    match error {
+        Error::Network(_) => true,
+        Error::GitHub(e) => matches!(e.as_ref(), octocrab::Error::GitHub { source, .. } if source.status_code.is_server_error()),
+        _ => false,
+    }
+}
+
+/// Get the message of a request GitHub rejected with `422 Unprocessable Entity`, `None` for any other error.
+fn unprocessable_message(error: &octocrab::Error) -> Option<&str> {
+    match error {
+        octocrab::Error::GitHub { source, .. } if source.status_code == StatusCode::UNPROCESSABLE_ENTITY => {
+            Some(&source.message)
+        }
+        _ => None,
+    }
+}
+
+/// Read the rate limit of the user from the `X-RateLimit-*` headers of a response.
+fn rate_limit_from_headers(headers: &HeaderMap) -> Option<RateLimit> {
+    let header = |name: &str| headers.get(name)?.to_str().ok()?.parse::<u64>().ok();
+    Some(RateLimit::new(
+        header("x-ratelimit-limit")?,
+        header("x-ratelimit-remaining")?,
+        header("x-ratelimit-used").unwrap_or_default(),
+        header("x-ratelimit-reset")?,
+    ))
 }
 
 impl GitHubApi {
     pub fn new(username: String, octocrab: Octocrab) -> Self {
         Self {
             username,
+            graphql: (octocrab.clone(), "/graphql".to_string()),
+            upload_url: UPLOAD_URL.to_string(),
             octocrab,
+            retry_policy: RetryPolicy::default(),
+            fork_wait_policy: ForkWaitPolicy::default(),
+            rate_limit: Mutex::new(None),
+            token_capabilities: Mutex::new(None),
+        }
+    }
+
+    /// Send GraphQL queries to another endpoint than `/graphql` under the REST API base URL,
+    /// e.g. `/api/graphql` on GitHub Enterprise Server.
+    ///
+    /// # Arguments
+    /// - `octocrab`: The Octocrab client whose base URL the route is relative to.
+    /// - `route`: The route of the GraphQL API.
+    pub fn with_graphql_endpoint(mut self, octocrab: Octocrab, route: String) -> Self {
+        self.graphql = (octocrab, route);
+        self
+    }
+
+    /// Upload files to another base URL than `https://uploads.github.com`, e.g. `/api/uploads` on GitHub Enterprise Server.
+    ///
+    /// Octocrab only knows the REST API base URL, so the uploads API is reached through this URL.
+    pub fn with_upload_url(mut self, upload_url: String) -> Self {
+        self.upload_url = upload_url;
+        self
+    }
+
+    /// Get the base URL of the GitHub uploads API.
+    pub fn upload_url(&self) -> &str {
+        &self.upload_url
+    }
+
+    /// Use a different [RetryPolicy](RetryPolicy) for requests failing with a transient error.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Use a different [ForkWaitPolicy](ForkWaitPolicy) for waiting for a new fork to become available.
+    pub fn with_fork_wait_policy(mut self, fork_wait_policy: ForkWaitPolicy) -> Self {
+        self.fork_wait_policy = fork_wait_policy;
+        self
+    }
+
+    /// Get the latest known GitHub API quota of the user.
+    ///
+    /// # Returns
+    /// - `Option<RateLimit>`: The [RateLimit](RateLimit) read from the latest response, `None` if no response contained it yet.
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.rate_limit.lock().unwrap().clone()
+    }
+
+    /// Get the current GitHub API quota of the user from GitHub. This request does not count against the quota.
+    ///
+    /// # Returns
+    /// - `Result<RateLimit>`
+    ///     - `Ok(RateLimit)`: The current [RateLimit](RateLimit) of the user.
+    ///     - `Err(Error)`: An error indicating why the method failed.
+    pub async fn fetch_rate_limit(&self) -> Result<RateLimit> {
+        let response: github_api_responses::rate_limit::RateLimitResponse = self.get("/rate_limit").await?;
+        *self.rate_limit.lock().unwrap() = Some(response.rate.clone());
+        Ok(response.rate)
+    }
+
+    /// Get how long to wait before retrying a failed request, `None` if the request should not be retried.
+    ///
+    /// Network errors, server errors and secondary rate limits are transient, so they are retried.
+    /// An exhausted quota is not, as it can take up to an hour to reset.
+    ///
+    /// A request that is not `idempotent` (e.g. opening a pull request) may have been handled before a network error
+    /// or a server error, and sending it again could make the change twice, so it is only retried on a secondary rate limit,
+    /// which GitHub reports before handling the request.
+    fn retry_delay(&self, error: &Error, retry_after: Option<Duration>, attempt: u32, idempotent: bool) -> Option<Duration> {
+        if attempt >= self.retry_policy.max_retries {
+            return None;
+        }
+        let backoff = self.retry_policy.backoff(attempt);
+        match error {
+            e if may_have_been_handled(e) => idempotent.then_some(backoff),
+            // Secondary rate limits come with a `Retry-After` header, or a message mentioning them.
+            Error::RateLimited(message) if retry_after.is_some() || message.to_lowercase().contains("secondary") => {
+                Some(retry_after.unwrap_or(backoff))
+            }
+            _ => None,
+        }
+    }
+
+    /// Turn a rate limit error that cannot be retried into an [`Error::QuotaExhausted`](Error::QuotaExhausted)
+    /// if the quota of the user is exhausted.
+    async fn quota_error(&self, error: Error) -> Error {
+        if !matches!(error, Error::RateLimited(_)) {
+            return error;
+        }
+        let rate_limit = match self.rate_limit() {
+            Some(rate_limit) if rate_limit.is_exhausted() => Some(rate_limit),
+            // The typed method of Octocrab is used here, as the request layer reports its errors through this method.
+            _ => self.octocrab.ratelimit().get().await.ok().map(|response| RateLimit::new(
+                response.rate.limit as u64,
+                response.rate.remaining as u64,
+                response.rate.used as u64,
+                response.rate.reset,
+            )),
+        };
+        match rate_limit {
+            Some(rate_limit) if rate_limit.is_exhausted() => Error::QuotaExhausted {
+                limit: rate_limit.limit,
+                reset: rate_limit.reset,
+            },
+            _ => error,
+        }
+    }
+
+    /// Send a request with an Octocrab method, retrying it according to the [RetryPolicy](RetryPolicy).
+    ///
+    /// This is used for the typed methods of Octocrab, which do not expose the response headers.
+    /// Requests to a route should use [`get`](GitHubApi::get), [`post`](GitHubApi::post) or [`patch`](GitHubApi::patch) instead,
+    /// which also track the quota of the user.
+    ///
+    /// The request must be idempotent, e.g. a `GET` request. Requests that are not should use [`retry_write`](GitHubApi::retry_write).
+    async fn retry<T, F, Fut>(&self, request: F) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = octocrab::Result<T>>,
+    {
+        self.retry_request(request, true).await
+    }
+
+    /// Send a request that is not idempotent (e.g. creating a file) with an Octocrab method, like [`retry`](GitHubApi::retry),
+    /// but without retrying it if it may have been handled already.
+    ///
+    /// The caller can check whether the change has been made when [`may_have_been_handled`] holds for the error.
+    async fn retry_write<T, F, Fut>(&self, request: F) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = octocrab::Result<T>>,
+    {
+        self.retry_request(request, false).await
+    }
+
+    /// Send a request with an Octocrab method, retrying it as [`retry_delay`](GitHubApi::retry_delay) allows.
+    async fn retry_request<T, F, Fut>(&self, request: F, idempotent: bool) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = octocrab::Result<T>>,
+    {
+        let mut attempt = 0;
+        loop {
+            let error = match request().await {
+                Ok(response) => return Ok(response),
+                Err(e) => Error::from(e),
+            };
+            match self.retry_delay(&error, None, attempt, idempotent) {
+                Some(delay) => {
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                None => return Err(self.quota_error(error).await),
+            }
         }
     }
 
+    /// Send a request to a route of the GitHub API and deserialize the response.
+    ///
+    /// The quota of the user is updated from the response headers, and requests failing with a transient error
+    /// are retried according to the [RetryPolicy](RetryPolicy), if they are `idempotent`.
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        route: &str,
+        body: Option<&serde_json::Value>,
+        idempotent: bool,
+    ) -> Result<T> {
+        self.send_with_headers(&self.octocrab, method, route, body, idempotent).await.map(|(_, body)| body)
+    }
+
+    /// Send a request to a route of the GitHub API like [`send`](GitHubApi::send) with the given Octocrab client,
+    /// returning the response headers as well.
+    async fn send_with_headers<T: DeserializeOwned>(
+        &self,
+        octocrab: &Octocrab,
+        method: Method,
+        route: &str,
+        body: Option<&serde_json::Value>,
+        idempotent: bool,
+    ) -> Result<(HeaderMap, T)> {
+        let mut attempt = 0;
+        loop {
+            let response = match method {
+                Method::Get => octocrab._get(route).await,
+                Method::Post => octocrab._post(route, body).await,
+                Method::Patch => octocrab._patch(route, body).await,
+            };
+
+            let (error, retry_after) = match response {
+                Ok(response) => {
+                    let rate_limit = rate_limit_from_headers(response.headers());
+                    if let Some(rate_limit) = &rate_limit {
+                        *self.rate_limit.lock().unwrap() = Some(rate_limit.clone());
+                    }
+                    let retry_after = response.headers().get("retry-after")
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| value.parse::<u64>().ok())
+                        .map(Duration::from_secs);
+                    let status = response.status();
+                    let headers = response.headers().clone();
+
+                    match octocrab::map_github_error(response).await {
+                        Ok(response) => {
+                            let body = octocrab.body_to_string(response).await?;
+                            // Responses without a body (e.g. `204 No Content`) are deserialized from `null`.
+                            let body = if body.trim().is_empty() { "null" } else { &body };
+                            return serde_json::from_str(body)
+                                .map(|body| (headers, body))
+                                .map_err(|e| Error::parse("the GitHub API response", e));
+                        }
+                        // The body of a server error is not always JSON, so it is retried whatever the body is.
+                        Err(_) if status.is_server_error() && idempotent && attempt < self.retry_policy.max_retries => {
+                            tokio::time::sleep(self.retry_policy.backoff(attempt)).await;
+                            attempt += 1;
+                            continue;
+                        }
+                        Err(e) => match Error::from(e) {
+                            Error::RateLimited(_) if rate_limit.as_ref().is_some_and(RateLimit::is_exhausted) => {
+                                let rate_limit = rate_limit.unwrap();
+                                return Err(Error::QuotaExhausted {
+                                    limit: rate_limit.limit,
+                                    reset: rate_limit.reset,
+                                });
+                            }
+                            e => (e, retry_after),
+                        },
+                    }
+                }
+                Err(e) => (Error::from(e), None),
+            };
+
+            match self.retry_delay(&error, retry_after, attempt, idempotent) {
+                Some(delay) => {
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                None => return Err(self.quota_error(error).await),
+            }
+        }
+    }
+
+    /// Send a query or a mutation to the GitHub GraphQL API through the request layer.
+    ///
+    /// # Returns
+    /// - `Result<serde_json::Value>`
+    ///     - `Ok(serde_json::Value)`: The `data` of the response.
+    ///     - `Err(Error)`: [`Error::GraphQL`](Error::GraphQL) if the response contains errors, or another error indicating why the method failed.
+    async fn graphql(&self, query: &str, variables: serde_json::Value) -> Result<serde_json::Value> {
+        let (octocrab, route) = &self.graphql;
+        let body = serde_json::json!({
+            "query": query,
+            "variables": variables,
+        });
+        // The queries and mutations sent by the toolkit (e.g. marking a pull request as ready for review)
+        // have the same effect whether they are handled once or several times.
+        let (_, mut response): (HeaderMap, serde_json::Value) = self.send_with_headers(
+            octocrab, Method::Post, route, Some(&body), true,
+        ).await?;
+
+        // GraphQL errors are reported in the response body with a successful status code.
+        if let Some(errors) = response.get("errors").and_then(|errors| errors.as_array()) {
+            let messages: Vec<&str> = errors.iter()
+                .filter_map(|error| error.get("message").and_then(|message| message.as_str()))
+                .collect();
+            return Err(Error::GraphQL(messages.join("; ")));
+        }
+
+        Ok(response.get_mut("data").map(serde_json::Value::take).unwrap_or_default())
+    }
+
+    /// Send a `GET` request to a route of the GitHub API through the request layer.
+    async fn get<T: DeserializeOwned>(&self, route: impl AsRef<str>) -> Result<T> {
+        self.send(Method::Get, route.as_ref(), None, true).await
+    }
+
+    /// Send a `POST` request to a route of the GitHub API through the request layer.
+    ///
+    /// The request is not retried if it may have been handled already, see [`retry_write`](GitHubApi::retry_write).
+    async fn post<T: DeserializeOwned>(&self, route: impl AsRef<str>, body: &serde_json::Value) -> Result<T> {
+        self.send(Method::Post, route.as_ref(), Some(body), false).await
+    }
+
+    /// Send a `POST` request that has the same effect whether it is handled once or several times (e.g. adding labels)
+    /// to a route of the GitHub API through the request layer, retrying it like a `GET` request.
+    async fn post_idempotent<T: DeserializeOwned>(&self, route: impl AsRef<str>, body: &serde_json::Value) -> Result<T> {
+        self.send(Method::Post, route.as_ref(), Some(body), true).await
+    }
+
+    /// Send a `PATCH` request to a route of the GitHub API through the request layer.
+    ///
+    /// The fields of the resource are set to the values of `body`, so setting them again has the same effect.
+    async fn patch<T: DeserializeOwned>(&self, route: impl AsRef<str>, body: &serde_json::Value) -> Result<T> {
+        self.send(Method::Patch, route.as_ref(), Some(body), true).await
+    }
+
+    /// Get the username provided to the GitHubApi, i.e. the username of the signed in GitHub user.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
     /// Get the username (login) of the currently signed in GitHub user.
     ///
     /// # Returns
-    /// - `Result<String, &str>`: The username of the currently signed-in GitHub user if successful, an error message otherwise.
-    pub async fn get_user(&self) -> Result<String, &str> {
+    /// - `Result<String>`: The username of the currently signed-in GitHub user if successful,
+    ///   [`Error::Unauthorized`](Error::Unauthorized) if the token is invalid, or another [`Error`](Error) otherwise.
+    pub async fn get_user(&self) -> Result<String> {
         // Get the user information from the GitHub API.
-        let user = self.octocrab.current().user().await;
-        match user {
-            // If the user information is successfully retrieved, return the content of the `login` field (the username).
-            Ok(user) => Ok(user.login),
-            // If the user information is not successfully retrieved, return an error message.
-            Err(_) => {
-                Err(
-                    "Failed to get the username of the currently signed in GitHub user. Could be due to an invalid token."
-                )
+        let user = self.retry(|| async { self.octocrab.current().user().await }).await?;
+        // Return the content of the `login` field (the username).
+        Ok(user.login)
+    }
+
+    /// Check the capabilities needed by the toolkit that the token of the user lacks.
+    ///
+    /// The OAuth scopes of a classic token are listed in the `X-OAuth-Scopes` header of every response.
+    /// Fine-grained tokens do not send this header, as their permissions are granted per repository,
+    /// so their capabilities are reported as unknown.
+    ///
+    /// # Returns
+    /// - `Result<TokenCapabilities>`
+    ///     - `Ok(TokenCapabilities)`: The [missing](TokenCapabilities::Missing) capabilities of a classic token,
+    ///       empty if it has every capability, or [`Unknown`](TokenCapabilities::Unknown) for any other token.
+    ///     - `Err(Error)`: An error indicating why the method failed.
+    pub async fn check_capabilities(&self) -> Result<TokenCapabilities> {
+        let (headers, _): (HeaderMap, serde_json::Value) = self.send_with_headers(&self.octocrab, Method::Get, "/user", None, true).await?;
+
+        let capabilities = match headers.get("x-oauth-scopes").and_then(|value| value.to_str().ok()) {
+            Some(scopes) => {
+                let scopes: Vec<String> = scopes.split(',')
+                    .map(|scope| scope.trim().to_string())
+                    .filter(|scope| !scope.is_empty())
+                    .collect();
+                TokenCapabilities::Missing(TokenCapability::missing_from(&scopes))
             }
-        }
+            None => TokenCapabilities::Unknown,
+        };
+        *self.token_capabilities.lock().unwrap() = Some(capabilities.clone());
+        Ok(capabilities)
+    }
+
+    /// Get the capabilities of the token found by the latest [`check_capabilities`](GitHubApi::check_capabilities),
+    /// e.g. to warn the user about the ones it lacks after login.
+    ///
+    /// # Returns
+    /// - `Option<TokenCapabilities>`: The [TokenCapabilities](TokenCapabilities) of the token, `None` if they were not checked yet.
+    pub fn token_capabilities(&self) -> Option<TokenCapabilities> {
+        self.token_capabilities.lock().unwrap().clone()
     }
 
     /// Verify that the username of the currently signed in GitHub user matches the username
@@ -62,6 +464,27 @@ impl GitHubApi {
         }
     }
 
+    /// Get the HTTPS URL to clone a repository from, e.g. with a [LocalGitRepo](crate::apis::LocalGitRepo).
+    ///
+    /// # Arguments
+    /// - `repo`: The repository to get the clone URL of.
+    ///
+    /// # Returns
+    /// - `Result<String>`
+    ///     - `Ok(String)`: The clone URL. e.g. `https://github.com/FOSScope/TranslateProject.git`.
+    ///     - `Err(Error)`: [`Error::NotFound`](Error::NotFound) if the repository does not exist, or another error indicating why the method failed.
+    pub async fn get_clone_url(&self, repo: &GitHubRepo) -> Result<String> {
+        let repository: Repository = self.get(format!("/repos/{}/{}", repo.owner, repo.name))
+            .await
+            .map_err(|e| match e {
+                Error::NotFound(_) => Error::NotFound(format!("repository {}", repo.get_full_name())),
+                e => e,
+            })?;
+        repository.clone_url
+            .map(|url| url.to_string())
+            .ok_or_else(|| Error::NotFound(format!("clone URL of {}", repo.get_full_name())))
+    }
+
     /// Validate that the given repository is a fork of the given upstream repository.
     ///
     /// # Arguments
@@ -69,22 +492,37 @@ impl GitHubApi {
     /// - `upstream`: The upstream repository that the repository should be a fork of.
     ///
     /// # Returns
-    /// - `Result<(), &str>`: `Ok(())` if the repository is a fork of the upstream repository, an error message indicating why the repository is not a fork otherwise.
-    pub async fn validate_repo(&self, repo: GitHubRepo, upstream: GitHubRepo) -> Result<(), &str> {
+    /// - `Result<()>`: `Ok(())` if the repository is a fork of the upstream repository, otherwise:
+    ///     - [`Error::NotFound`](Error::NotFound) if the repository does not exist.
+    ///     - [`Error::NotAFork`](Error::NotAFork) if the repository is not a fork.
+    ///     - [`Error::WrongParent`](Error::WrongParent) if the repository is a fork of another repository.
+    pub async fn validate_repo(&self, repo: GitHubRepo, upstream: GitHubRepo) -> Result<()> {
         // Get the repository information from the GitHub API.
-        if let Ok(repo) = self.octocrab.repos(repo.owner, repo.name).get().await {
-            if repo.fork.unwrap_or(false) {
-                // Check if the parent of the repository is the upstream repository by comparing the full names.
-                if repo.parent.unwrap().full_name.unwrap() == upstream.get_full_name() {
-                    Ok(())
-                } else {
-                    Err("Repository is not a fork of the upstream repository")
-                }
-            } else {
-                Err("Repository is not a fork")
+        let full_name = repo.get_full_name();
+        let repo = match self.retry(|| async { self.octocrab.repos(repo.owner.clone(), repo.name.clone()).get().await }).await {
+            Ok(repo) => repo,
+            Err(e) => {
+                return match e {
+                    Error::NotFound(_) => Err(Error::NotFound(format!("repository {}", full_name))),
+                    e => Err(e),
+                };
             }
+        };
+
+        if !repo.fork.unwrap_or(false) {
+            return Err(Error::NotAFork(full_name));
+        }
+
+        // Check if the parent of the repository is the upstream repository by comparing the full names.
+        let parent = repo.parent.and_then(|parent| parent.full_name).unwrap_or_default();
+        if parent == upstream.get_full_name() {
+            Ok(())
         } else {
-            Err("Repository does not exist")
+            Err(Error::WrongParent {
+                repo: full_name,
+                expected: upstream.get_full_name(),
+                actual: parent,
+            })
         }
     }
 
@@ -94,16 +532,18 @@ impl GitHubApi {
     /// - `upstream`: The upstream repository to get the forks of.
     ///
     /// # Returns
-    /// - `Vec<GitHubRepo>`: A list of [GitHubRepo](GitHubRepo) representing the forks of the upstream repository.
-    pub async fn get_forks(&self, upstream: GitHubRepo) -> Vec<GitHubRepo> {
+    /// - `Result<Vec<GitHubRepo>>`: A list of [GitHubRepo](GitHubRepo) representing the forks of the upstream repository.
+    pub async fn get_forks(&self, upstream: GitHubRepo) -> Result<Vec<GitHubRepo>> {
         let mut forks = Vec::new();
         let mut page: u32 = 1;
 
         loop {
             // Get the forks of the upstream repository from the GitHub API page by page.
-            let response = self.octocrab.repos(
-                upstream.owner.clone(), upstream.name.clone(),
-            ).list_forks().page(page).per_page(100).send().await.unwrap();
+            let response = self.retry(|| async {
+                self.octocrab.repos(
+                    upstream.owner.clone(), upstream.name.clone(),
+                ).list_forks().page(page).per_page(100).send().await
+            }).await?;
 
             // This is to attempt to get the next page of forks, so it is possible to know if there's
             // another page of forks to fetch.
@@ -113,8 +553,8 @@ impl GitHubApi {
             let next_page = response.next.clone();
 
             // Only include the owner and name of the forked repository.
-            forks.extend(response.into_iter().map(
-                |repo| GitHubRepo::new(repo.owner.unwrap().login, repo.name)
+            forks.extend(response.into_iter().filter_map(
+                |repo| repo.owner.map(|owner| GitHubRepo::new(owner.login, repo.name))
             ));
 
             // If there's no next page, break out of the loop.
@@ -126,102 +566,253 @@ impl GitHubApi {
             page += 1;
         }
 
-        forks
+        Ok(forks)
+    }
+
+    /// Get the forks of the upstream repository that the currently signed in GitHub user can contribute with,
+    /// i.e. the forks owned by the user or by an organization the user is a member of.
+    ///
+    /// Instead of paging through every fork of the upstream repository, only the forks of the user are looked up,
+    /// so forks that were renamed are found as well.
+    ///
+    /// # Arguments
+    /// - `upstream`: The upstream repository to get the user's forks of.
+    ///
+    /// # Returns
+    /// - `Result<Vec<GitHubRepo>>`
+    ///     - `Ok(Vec<GitHubRepo>)`: The candidate forks, with the forks owned by the user first,
+    ///       and the fork keeping the name of the upstream repository first among them. Empty if there is no fork.
+    ///     - `Err(Error)`: An error indicating why the method failed.
+    pub async fn get_user_forks(&self, upstream: &GitHubRepo) -> Result<Vec<GitHubRepo>> {
+        const QUERY: &str = "query($cursor: String) { viewer { repositories(\
+            first: 100, after: $cursor, isFork: true, ownerAffiliations: [OWNER, ORGANIZATION_MEMBER]\
+        ) { nodes { name owner { login } parent { nameWithOwner } } pageInfo { hasNextPage endCursor } } } }";
+
+        let upstream_full_name = upstream.get_full_name();
+        let mut forks = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let data = self.graphql(QUERY, serde_json::json!({
+                "cursor": cursor,
+            })).await?;
+            let repositories: github_api_responses::user_forks::UserForks = serde_json::from_value(data)
+                .map_err(|e| Error::parse("the forks of the user", e))?;
+            let repositories = repositories.viewer.repositories;
+
+            // Repository names are case-insensitive on GitHub.
+            forks.extend(repositories.nodes.into_iter()
+                .filter(|repo| repo.parent.as_ref().is_some_and(
+                    |parent| parent.name_with_owner.eq_ignore_ascii_case(&upstream_full_name)
+                ))
+                .map(|repo| GitHubRepo::new(repo.owner.login, repo.name)));
+
+            match repositories.page_info.end_cursor {
+                Some(end_cursor) if repositories.page_info.has_next_page => cursor = Some(end_cursor),
+                _ => break,
+            }
+        }
+
+        forks.sort_by_key(|fork| (fork.owner != self.username, fork.name != upstream.name));
+        Ok(forks)
     }
 
     /// Get the fork of upstream repository that belongs to the currently signed in GitHub user.
     ///
+    /// If the user has more than one fork (e.g. a renamed fork), the fork keeping the name of the upstream repository is preferred.
+    /// Use [`get_user_forks`](GitHubApi::get_user_forks) to let the user choose among all of them.
+    ///
     /// # Arguments
     /// - `upstream`: The upstream repository to get the user's fork of.
     ///
     /// # Returns
-    /// - `Result<GitHubRepo, &str>`
+    /// - `Result<GitHubRepo>`
     ///     - `Ok(GitHubRepo)`: A [GitHubRepo](GitHubRepo) representing the user's fork of the upstream repository.
-    ///     - `Err(&str)`: An error message indicating why the method failed.
-    pub async fn get_user_fork(&self, upstream: GitHubRepo) -> Result<GitHubRepo, &str> {
-        // Get all forks of the upstream repository.
-        let forks = self.get_forks(upstream).await;
-
-        // Find the fork that belongs to the currently signed in GitHub user.
-        for fork in forks {
-            if fork.owner == self.username {
-                return Ok(fork);
-            }
-        }
+    ///     - `Err(Error)`: [`Error::NotFound`](Error::NotFound) if the user has not forked the upstream repository, or another error indicating why the method failed.
+    pub async fn get_user_fork(&self, upstream: GitHubRepo) -> Result<GitHubRepo> {
+        let forks = self.get_user_forks(&upstream).await?;
 
-        Err("User has not forked the upstream repository.")
+        forks.into_iter()
+            .find(|fork| fork.owner == self.username)
+            .ok_or_else(|| Error::NotFound(format!("fork of {} owned by {}", upstream.get_full_name(), self.username)))
     }
 
     /// Create a fork of the upstream repository using the information given.
     ///
+    /// GitHub creates forks asynchronously, so this waits until the Git data of the fork is available,
+    /// see [`create_fork_with_progress`](GitHubApi::create_fork_with_progress).
+    ///
     /// # Arguments
     /// - `repo`: A [GitHubRepo](GitHubRepo) containing the information to use to create the fork.
     /// - `upstream`: The upstream repository to fork.
     ///
     /// # Returns
-    /// - `Result<GitHubRepo, &str>`
+    /// - `Result<GitHubRepo>`
     ///     - `Ok(GitHubRepo)`: A [GitHubRepo](GitHubRepo) representing the created fork.
-    ///     - `Err(&str)`: An error message indicating why the method failed.
-    pub async fn create_fork(&self, repo: GitHubRepo, upstream: GitHubRepo) -> Result<GitHubRepo, &str> {
+    ///     - `Err(Error)`: [`Error::ForkTimeout`](Error::ForkTimeout) if the fork is still not available after waiting for it,
+    ///       or another error indicating why the method failed.
+    pub async fn create_fork(&self, repo: GitHubRepo, upstream: GitHubRepo) -> Result<GitHubRepo> {
+        self.create_fork_with_progress(repo, upstream, |_| {}).await
+    }
+
+    /// Create a fork of the upstream repository using the information given, and wait until it can be used.
+    ///
+    /// GitHub answers the request before the fork is created, so the default branch of the fork is checked
+    /// according to the [ForkWaitPolicy](ForkWaitPolicy) until it exists.
+    ///
+    /// # Arguments
+    /// - `repo`: A [GitHubRepo](GitHubRepo) containing the information to use to create the fork.
+    /// - `upstream`: The upstream repository to fork.
+    /// - `progress`: Called with the time waited so far each time the fork is not available yet.
+    ///
+    /// # Returns
+    /// - `Result<GitHubRepo>`
+    ///     - `Ok(GitHubRepo)`: A [GitHubRepo](GitHubRepo) representing the created fork, which is ready to be used.
+    ///     - `Err(Error)`: [`Error::ForkTimeout`](Error::ForkTimeout) if the fork is still not available after waiting for it,
+    ///       or another error indicating why the method failed.
+    pub async fn create_fork_with_progress(
+        &self,
+        repo: GitHubRepo,
+        upstream: GitHubRepo,
+        progress: impl Fn(Duration) + Send + Sync,
+    ) -> Result<GitHubRepo> {
         // Create a fork of the upstream repository using the information given.
-        let response = self.octocrab._post(
+        // GitHub returns the existing fork if the repository has been forked already.
+        let repo: Repository = self.post_idempotent(
             format!("/repos/{}/{}/forks", upstream.owner, upstream.name),
-            Some(&serde_json::json!({
+            &serde_json::json!({
                 "organization": repo.owner,
                 "name": repo.name,
-            })),
-        ).await;
+            }),
+        ).await?;
 
-        if response.is_err() {
-            return Err("Failed to create fork");
-        }
+        // Parse the response to get a GitHubRepo object.
+        let fork = match repo.owner {
+            Some(owner) => GitHubRepo::new(owner.login, repo.name),
+            None => return Err(Error::NotFound(format!("owner of the fork {}", repo.name))),
+        };
+        let default_branch = repo.default_branch
+            .ok_or_else(|| Error::NotFound(format!("default branch of the fork {}", fork.get_full_name())))?;
 
-        let response_body = self.octocrab.body_to_string(response.unwrap()).await;
-        if response_body.is_err() {
-            return Err("Failed to read fork response");
+        self.wait_for_fork(&fork, &default_branch, progress).await?;
+        Ok(fork)
+    }
+
+    /// Wait until a branch of a new fork exists, which means the Git data of the fork has been copied.
+    ///
+    /// While the fork is being created, GitHub answers `404 Not Found` or `409 Conflict` (the repository is empty).
+    async fn wait_for_fork(&self, fork: &GitHubRepo, branch: &str, progress: impl Fn(Duration)) -> Result<()> {
+        let start = tokio::time::Instant::now();
+        loop {
+            match self.get_branch_sha(fork, branch).await {
+                Ok(_) => return Ok(()),
+                Err(Error::NotFound(_)) | Err(Error::Conflict(_)) => {}
+                Err(e) => return Err(e),
+            }
+
+            let elapsed = start.elapsed();
+            if elapsed >= self.fork_wait_policy.timeout {
+                return Err(Error::ForkTimeout {
+                    repo: fork.get_full_name(),
+                    seconds: elapsed.as_secs(),
+                });
+            }
+            progress(elapsed);
+            tokio::time::sleep(self.fork_wait_policy.interval).await;
         }
+    }
 
-        let json_response = serde_json::from_str(&*response_body.unwrap());
-        match { json_response } {
-            Ok(repo) => {
-                // Parse the response to get a GitHubRepo object and return it.
-                let repo: Repository = repo;
-                Ok(GitHubRepo::new(repo.owner.unwrap().login, repo.name))
+    /// Compare a branch of a fork with the branch of the same name in its upstream repository.
+    ///
+    /// # Arguments
+    /// - `fork`: The fork to compare.
+    /// - `upstream`: The upstream repository of the fork.
+    /// - `branch`: The branch to compare, usually the base branch of the upstream repository. e.g. `main`.
+    ///
+    /// # Returns
+    /// - `Result<ForkStatus>`
+    ///     - `Ok(ForkStatus)`: A [ForkStatus](ForkStatus) containing how many commits the fork is ahead of and behind the upstream repository.
+    ///     - `Err(Error)`: An error indicating why the method failed.
+    pub async fn fork_status(&self, fork: &GitHubRepo, upstream: &GitHubRepo, branch: &str) -> Result<ForkStatus> {
+        // The upstream branch is the base of the comparison, so `ahead_by` counts the commits that only the fork has.
+        let comparison: github_api_responses::compare::Comparison = self.get(
+            format!(
+                "/repos/{}/{}/compare/{}...{}:{}",
+                upstream.owner, upstream.name, branch, fork.owner, branch
+            ),
+        ).await?;
+
+        Ok(ForkStatus::new(comparison.ahead_by, comparison.behind_by))
+    }
+
+    /// Synchronize a branch of a fork with the branch of the same name in its upstream repository.
+    ///
+    /// # Arguments
+    /// - `fork`: The fork to synchronize.
+    /// - `branch`: The branch to synchronize. e.g. `main`.
+    ///
+    /// # Returns
+    /// - `Result<()>`: `Ok` if the branch is synchronized (or was already up to date), otherwise:
+    ///     - [`Error::ForkDiverged`](Error::ForkDiverged) if the branch has diverged and cannot be synchronized automatically.
+    ///     - [`Error::MissingPermissions`](Error::MissingPermissions) if the upstream repository changed its workflows
+    ///       and the token lacks the [`Workflow`](TokenCapability::Workflow) capability.
+    ///     - Another error indicating why the method failed.
+    pub async fn sync_fork(&self, fork: &GitHubRepo, branch: &str) -> Result<()> {
+        // Merging the upstream branch again does nothing once the fork is up to date.
+        let response: Result<github_api_responses::merge_upstream::MergeUpstream, _> = self.post_idempotent(
+            format!("/repos/{}/{}/merge-upstream", fork.owner, fork.name),
+            &serde_json::json!({
+                "branch": branch,
+            }),
+        ).await;
+
+        match response {
+            Ok(_) => Ok(()),
+            // GitHub responds with 409 Conflict if the branch cannot be merged without conflicts.
+            Err(Error::Conflict(_)) => {
+                Err(Error::ForkDiverged(fork.get_full_name()))
             }
-            Err(_) => Err("Failed to parse fork response"),
+            // A token without the `workflow` scope cannot bring the changed workflows of the upstream repository into the fork.
+            Err(Error::GitHub(e)) if unprocessable_message(&e).is_some_and(|message| message.contains("`workflow` scope")) => {
+                Err(Error::MissingPermissions(vec![TokenCapability::Workflow]))
+            }
+            Err(e) => Err(e),
         }
     }
 
-    /// Get the **decoded** content of a file in a repository.
+    /// Get the **decoded** content of a file on the default branch of a repository.
     ///
     /// # Arguments
     /// - `repo`: The repository to get the file from.
     /// - `path`: The path to the file in the repository.
     ///
     /// # Returns
-    /// - `Result<String, &str>`
+    /// - `Result<String>`
     ///     - `Ok(String)`: The decoded content of the file.
-    ///     - `Err(&str)`: An error message indicating why the method failed.
-    pub async fn get_file_content(&self, repo: &GitHubRepo, path: &str) -> Result<String, &str> {
-        let repo = self.octocrab.repos(repo.owner.clone(), repo.name.clone());
-        let content = repo
-            .get_content()
-            .path(path)
-            .r#ref("main")
-            .send()
-            .await;
-        match content {
-            Ok(mut content) => {
-                let contents = content.take_items();
-                let c = &contents[0];
-                let decoded_content = c.decoded_content();
-                match decoded_content {
-                    Some(decoded_content) => Ok(decoded_content),
-                    None => Err("No file content found"),
-                }
+    ///     - `Err(Error)`: [`Error::NotFound`](Error::NotFound) if the file does not exist or has no content, or another error indicating why the method failed.
+    pub async fn get_file_content(&self, repo: &GitHubRepo, path: &str) -> Result<String> {
+        let full_name = repo.get_full_name();
+        let content = self.retry(|| async {
+            self.octocrab.repos(repo.owner.clone(), repo.name.clone())
+                .get_content()
+                .path(path)
+                .send()
+                .await
+        }).await;
+        let mut content = match content {
+            Ok(content) => content,
+            Err(e) => {
+                return match e {
+                    Error::NotFound(_) => Err(Error::NotFound(format!("file {} in {}", path, full_name))),
+                    e => Err(e),
+                };
             }
-            Err(_) => Err("Failed to get file content"),
-        }
+        };
+
+        let contents = content.take_items();
+        contents.first()
+            .and_then(|c| c.decoded_content())
+            .ok_or_else(|| Error::NotFound(format!("content of the file {} in {}", path, full_name)))
     }
 
     /// Get the latest commit SHA of the default branch of a repository.
@@ -230,36 +821,67 @@ impl GitHubApi {
     /// - `repo`: The repository to get the latest commit SHA from.
     ///
     /// # Returns
-    /// - `Result<String, &str>`
+    /// - `Result<String>`
     ///     - `Ok(String)`: The SHA of the latest commit.
-    ///     - `Err(&str)`: An error message indicating why the method failed.
-    pub async fn get_latest_commit_sha(&self, repo: &GitHubRepo) -> Result<String, String> {
-        let response = self.octocrab._get(
+    ///     - `Err(Error)`: An error indicating why the method failed.
+    pub async fn get_latest_commit_sha(&self, repo: &GitHubRepo) -> Result<String> {
+        let commits: Vec<github_api_responses::commit::Commit> = self.get(
             format!("/repos/{}/{}/commits", repo.owner, repo.name),
-        ).await;
+        ).await?;
 
-        if response.is_err() {
-            return Err("Failed to read commit information of the repository.".to_string());
+        match commits.first() {
+            Some(commit) => Ok(commit.sha.clone()),
+            None => Err(Error::NotFound(format!("commits in the repository {}", repo.get_full_name()))),
         }
+    }
+
+    /// Get the SHA of the commit that a branch of a repository currently points to.
+    ///
+    /// # Arguments
+    /// - `repo`: The repository to get the branch from.
+    /// - `branch`: The name of the branch. e.g. `main`.
+    ///
+    /// # Returns
+    /// - `Result<String>`
+    ///     - `Ok(String)`: The SHA of the head commit of the branch.
+    ///     - `Err(Error)`: [`Error::NotFound`](Error::NotFound) if the branch does not exist, or another error indicating why the method failed.
+    pub async fn get_branch_sha(&self, repo: &GitHubRepo, branch: &str) -> Result<String> {
+        let reference: Result<github_api_responses::reference::Reference, _> = self.get(
+            format!("/repos/{}/{}/git/ref/heads/{}", repo.owner, repo.name, branch),
+        ).await;
 
-        let response_body = self.octocrab.body_to_string(response.unwrap()).await;
-        if response_body.is_err() {
-            return Err("Failed to get the response body of the commit information of the repository.".to_string());
+        match reference {
+            Ok(reference) => Ok(reference.object.sha),
+            Err(e) => match e {
+                Error::NotFound(_) => Err(Error::NotFound(format!("branch {} in {}", branch, repo.get_full_name()))),
+                e => Err(e),
+            },
         }
+    }
 
-        let json_response: Result<Vec<github_api_responses::commit::Commit>, _> = serde_json::from_str(&*response_body.unwrap());
-        match json_response {
-            Ok(commits) => {
-                if commits.is_empty() {
-                    return Err("No commits found in the repository.".to_string());
-                }
+    /// Create a new reference in a repository, pointing to the given commit.
+    ///
+    /// # Arguments
+    /// - `repo`: The repository to create the new reference in.
+    /// - `r#ref`: The name of the new reference.
+    /// - `sha`: The SHA of the commit that the new reference points to.
+    async fn create_reference_at(&self, repo: &GitHubRepo, r#ref: &str, sha: &str) -> Result<()> {
+        let created: Result<serde_json::Value> = self.post(
+            format!("/repos/{}/{}/git/refs", repo.owner, repo.name),
+            &serde_json::json!({
+                "ref": format!("refs/heads/{}", r#ref),
+                "sha": sha,
+            }),
+        ).await;
 
-                Ok(commits[0].sha.clone())
-            }
-            Err(e) => {
-                let error_message = format!("Failed to parse commit information of the repository: {:?}", e);
-                return Err(error_message);
-            }
+        match created {
+            Ok(_) => Ok(()),
+            // The reference may have been created even though the request failed.
+            Err(e) if may_have_been_handled(&e) => match self.get_branch_sha(repo, r#ref).await {
+                Ok(created_sha) if created_sha == sha => Ok(()),
+                _ => Err(e),
+            },
+            Err(e) => Err(e),
         }
     }
 
@@ -270,31 +892,93 @@ impl GitHubApi {
     /// - `r#ref`: The name of the new reference.
     ///
     /// # Returns
-    /// - `Result<(), String>`: `Ok` if the new reference is successfully created, an error message indicating why the method failed otherwise.
-    pub async fn create_new_reference(&self, repo: &GitHubRepo, r#ref: &str) -> Result<(), String> {
+    /// - `Result<()>`: `Ok` if the new reference is successfully created, an error indicating why the method failed otherwise.
+    pub async fn create_new_reference(&self, repo: &GitHubRepo, r#ref: &str) -> Result<()> {
         // Get the SHA of the latest commit on the default branch.
-        let sha = self.get_latest_commit_sha(repo).await;
-        if sha.is_err() {
-            return Err(sha.err().unwrap());
-        }
-        let sha = sha.unwrap();
+        let sha = self.get_latest_commit_sha(repo).await?;
 
-        let response = self.octocrab._post(
-            format!("/repos/{}/{}/git/refs", repo.owner, repo.name),
-            Some(&serde_json::json!({
-                "ref": format!("refs/heads/{}", r#ref),
-                "sha": sha,
-            })),
-        ).await;
+        self.create_reference_at(repo, r#ref, &sha).await
+    }
+
+    /// Create a new reference in a repository, based on the current head of a branch in another (or the same) repository.
+    ///
+    /// This is used to branch off the upstream repository's base branch in a fork, so that a stale fork
+    /// does not carry old history into new contributions.
+    ///
+    /// Forks share their git objects with the upstream repository on GitHub, so the commit is usually available
+    /// in the fork already. If GitHub reports that it does not exist, the fork's branch with the same name as `source_branch`
+    /// is synchronized with the upstream repository first to make the commit available. Any other rejection,
+    /// e.g. the reference already exists, is returned without touching the fork.
+    ///
+    /// # Arguments
+    /// - `repo`: The repository to create the new reference in.
+    /// - `r#ref`: The name of the new reference.
+    /// - `source_repo`: The repository to take the commit from.
+    /// - `source_branch`: The branch in `source_repo` whose head commit the new reference points to.
+    ///
+    /// # Returns
+    /// - `Result<()>`: `Ok` if the new reference is successfully created, an error indicating why the method failed otherwise.
+    pub async fn create_reference_from(
+        &self,
+        repo: &GitHubRepo,
+        r#ref: &str,
+        source_repo: &GitHubRepo,
+        source_branch: &str,
+    ) -> Result<()> {
+        // Get the SHA of the current head of the source branch.
+        let sha = self.get_branch_sha(source_repo, source_branch).await?;
 
-        if response.is_err() {
-            let error_message = format!(
-                "Failed to create a new reference {:?} in the repository. Error: {:?}",
-                r#ref, response.err().unwrap()
-            );
-            return Err(error_message);
+        match self.create_reference_at(repo, r#ref, &sha).await {
+            // GitHub rejects the reference with 422 and `Object does not exist` if the commit is not (yet) available
+            // in the repository. Other rejections (e.g. the reference already exists) are left to the caller.
+            Err(Error::GitHub(e))
+                if repo != source_repo
+                    && unprocessable_message(&e).is_some_and(|message| message.contains("Object does not exist")) =>
+            {
+                // Pull the upstream changes into the fork to make the commit available, then try again.
+                self.sync_fork(repo, source_branch).await?;
+                self.create_reference_at(repo, r#ref, &sha).await
+            }
+            r => r,
         }
-        Ok(())
+    }
+
+    /// List every branch of a repository.
+    ///
+    /// # Arguments
+    /// - `repo`: The repository to list the branches of.
+    ///
+    /// # Returns
+    /// - `Result<Vec<Branch>>`
+    ///     - `Ok(Vec<Branch>)`: The [Branch](Branch)es of the repository, sorted by name.
+    ///     - `Err(Error)`: An error indicating why the method failed.
+    pub async fn list_branches(&self, repo: &GitHubRepo) -> Result<Vec<Branch>> {
+        let first_page = self.retry(|| async {
+            self.octocrab.repos(repo.owner.clone(), repo.name.clone())
+                .list_branches()
+                .per_page(100)
+                .send()
+                .await
+        }).await?;
+        let branches = self.retry(|| async { self.octocrab.all_pages(first_page.clone()).await }).await?;
+
+        Ok(branches.into_iter().map(Branch::from).collect())
+    }
+
+    /// Delete a branch of a repository.
+    ///
+    /// # Arguments
+    /// - `repo`: The repository to delete the branch from.
+    /// - `branch`: The name of the branch to delete.
+    ///
+    /// # Returns
+    /// - `Result<()>`: `Ok` if the branch is deleted, an error indicating why the method failed otherwise.
+    pub async fn delete_branch(&self, repo: &GitHubRepo, branch: &str) -> Result<()> {
+        self.retry_write(|| async {
+            self.octocrab.repos(repo.owner.clone(), repo.name.clone())
+                .delete_ref(&octocrab::params::repos::Reference::Branch(branch.to_string()))
+                .await
+        }).await
     }
 
     pub async fn create_file(
@@ -304,105 +988,751 @@ impl GitHubApi {
         path: &str,
         content: &str,
         commit_message: &str,
-    ) -> Result<(), String> {
-        let response = self.octocrab.repos(repo.owner.clone(), repo.name.clone())
-            .create_file(
-                path,
-                commit_message,
-                content,
-            )
-            .branch(branch)
-            .send()
-            .await;
-
-        if response.is_err() {
-            let error_message = format!(
-                "Failed to create a file {:?} in the repository. Error: {:?}",
-                path, response.err().unwrap()
-            );
-            return Err(error_message);
-        }
+    ) -> Result<()> {
+        self.retry_write(|| async {
+            self.octocrab.repos(repo.owner.clone(), repo.name.clone())
+                .create_file(
+                    path,
+                    commit_message,
+                    content,
+                )
+                .branch(branch)
+                .send()
+                .await
+        }).await?;
+
         Ok(())
     }
 
+    /// Get the blob SHA of a file on a branch of a repository.
+    ///
+    /// The SHA is required to update or delete the file, and guards against overwriting changes made in the meantime.
+    ///
+    /// # Arguments
+    /// - `repo`: The repository to get the file from.
+    /// - `branch`: The branch to get the file from.
+    /// - `path`: The path to the file in the repository.
+    ///
+    /// # Returns
+    /// - `Result<String>`
+    ///     - `Ok(String)`: The blob SHA of the file.
+    ///     - `Err(Error)`: [`Error::NotFound`](Error::NotFound) if the file does not exist on the branch, or another error indicating why the method failed.
+    pub async fn get_file_sha(&self, repo: &GitHubRepo, branch: &str, path: &str) -> Result<String> {
+        let content = self.retry(|| async {
+            self.octocrab.repos(repo.owner.clone(), repo.name.clone())
+                .get_content()
+                .path(path)
+                .r#ref(branch)
+                .send()
+                .await
+        }).await;
+        let not_found = || Error::NotFound(format!("file {} on branch {} in {}", path, branch, repo.get_full_name()));
+
+        let mut content = match content {
+            Ok(content) => content,
+            Err(e) => {
+                return match e {
+                    Error::NotFound(_) => Err(not_found()),
+                    e => Err(e),
+                };
+            }
+        };
+        // A directory lists its entries instead, which are not the file itself.
+        content.take_items().into_iter()
+            .find(|item| item.path == path && item.r#type == "file")
+            .map(|item| item.sha)
+            .ok_or_else(not_found)
+    }
+
+    /// Update an existing file on a branch of a repository.
+    ///
+    /// # Arguments
+    /// - `repo`: The repository to update the file in.
+    /// - `branch`: The branch to update the file on.
+    /// - `path`: The path to the file in the repository.
+    /// - `content`: The new content of the file.
+    /// - `sha`: The blob SHA of the file being replaced, as returned by [`get_file_sha`](GitHubApi::get_file_sha).
+    /// - `commit_message`: The commit message.
+    ///
+    /// # Returns
+    /// - `Result<()>`: `Ok` if the file is updated, otherwise:
+    ///     - [`Error::Conflict`](Error::Conflict) if the file has been changed since `sha` was read.
+    ///     - Another error indicating why the method failed.
+    pub async fn update_file(
+        &self,
+        repo: &GitHubRepo,
+        branch: &str,
+        path: &str,
+        content: &str,
+        sha: &str,
+        commit_message: &str,
+    ) -> Result<()> {
+        self.retry_write(|| async {
+            self.octocrab.repos(repo.owner.clone(), repo.name.clone())
+                .update_file(
+                    path,
+                    commit_message,
+                    content,
+                    sha,
+                )
+                .branch(branch)
+                .send()
+                .await
+        }).await?;
+
+        Ok(())
+    }
+
+    /// Delete an existing file on a branch of a repository.
+    ///
+    /// # Arguments
+    /// - `repo`: The repository to delete the file from.
+    /// - `branch`: The branch to delete the file from.
+    /// - `path`: The path to the file in the repository.
+    /// - `sha`: The blob SHA of the file being deleted, as returned by [`get_file_sha`](GitHubApi::get_file_sha).
+    /// - `commit_message`: The commit message.
+    ///
+    /// # Returns
+    /// - `Result<()>`: `Ok` if the file is deleted, otherwise:
+    ///     - [`Error::Conflict`](Error::Conflict) if the file has been changed since `sha` was read.
+    ///     - Another error indicating why the method failed.
+    pub async fn delete_file(
+        &self,
+        repo: &GitHubRepo,
+        branch: &str,
+        path: &str,
+        sha: &str,
+        commit_message: &str,
+    ) -> Result<()> {
+        self.retry_write(|| async {
+            self.octocrab.repos(repo.owner.clone(), repo.name.clone())
+                .delete_file(
+                    path,
+                    commit_message,
+                    sha,
+                )
+                .branch(branch)
+                .send()
+                .await
+        }).await?;
+
+        Ok(())
+    }
+
+    /// Commit several file changes to a branch atomically, in a single commit, using the Git Data API.
+    ///
+    /// Blobs are created for the written files, then a new tree is built on top of the branch's current tree,
+    /// committed, and the branch is moved to the new commit. The branch is only updated once everything else
+    /// succeeds, so a failure leaves the branch untouched.
+    ///
+    /// # Arguments
+    /// - `repo`: The repository to commit to.
+    /// - `branch`: The branch to commit to. The branch must already exist.
+    /// - `changes`: The [FileChange](FileChange)s to commit.
+    /// - `commit_message`: The commit message.
+    ///
+    /// # Returns
+    /// - `Result<String>`
+    ///     - `Ok(String)`: The SHA of the new commit.
+    ///     - `Err(Error)`: [`Error::NotFound`](Error::NotFound) if a file to rename does not exist, or another error indicating why the method failed.
+    pub async fn commit_files(
+        &self,
+        repo: &GitHubRepo,
+        branch: &str,
+        changes: &[FileChange],
+        commit_message: &str,
+    ) -> Result<String> {
+        use base64::Engine;
+        use github_api_responses::git_data;
+
+        // Get the current head of the branch, and the tree of it.
+        let head = self.get_branch_sha(repo, branch).await?;
+        let head_commit: git_data::Commit = self.get(
+            format!("/repos/{}/{}/git/commits/{}", repo.owner, repo.name, head),
+        ).await?;
+
+        // Renaming a file requires the blob SHA and mode of the existing file, which are found in the current tree.
+        let base_tree = if changes.iter().any(|change| matches!(change, FileChange::Rename { .. })) {
+            self.get_tree(repo, &head_commit.tree.sha, true).await?.entries
+        } else {
+            Vec::new()
+        };
+
+        let mut entries = Vec::new();
+        for change in changes {
+            match change {
+                FileChange::Write { path, content } => {
+                    // Blobs, trees and commits that are created twice are left unused, as the branch only points to one of them.
+                    let blob: git_data::Blob = self.post_idempotent(
+                        format!("/repos/{}/{}/git/blobs", repo.owner, repo.name),
+                        &serde_json::json!({
+                            "content": base64::prelude::BASE64_STANDARD.encode(content),
+                            "encoding": "base64",
+                        }),
+                    ).await?;
+                    entries.push(serde_json::json!({
+                        "path": path,
+                        "mode": "100644",
+                        "type": "blob",
+                        "sha": blob.sha,
+                    }));
+                }
+                FileChange::Delete { path } => {
+                    // A `null` SHA removes the file from the tree.
+                    entries.push(serde_json::json!({
+                        "path": path,
+                        "mode": "100644",
+                        "type": "blob",
+                        "sha": null,
+                    }));
+                }
+                FileChange::Rename { from, to } => {
+                    let entry = base_tree.iter()
+                        .find(|entry| entry.is_file() && &entry.path == from)
+                        .ok_or_else(|| Error::NotFound(format!(
+                            "file {} on branch {} in {}", from, branch, repo.get_full_name()
+                        )))?;
+                    entries.push(serde_json::json!({
+                        "path": to,
+                        "mode": entry.mode,
+                        "type": "blob",
+                        "sha": entry.sha,
+                    }));
+                    entries.push(serde_json::json!({
+                        "path": from,
+                        "mode": entry.mode,
+                        "type": "blob",
+                        "sha": null,
+                    }));
+                }
+            }
+        }
+
+        // Build the new tree on top of the current tree, so unchanged files are kept.
+        let tree: git_data::Tree = self.post_idempotent(
+            format!("/repos/{}/{}/git/trees", repo.owner, repo.name),
+            &serde_json::json!({
+                "base_tree": head_commit.tree.sha,
+                "tree": entries,
+            }),
+        ).await?;
+
+        let commit: git_data::Commit = self.post_idempotent(
+            format!("/repos/{}/{}/git/commits", repo.owner, repo.name),
+            &serde_json::json!({
+                "message": commit_message,
+                "tree": tree.sha,
+                "parents": [head],
+            }),
+        ).await?;
+
+        // Move the branch to the new commit. This is not forced, so concurrent updates to the branch are not overwritten.
+        let _: github_api_responses::reference::Reference = self.patch(
+            format!("/repos/{}/{}/git/refs/heads/{}", repo.owner, repo.name, branch),
+            &serde_json::json!({
+                "sha": commit.sha,
+                "force": false,
+            }),
+        ).await?;
+
+        Ok(commit.sha)
+    }
+
+    /// Create a pull request from a branch of the contributor's repository to the upstream repository.
+    ///
+    /// # Arguments
+    /// - `upstream_repo`: The upstream repository to open the pull request against.
+    /// - `contributor_repo`: The repository containing the head branch, usually the contributor's fork.
+    /// - `title`: The title of the pull request.
+    /// - `body`: The body of the pull request.
+    /// - `head`: The name of the branch containing the changes. e.g. `select/news/20240715-hello`.
+    /// - `base`: The name of the branch to pull the changes into. e.g. `main`.
+    /// - `draft`: Whether to open the pull request as a draft.
+    ///
+    /// # Returns
+    /// - `Result<PullRequest>`
+    ///     - `Ok(PullRequest)`: The created [PullRequest](PullRequest), including its number and URL.
+    ///     - `Err(Error)`: An error indicating why the method failed.
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_pull_request(
         &self,
         upstream_repo: &GitHubRepo,
         contributor_repo: &GitHubRepo,
         title: &str,
+        body: &str,
         head: &str,
         base: &str,
-    ) -> Result<(), String> {
-        let head = if upstream_repo == contributor_repo {
+        draft: bool,
+    ) -> Result<PullRequest> {
+        let head_label = if upstream_repo == contributor_repo {
             head.to_string()
         } else {
             format!("{}:{}", contributor_repo.owner.clone(), head)
         };
 
-        let response = self.octocrab.pulls(upstream_repo.owner.clone(), upstream_repo.name.clone())
-            .create(
-                title,
-                &head,
-                base,
-            )
-            .send()
-            .await;
-
-        if response.is_err() {
-            let error_message = format!(
-                "Failed to create a pull request from {:?} to {:?} in the repository. Error: {:?}",
-                head, base, response.err().unwrap()
-            );
-            return Err(error_message);
+        let pull_request = self.retry_write(|| async {
+            self.octocrab.pulls(upstream_repo.owner.clone(), upstream_repo.name.clone())
+                .create(
+                    title,
+                    &head_label,
+                    base,
+                )
+                .body(body)
+                .draft(draft)
+                .send()
+                .await
+        }).await;
+
+        match pull_request {
+            Ok(pull_request) => Ok(pull_request.into()),
+            // The pull request may have been opened even though the request failed,
+            // in which case it is the open pull request from the branch.
+            Err(e) if may_have_been_handled(&e) => {
+                let opened = self.list_branch_pull_requests(upstream_repo, contributor_repo, head).await
+                    .ok()
+                    .and_then(|pull_requests| pull_requests.into_iter().find(|pr| pr.state == PullRequestState::Open));
+                opened.ok_or(e)
+            }
+            Err(e) => Err(e),
         }
+    }
+
+    /// Add labels to a pull request.
+    ///
+    /// # Arguments
+    /// - `upstream_repo`: The repository the pull request is opened against.
+    /// - `number`: The number of the pull request.
+    /// - `labels`: The names of the labels to add. e.g. `["选题"]`.
+    ///
+    /// # Returns
+    /// - `Result<()>`: `Ok` if the labels are added, otherwise an error indicating why the method failed.
+    pub async fn add_labels(&self, upstream_repo: &GitHubRepo, number: u64, labels: &[String]) -> Result<()> {
+        // Pull requests are issues in the REST API, so labels are managed by the issues endpoints.
+        // Labels, reviewers and assignees that are added twice are only kept once.
+        let _: serde_json::Value = self.post_idempotent(
+            format!("/repos/{}/{}/issues/{}/labels", upstream_repo.owner, upstream_repo.name, number),
+            &serde_json::json!({
+                "labels": labels,
+            }),
+        ).await?;
+
         Ok(())
     }
 
-    pub async fn get_contents(&self, repo: &GitHubRepo, path: &str) -> Result<github_api_responses::repository_content::RepositoryContent, String> {
-        let path = path.strip_prefix("/").unwrap_or_else(|| path);
+    /// Request reviews of a pull request from users.
+    ///
+    /// # Arguments
+    /// - `upstream_repo`: The repository the pull request is opened against.
+    /// - `number`: The number of the pull request.
+    /// - `reviewers`: The usernames of the users to request a review from.
+    ///
+    /// # Returns
+    /// - `Result<()>`: `Ok` if the reviews are requested, otherwise an error indicating why the method failed.
+    pub async fn request_reviewers(&self, upstream_repo: &GitHubRepo, number: u64, reviewers: &[String]) -> Result<()> {
+        let _: serde_json::Value = self.post_idempotent(
+            format!("/repos/{}/{}/pulls/{}/requested_reviewers", upstream_repo.owner, upstream_repo.name, number),
+            &serde_json::json!({
+                "reviewers": reviewers,
+            }),
+        ).await?;
 
-        let mut headers = HeaderMap::new();
-        headers.insert("ACCEPT", HeaderValue::from_static("application/vnd.github.v3.object"));
+        Ok(())
+    }
 
-        let response = self.octocrab._get_with_headers(
-            format!("/repos/{}/{}/contents/{path}", repo.owner, repo.name),
-            Some(headers),
-        ).await;
+    /// Assign users to a pull request.
+    ///
+    /// # Arguments
+    /// - `upstream_repo`: The repository the pull request is opened against.
+    /// - `number`: The number of the pull request.
+    /// - `assignees`: The usernames of the users to assign.
+    ///
+    /// # Returns
+    /// - `Result<()>`: `Ok` if the users are assigned, otherwise an error indicating why the method failed.
+    pub async fn add_assignees(&self, upstream_repo: &GitHubRepo, number: u64, assignees: &[String]) -> Result<()> {
+        let _: serde_json::Value = self.post_idempotent(
+            format!("/repos/{}/{}/issues/{}/assignees", upstream_repo.owner, upstream_repo.name, number),
+            &serde_json::json!({
+                "assignees": assignees,
+            }),
+        ).await?;
 
-        if response.is_err() {
-            return Err(
-                format!(
-                    "Failed to get the contents of the {:?} directory in the {:?} repository.",
-                    path, repo.get_full_name()
-                )
-            );
+        Ok(())
+    }
+
+    /// Get a pull request by its number.
+    ///
+    /// # Arguments
+    /// - `upstream_repo`: The repository the pull request is opened against.
+    /// - `number`: The number of the pull request.
+    ///
+    /// # Returns
+    /// - `Result<PullRequest>`
+    ///     - `Ok(PullRequest)`: The [PullRequest](PullRequest).
+    ///     - `Err(Error)`: [`Error::NotFound`](Error::NotFound) if the pull request does not exist, or another error indicating why the method failed.
+    pub async fn get_pull_request(&self, upstream_repo: &GitHubRepo, number: u64) -> Result<PullRequest> {
+        let pull_request = self.retry(|| async {
+            self.octocrab.pulls(upstream_repo.owner.clone(), upstream_repo.name.clone())
+                .get(number)
+                .await
+        }).await?;
+
+        Ok(pull_request.into())
+    }
+
+    /// Get every open pull request against a repository, from the newest to the oldest.
+    async fn fetch_open_pull_requests(&self, upstream_repo: &GitHubRepo) -> Result<Vec<octocrab::models::pulls::PullRequest>> {
+        let first_page = self.retry(|| async {
+            self.octocrab.pulls(upstream_repo.owner.clone(), upstream_repo.name.clone())
+                .list()
+                .state(octocrab::params::State::Open)
+                .per_page(100)
+                .send()
+                .await
+        }).await?;
+        self.retry(|| async { self.octocrab.all_pages(first_page.clone()).await }).await
+    }
+
+    /// List every open pull request against a repository, whoever opened it.
+    ///
+    /// # Arguments
+    /// - `upstream_repo`: The repository to list the pull requests of.
+    ///
+    /// # Returns
+    /// - `Result<Vec<PullRequest>>`
+    ///     - `Ok(Vec<PullRequest>)`: The open [PullRequest](PullRequest)s, from the newest to the oldest.
+    ///     - `Err(Error)`: An error indicating why the method failed.
+    pub async fn list_open_pull_requests(&self, upstream_repo: &GitHubRepo) -> Result<Vec<PullRequest>> {
+        Ok(self.fetch_open_pull_requests(upstream_repo).await?
+            .into_iter()
+            .map(PullRequest::from)
+            .collect())
+    }
+
+    /// List the open pull requests opened by the authenticated user against a repository.
+    ///
+    /// # Arguments
+    /// - `upstream_repo`: The repository to list the pull requests of.
+    ///
+    /// # Returns
+    /// - `Result<Vec<PullRequest>>`
+    ///     - `Ok(Vec<PullRequest>)`: The open [PullRequest](PullRequest)s of the user, from the newest to the oldest.
+    ///     - `Err(Error)`: An error indicating why the method failed.
+    pub async fn list_user_pull_requests(&self, upstream_repo: &GitHubRepo) -> Result<Vec<PullRequest>> {
+        Ok(self.fetch_open_pull_requests(upstream_repo).await?
+            .into_iter()
+            .filter(|pr| pr.user.as_ref().is_some_and(|user| user.login == self.username))
+            .map(PullRequest::from)
+            .collect())
+    }
+
+    /// List every pull request from a branch of a contributor repository against a repository, whatever its state.
+    ///
+    /// # Arguments
+    /// - `upstream_repo`: The repository the pull requests are opened against.
+    /// - `contributor_repo`: The repository that contains the branch.
+    /// - `branch`: The name of the branch.
+    ///
+    /// # Returns
+    /// - `Result<Vec<PullRequest>>`
+    ///     - `Ok(Vec<PullRequest>)`: The [PullRequest](PullRequest)s from the branch, from the newest to the oldest.
+    ///     - `Err(Error)`: An error indicating why the method failed.
+    pub async fn list_branch_pull_requests(
+        &self,
+        upstream_repo: &GitHubRepo,
+        contributor_repo: &GitHubRepo,
+        branch: &str,
+    ) -> Result<Vec<PullRequest>> {
+        let first_page = self.retry(|| async {
+            self.octocrab.pulls(upstream_repo.owner.clone(), upstream_repo.name.clone())
+                .list()
+                .state(octocrab::params::State::All)
+                .head(format!("{}:{}", contributor_repo.owner, branch))
+                .per_page(100)
+                .send()
+                .await
+        }).await?;
+        let pull_requests = self.retry(|| async { self.octocrab.all_pages(first_page.clone()).await }).await?;
+
+        Ok(pull_requests.into_iter().map(PullRequest::from).collect())
+    }
+
+    /// List the files added, changed or removed by a pull request.
+    ///
+    /// # Arguments
+    /// - `upstream_repo`: The repository the pull request is opened against.
+    /// - `number`: The number of the pull request.
+    ///
+    /// # Returns
+    /// - `Result<Vec<PullRequestFile>>`
+    ///     - `Ok(Vec<PullRequestFile>)`: The [PullRequestFile](PullRequestFile)s of the pull request, sorted by path.
+    ///     - `Err(Error)`: An error indicating why the method failed.
+    pub async fn list_pull_request_files(&self, upstream_repo: &GitHubRepo, number: u64) -> Result<Vec<PullRequestFile>> {
+        let first_page = self.retry(|| async {
+            self.octocrab.pulls(upstream_repo.owner.clone(), upstream_repo.name.clone())
+                .list_files(number)
+                .await
+        }).await?;
+        let files = self.retry(|| async { self.octocrab.all_pages(first_page.clone()).await }).await?;
+
+        let mut files: Vec<PullRequestFile> = files.into_iter().map(PullRequestFile::from).collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
+    /// Get what has happened to a pull request: its review decision, whether it can be merged, and its checks.
+    ///
+    /// # Arguments
+    /// - `upstream_repo`: The repository the pull request is opened against.
+    /// - `number`: The number of the pull request.
+    ///
+    /// # Returns
+    /// - `Result<PullRequestStatus>`
+    ///     - `Ok(PullRequestStatus)`: The [PullRequestStatus](PullRequestStatus) of the pull request.
+    ///     - `Err(Error)`: An error indicating why the method failed.
+    pub async fn pull_request_status(&self, upstream_repo: &GitHubRepo, number: u64) -> Result<PullRequestStatus> {
+        let pulls = self.octocrab.pulls(upstream_repo.owner.clone(), upstream_repo.name.clone());
+        let pull_request = self.retry(|| async { pulls.get(number).await }).await?;
+        let mergeable = pull_request.mergeable;
+        let mergeable_state = pull_request.mergeable_state.as_ref()
+            .and_then(|state| serde_json::to_value(state).ok())
+            .and_then(|state| state.as_str().map(str::to_string));
+
+        let first_page = self.retry(|| async { pulls.list_reviews(number).per_page(100).send().await }).await?;
+        let reviews = self.retry(|| async { self.octocrab.all_pages(first_page.clone()).await }).await?;
+
+        // Only the latest approval or change request of each reviewer counts, just like on GitHub.
+        let mut decisions: HashMap<String, ReviewState> = HashMap::new();
+        for review in reviews {
+            let (Some(user), Some(state)) = (review.user, review.state) else {
+                continue;
+            };
+            match state {
+                ReviewState::Approved | ReviewState::ChangesRequested | ReviewState::Dismissed => {
+                    decisions.insert(user.login, state);
+                }
+                _ => {}
+            }
         }
+        let review_decision = if decisions.values().any(|state| *state == ReviewState::ChangesRequested) {
+            ReviewDecision::ChangesRequested
+        } else if decisions.values().any(|state| *state == ReviewState::Approved) {
+            ReviewDecision::Approved
+        } else {
+            ReviewDecision::Pending
+        };
 
-        let response_body = self.octocrab.body_to_string(response.unwrap()).await;
-        if response_body.is_err() {
-            return Err(
-                format!(
-                    "Failed to get the response body of the contents of the {:?} directory in the {:?} repository.",
-                    path, repo.get_full_name()
-                )
-            );
+        let pull_request = PullRequest::from(pull_request);
+        let check_runs: github_api_responses::check_runs::CheckRuns = self.get(
+            format!(
+                "/repos/{}/{}/commits/{}/check-runs",
+                upstream_repo.owner, upstream_repo.name, pull_request.head_sha
+            ),
+        ).await?;
+        let checks = check_runs.check_runs.into_iter()
+            .map(|check| CheckRun {
+                name: check.name,
+                status: check.status,
+                conclusion: check.conclusion,
+            })
+            .collect();
+
+        Ok(PullRequestStatus {
+            pull_request,
+            review_decision,
+            mergeable,
+            mergeable_state,
+            checks,
+        })
+    }
+
+    /// Close a pull request without merging it.
+    ///
+    /// # Arguments
+    /// - `upstream_repo`: The repository the pull request is opened against.
+    /// - `number`: The number of the pull request.
+    ///
+    /// # Returns
+    /// - `Result<PullRequest>`
+    ///     - `Ok(PullRequest)`: The closed [PullRequest](PullRequest).
+    ///     - `Err(Error)`: An error indicating why the method failed.
+    pub async fn close_pull_request(&self, upstream_repo: &GitHubRepo, number: u64) -> Result<PullRequest> {
+        let pull_request = self.retry(|| async {
+            self.octocrab.pulls(upstream_repo.owner.clone(), upstream_repo.name.clone())
+                .update(number)
+                .state(octocrab::params::pulls::State::Closed)
+                .send()
+                .await
+        }).await?;
+
+        Ok(pull_request.into())
+    }
+
+    /// Mark a draft pull request as ready for review.
+    ///
+    /// The REST API cannot change the draft state of a pull request, so this uses the GraphQL API.
+    ///
+    /// # Arguments
+    /// - `pull_request`: The draft [PullRequest](PullRequest) to mark as ready for review.
+    ///
+    /// # Returns
+    /// - `Result<()>`: `Ok` if the pull request is ready for review, otherwise:
+    ///     - [`Error::GraphQL`](Error::GraphQL) if GitHub rejected the mutation.
+    ///     - Another error indicating why the method failed.
+    pub async fn mark_ready_for_review(&self, pull_request: &PullRequest) -> Result<()> {
+        self.graphql(
+            "mutation($id: ID!) { markPullRequestReadyForReview(input: { pullRequestId: $id }) { pullRequest { isDraft } } }",
+            serde_json::json!({
+                "id": pull_request.node_id,
+            }),
+        ).await?;
+
+        Ok(())
+    }
+
+    /// Get a single tree of a repository from the Git Data API.
+    async fn fetch_tree(&self, repo: &GitHubRepo, tree: &str, recursive: bool) -> Result<github_api_responses::git_data::Tree> {
+        let route = format!("/repos/{}/{}/git/trees/{}", repo.owner, repo.name, tree);
+        let route = if recursive { format!("{}?recursive=1", route) } else { route };
+
+        self.get(route).await.map_err(|e| match e {
+            Error::NotFound(_) => Error::NotFound(format!("tree {} in {}", tree, repo.get_full_name())),
+            e => e,
+        })
+    }
+
+    /// List the files and directories of a repository at a given reference in a single request,
+    /// without the content of the files.
+    ///
+    /// GitHub truncates the recursive listing of very large trees. In that case the subtrees are walked one level at a time instead,
+    /// so the listing is always complete.
+    ///
+    /// # Arguments
+    /// - `repo`: The repository to list the files of.
+    /// - `r#ref`: The branch, tag, commit SHA or tree SHA to list the files at.
+    /// - `recursive`: Whether to list the content of the subdirectories as well, or only the root directory.
+    ///
+    /// # Returns
+    /// - `Result<RepoTree>`
+    ///     - `Ok(RepoTree)`: The [RepoTree](RepoTree) of the repository at the reference.
+    ///     - `Err(Error)`: [`Error::NotFound`](Error::NotFound) if the reference does not exist, or another error indicating why the method failed.
+    pub async fn get_tree(&self, repo: &GitHubRepo, r#ref: &str, recursive: bool) -> Result<RepoTree> {
+        let tree = self.fetch_tree(repo, r#ref, recursive).await?;
+        if !(recursive && tree.truncated) {
+            return Ok(RepoTree::new(tree.sha, tree.tree.into_iter().map(TreeEntry::from).collect()));
         }
 
-        let json_response: Result<github_api_responses::repository_content::RepositoryContent, _> = serde_json::from_str(&*response_body.unwrap());
-        match json_response {
-            Ok(content) => Ok(content),
-            Err(e) => {
-                let error_message = format!(
-                    "Failed to parse the contents of the {:?} directory in the {:?} repository: {:?}",
-                    path, repo.get_full_name(), e
-                );
-                Err(error_message)
+        let mut entries = Vec::new();
+        let mut pending: Vec<TreeEntry> = self.fetch_tree(repo, &tree.sha, false).await?
+            .tree.into_iter().map(TreeEntry::from).collect();
+        while !pending.is_empty() {
+            let dirs: Vec<TreeEntry> = pending.iter()
+                .filter(|entry| entry.kind == TreeEntryKind::Tree)
+                .cloned()
+                .collect();
+            entries.append(&mut pending);
+
+            let listings: Vec<Vec<TreeEntry>> = stream::iter(dirs)
+                .map(|dir| async move {
+                    let subtree = self.fetch_tree(repo, &dir.sha, false).await?;
+                    // Paths in a subtree are relative to the subtree.
+                    Ok::<_, Error>(subtree.tree.into_iter().map(|entry| {
+                        let mut entry = TreeEntry::from(entry);
+                        entry.path = format!("{}/{}", dir.path, entry.path);
+                        entry
+                    }).collect())
+                })
+                .buffered(TREE_WALK_CONCURRENCY)
+                .try_collect()
+                .await?;
+            pending = listings.into_iter().flatten().collect();
+        }
+
+        Ok(RepoTree::new(tree.sha, entries))
+    }
+
+    /// Get the **decoded** content of a file from its blob SHA, e.g. the SHA of a [TreeEntry](TreeEntry).
+    ///
+    /// # Arguments
+    /// - `repo`: The repository to get the file from.
+    /// - `sha`: The SHA of the blob.
+    ///
+    /// # Returns
+    /// - `Result<Vec<u8>>`
+    ///     - `Ok(Vec<u8>)`: The raw content of the file.
+    ///     - `Err(Error)`: [`Error::NotFound`](Error::NotFound) if the blob does not exist, or another error indicating why the method failed.
+    pub async fn get_blob(&self, repo: &GitHubRepo, sha: &str) -> Result<Vec<u8>> {
+        use base64::Engine;
+
+        let blob: github_api_responses::git_data::BlobContent = self.get(
+            format!("/repos/{}/{}/git/blobs/{}", repo.owner, repo.name, sha),
+        ).await.map_err(|e| match e {
+            Error::NotFound(_) => Error::NotFound(format!("blob {} in {}", sha, repo.get_full_name())),
+            e => e,
+        })?;
+
+        match blob.encoding.as_str() {
+            "base64" => {
+                // The encoded content is wrapped in lines.
+                let content: String = blob.content.split_whitespace().collect();
+                base64::prelude::BASE64_STANDARD.decode(content)
+                    .map_err(|e| Error::parse(format!("the content of the blob {}", sha), e))
             }
+            _ => Ok(blob.content.into_bytes()),
+        }
+    }
+
+    /// Get the **decoded** content of several files of a [RepoTree](RepoTree),
+    /// with at most `concurrency` requests in flight at the same time.
+    ///
+    /// A file that fails to be fetched does not stop the others from being fetched.
+    ///
+    /// # Arguments
+    /// - `repo`: The repository to get the files from.
+    /// - `entries`: The entries of the files to get, e.g. `tree.files_in("sources")`.
+    /// - `concurrency`: The maximum number of concurrent requests.
+    ///
+    /// # Returns
+    /// - `Vec<(String, Result<Vec<u8>>)>`: The path of every file with its raw content, or the error of fetching it, in the order of `entries`.
+    pub async fn get_blobs<'a>(
+        &self,
+        repo: &GitHubRepo,
+        entries: impl IntoIterator<Item = &'a TreeEntry>,
+        concurrency: usize,
+    ) -> Vec<(String, Result<Vec<u8>>)> {
+        stream::iter(entries)
+            .map(|entry| async move {
+                (entry.path.clone(), self.get_blob(repo, &entry.sha).await)
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    pub async fn get_contents(&self, repo: &GitHubRepo, path: &str) -> Result<github_api_responses::repository_content::RepositoryContent> {
+        let path = path.strip_prefix("/").unwrap_or_else(|| path);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("ACCEPT", HeaderValue::from_static("application/vnd.github.v3.object"));
+
+        let content = self.retry(|| async {
+            self.octocrab.get_with_headers(
+                format!("/repos/{}/{}/contents/{path}", repo.owner, repo.name),
+                None::<&()>,
+                Some(headers.clone()),
+            ).await
+        }).await;
+
+        match content {
+            Ok(content) => Ok(content),
+            Err(e) => match e {
+                Error::NotFound(_) => Err(Error::NotFound(format!("{:?} in {}", path, repo.get_full_name()))),
+                e => Err(e),
+            },
         }
     }
 
     pub fn get_all_file_contents<'a>(&'a self, repo: &'a GitHubRepo, path: &'a str) -> BoxFuture<'a, Result<
-        Vec<github_api_responses::repository_content::RepositoryContent>, String
+        Vec<github_api_responses::repository_content::RepositoryContent>
     >> {
         async move {
             let mut contents = Vec::new();
@@ -415,7 +1745,7 @@ impl GitHubApi {
                             contents.push(content);
                         }
                         github_api_responses::repository_content::RepositoryContent::Dir(content) => {
-                            for item in content.entries.unwrap() {
+                            for item in content.entries.unwrap_or_default() {
                                 match item {
                                     github_api_responses::repository_content::RepositoryContent::File(content) => {
                                         let file_content = self.get_contents(repo, &content.path).await;
@@ -446,7 +1776,7 @@ impl GitHubApi {
     }
 
     pub async fn get_all_file_contents_in_repo<'a> (&'a self, repo: &'a GitHubRepo) -> Result<
-        Vec<github_api_responses::repository_content::RepositoryContent>, String
+        Vec<github_api_responses::repository_content::RepositoryContent>
     > {
         self.get_all_file_contents(repo, "").await
     }
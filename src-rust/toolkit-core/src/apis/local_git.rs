@@ -0,0 +1,198 @@
+use std::path::{Path, PathBuf};
+
+use tokio::process::Command;
+
+use crate::error::{Error, Result};
+use crate::models::{FileChange, GitHubRepo};
+
+/// A local clone of a repository, managed with the `git` command of the user.
+///
+/// Unlike the REST API, commits are created locally with the Git configuration of the user
+/// (name, email, signing key, hooks...), so the history can be inspected and changes can be made offline before they are pushed.
+///
+/// # Fields
+/// - `path`: The path of the working tree of the clone.
+pub struct LocalGitRepo {
+    path: PathBuf,
+}
+
+impl LocalGitRepo {
+    /// Open an existing local clone.
+    ///
+    /// # Returns
+    /// - `Result<LocalGitRepo>`
+    ///     - `Ok(LocalGitRepo)`: The local clone.
+    ///     - `Err(Error)`: [`Error::NotFound`](Error::NotFound) if the path is not the working tree of a Git repository.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if !path.join(".git").exists() {
+            return Err(Error::NotFound(format!("Git repository at {}", path.display())));
+        }
+        Ok(Self { path })
+    }
+
+    /// Clone a repository to a path.
+    ///
+    /// # Arguments
+    /// - `url`: The URL to clone from. Any URL accepted by `git clone`, including the path of a local bare repository.
+    /// - `path`: The path to clone to, which must not exist yet or be empty.
+    pub async fn clone_from(url: &str, path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // `--` keeps a URL starting with `-` from being read as an option of `git clone`.
+        run_git(None, &["clone", "--", url, &path.to_string_lossy()]).await?;
+        Ok(Self { path })
+    }
+
+    /// Open the clone at a path, cloning the repository there first if it has not been cloned yet.
+    pub async fn open_or_clone(url: &str, path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        match Self::open(&path) {
+            Ok(repo) => Ok(repo),
+            Err(Error::NotFound(_)) => Self::clone_from(url, path).await,
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get the path in the runtime storage directory `workspace` where a repository is cloned to. (`<workspace>/repos/<owner>/<name>`)
+    pub fn workspace_path(workspace: &Path, repo: &GitHubRepo) -> PathBuf {
+        workspace.join("repos").join(&repo.owner).join(&repo.name)
+    }
+
+    /// Get the path of the working tree of the clone.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Run a `git` command in the clone, returning its trimmed standard output.
+    async fn git(&self, args: &[&str]) -> Result<String> {
+        run_git(Some(&self.path), args).await
+    }
+
+    /// Add a remote, or change its URL if it already exists.
+    pub async fn set_remote(&self, name: &str, url: &str) -> Result<()> {
+        let remotes = self.git(&["remote"]).await?;
+        if remotes.lines().any(|remote| remote == name) {
+            self.git(&["remote", "set-url", name, url]).await?;
+        } else {
+            self.git(&["remote", "add", name, url]).await?;
+        }
+        Ok(())
+    }
+
+    /// Fetch a branch from a remote, updating `<remote>/<branch>`.
+    pub async fn fetch(&self, remote: &str, branch: &str) -> Result<()> {
+        let refspec = format!("+refs/heads/{branch}:refs/remotes/{remote}/{branch}");
+        self.git(&["fetch", remote, &refspec]).await?;
+        Ok(())
+    }
+
+    /// Whether a remote has a branch, asking the remote instead of reading the remote-tracking branches.
+    pub async fn remote_has_branch(&self, remote: &str, branch: &str) -> Result<bool> {
+        let r#ref = format!("refs/heads/{branch}");
+        match self.git(&["ls-remote", "--exit-code", remote, &r#ref]).await {
+            Ok(_) => Ok(true),
+            // `ls-remote --exit-code` fails silently (with the exit status 2) when the remote does not have the reference,
+            // while failing to reach the remote is reported on the standard error.
+            Err(Error::Git(message)) if message.is_empty() => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Whether a reference (e.g. `refs/heads/main` or `refs/remotes/origin/main`) exists.
+    pub async fn has_ref(&self, r#ref: &str) -> Result<bool> {
+        match self.git(&["rev-parse", "--verify", "--quiet", r#ref]).await {
+            Ok(_) => Ok(true),
+            // `rev-parse --verify --quiet` fails silently when the reference does not exist.
+            Err(Error::Git(message)) if message.is_empty() => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Check out a branch at `start_point` (e.g. `upstream/main`), creating it if it does not exist locally.
+    ///
+    /// A local branch that already exists is reset to `start_point`, so a stale branch (e.g. left behind by a branch deleted
+    /// in the remote, or by a failed attempt) is not committed on. Commits of the local branch that are not in `start_point` are dropped.
+    pub async fn checkout_branch(&self, branch: &str, start_point: &str) -> Result<()> {
+        self.git(&["checkout", "--quiet", "-B", branch, start_point]).await?;
+        Ok(())
+    }
+
+    /// Apply [FileChange](FileChange)s to the working tree, and stage them.
+    pub async fn apply_changes(&self, changes: &[FileChange]) -> Result<()> {
+        for change in changes {
+            match change {
+                FileChange::Write { path, content } => {
+                    let file = self.path.join(path);
+                    if let Some(parent) = file.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    std::fs::write(file, content)?;
+                    self.git(&["add", "--", path]).await?;
+                }
+                FileChange::Delete { path } => {
+                    self.git(&["rm", "--quiet", "--", path]).await?;
+                }
+                FileChange::Rename { from, to } => {
+                    if let Some(parent) = self.path.join(to).parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    self.git(&["mv", "--", from, to]).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Commit the staged changes, returning the SHA of the commit.
+    ///
+    /// The commit is created with the Git configuration of the user, e.g. it is signed if `commit.gpgsign` is set.
+    /// If nothing is staged (e.g. the content is the same as in the last commit), no commit is created and the SHA of `HEAD` is returned.
+    pub async fn commit(&self, message: &str) -> Result<String> {
+        if self.has_staged_changes().await? {
+            self.git(&["commit", "--quiet", "-m", message]).await?;
+        }
+        self.head_sha().await
+    }
+
+    /// Whether the index has changes that are not committed yet.
+    async fn has_staged_changes(&self) -> Result<bool> {
+        match self.git(&["diff", "--cached", "--quiet"]).await {
+            Ok(_) => Ok(false),
+            // `diff --quiet` fails silently when there are differences.
+            Err(Error::Git(message)) if message.is_empty() => Ok(true),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get the SHA of the commit checked out.
+    pub async fn head_sha(&self) -> Result<String> {
+        self.git(&["rev-parse", "HEAD"]).await
+    }
+
+    /// Push a branch to a remote, setting it as the upstream branch.
+    pub async fn push(&self, remote: &str, branch: &str) -> Result<()> {
+        self.git(&["push", "--quiet", "--set-upstream", remote, branch]).await?;
+        Ok(())
+    }
+}
+
+/// Run a `git` command, in a working tree if one is given, returning its trimmed standard output.
+///
+/// A failing command is reported as an [`Error::Git`](Error::Git) with its standard error.
+async fn run_git(dir: Option<&Path>, args: &[&str]) -> Result<String> {
+    let mut command = Command::new("git");
+    if let Some(dir) = dir {
+        command.current_dir(dir);
+    }
+    // Never wait for credentials on the terminal, which would hang the toolkit.
+    command.env("GIT_TERMINAL_PROMPT", "0");
+    let output = command.args(args).output().await?;
+
+    if !output.status.success() {
+        return Err(Error::Git(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -1,3 +1,13 @@
+pub use forge::Forge;
+pub use gitea_api::GiteaApi;
 pub use github_api::GitHubApi;
+pub use local_git::LocalGitRepo;
+pub use retry::{ForkWaitPolicy, RetryPolicy};
+pub use sandbox_forge::{SANDBOX_UPSTREAM_REPOS, SandboxForge};
 
+mod forge;
+mod gitea_api;
 mod github_api;
+mod local_git;
+mod retry;
+mod sandbox_forge;
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+/// How requests to the GitHub API are retried when they fail with a transient error,
+/// i.e. a network error, a server error (5xx), or a secondary rate limit.
+///
+/// The delay before each retry doubles, starting at `initial_backoff` and capped at `max_backoff`.
+/// If GitHub asks to wait for a specific time with the `Retry-After` header, that time is used instead.
+/// Requests that are not idempotent (e.g. opening a pull request) are only retried on a secondary rate limit,
+/// as they may have been handled before a network error or a server error.
+///
+/// # Fields
+/// - `max_retries`: The maximum number of times a request is retried.
+/// - `initial_backoff`: The delay before the first retry.
+/// - `max_backoff`: The maximum delay before a retry.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RetryPolicy {
+    /// The maximum number of times a request is retried.
+    pub max_retries: u32,
+    /// The delay before the first retry.
+    pub initial_backoff: Duration,
+    /// The maximum delay before a retry.
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: u32, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_backoff,
+            max_backoff,
+        }
+    }
+
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self::new(0, Duration::ZERO, Duration::ZERO)
+    }
+
+    /// Get the delay before a retry.
+    ///
+    /// # Arguments
+    /// - `attempt`: The number of retries already made, starting at 0.
+    ///
+    /// # Returns
+    /// - `Duration`: The exponential backoff delay for the retry.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3, Duration::from_secs(1), Duration::from_secs(60))
+    }
+}
+
+/// How long to wait for a fork to become available after requesting it.
+///
+/// GitHub creates forks asynchronously, so the Git data of a new fork is checked every `interval`
+/// until it is available, or until `timeout` has passed.
+///
+/// # Fields
+/// - `interval`: The delay between two checks.
+/// - `timeout`: The maximum time to wait for the fork.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ForkWaitPolicy {
+    /// The delay between two checks.
+    pub interval: Duration,
+    /// The maximum time to wait for the fork.
+    pub timeout: Duration,
+}
+
+impl ForkWaitPolicy {
+    pub fn new(interval: Duration, timeout: Duration) -> Self {
+        Self { interval, timeout }
+    }
+}
+
+impl Default for ForkWaitPolicy {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(2), Duration::from_secs(300))
+    }
+}
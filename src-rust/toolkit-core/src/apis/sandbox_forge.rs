@@ -0,0 +1,812 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use sha2::{Digest, Sha256};
+
+use crate::apis::Forge;
+use crate::error::{Error, Result};
+use crate::models::{
+    Branch, FileChange, ForkStatus, GitHubRepo, PullRequest, PullRequestFile, PullRequestState, PullRequestStatus, RepoTree,
+    ReviewDecision, TreeEntry, TreeEntryKind,
+};
+
+/// The URL the pull requests of the sandbox appear to be at. The `.invalid` top-level domain never resolves.
+const SANDBOX_URL: &str = "https://sandbox.fosscope.invalid";
+
+/// The upstream repositories the sandbox is seeded with, each with a sample REPORULE.
+pub const SANDBOX_UPSTREAM_REPOS: [(&str, &str); 2] = [("FOSScope", "Articles"), ("FOSScope", "TranslateProject")];
+
+/// The sample repository rule the upstream repositories of the sandbox are seeded with.
+const SAMPLE_REPO_RULE: &str = include_str!("../../resources/sandbox/REPORULE");
+
+/// The README the upstream repositories of the sandbox are seeded with.
+const SAMPLE_README: &str = include_str!("../../resources/sandbox/README.md");
+
+/// A repository of the sandbox.
+///
+/// # Fields
+/// - `repo`: The owner and name of the repository.
+/// - `parent`: The upstream repository, if the repository is a fork.
+/// - `default_branch`: The default branch of the repository.
+/// - `branches`: The SHA of the head commit of each branch.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct SandboxRepo {
+    repo: GitHubRepo,
+    parent: Option<GitHubRepo>,
+    default_branch: String,
+    branches: BTreeMap<String, String>,
+}
+
+/// A commit of the sandbox, with a snapshot of every file of the repository.
+///
+/// # Fields
+/// - `parent`: The SHA of the parent commit, `None` for the first commit of a repository.
+/// - `message`: The commit message.
+/// - `files`: The content of every file, keyed by its path.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct SandboxCommit {
+    parent: Option<String>,
+    message: String,
+    #[serde(with = "base64_files")]
+    files: BTreeMap<String, Vec<u8>>,
+}
+
+/// A pull request of the sandbox.
+///
+/// # Fields
+/// - `upstream`: The repository the pull request is opened against.
+/// - `pull_request`: The pull request itself.
+/// - `body`, `labels`, `reviewers`, `assignees`: What the pull request has been opened with.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct SandboxPullRequest {
+    upstream: GitHubRepo,
+    pull_request: PullRequest,
+    body: String,
+    labels: Vec<String>,
+    reviewers: Vec<String>,
+    assignees: Vec<String>,
+}
+
+/// Everything stored in the sandbox, which is saved to the file of a file-backed sandbox.
+///
+/// Commits are shared by every repository, like in the fork network of a repository on GitHub.
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct SandboxState {
+    repos: BTreeMap<String, SandboxRepo>,
+    commits: BTreeMap<String, SandboxCommit>,
+    pull_requests: Vec<SandboxPullRequest>,
+    commit_count: u64,
+}
+
+/// Stores the content of the files as Base64, so the saved state stays readable JSON.
+mod base64_files {
+    use std::collections::BTreeMap;
+
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde::de::Error;
+
+    pub fn serialize<S: Serializer>(files: &BTreeMap<String, Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(files.iter().map(|(path, content)| (path, STANDARD.encode(content))))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<String, Vec<u8>>, D::Error> {
+        BTreeMap::<String, String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(path, content)| STANDARD.decode(content).map(|content| (path, content)).map_err(D::Error::custom))
+            .collect()
+    }
+}
+
+/// The key a repository is stored under. Owners and names are case-insensitive, just like on GitHub.
+fn repo_key(repo: &GitHubRepo) -> String {
+    repo.get_full_name().to_lowercase()
+}
+
+fn same_repo(a: &GitHubRepo, b: &GitHubRepo) -> bool {
+    repo_key(a) == repo_key(b)
+}
+
+/// The blob SHA of the content of a file in the sandbox, hashed like Git does in a SHA-256 repository (`blob <len>\0<content>`),
+/// so it stays the same across runs and versions of the toolkit.
+fn blob_sha(content: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("blob {}\0", content.len()));
+    hasher.update(content);
+    hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+impl SandboxState {
+    fn repo(&self, repo: &GitHubRepo) -> Result<&SandboxRepo> {
+        self.repos.get(&repo_key(repo))
+            .ok_or_else(|| Error::NotFound(format!("repository {}", repo.get_full_name())))
+    }
+
+    fn repo_mut(&mut self, repo: &GitHubRepo) -> Result<&mut SandboxRepo> {
+        self.repos.get_mut(&repo_key(repo))
+            .ok_or_else(|| Error::NotFound(format!("repository {}", repo.get_full_name())))
+    }
+
+    /// Get the SHA of the head commit of a branch.
+    fn head(&self, repo: &GitHubRepo, branch: &str) -> Result<String> {
+        self.repo(repo)?.branches.get(branch).cloned()
+            .ok_or_else(|| Error::NotFound(format!("branch {} in {}", branch, repo.get_full_name())))
+    }
+
+    fn files(&self, sha: &str) -> &BTreeMap<String, Vec<u8>> {
+        &self.commits[sha].files
+    }
+
+    /// Get the content of a file on a branch.
+    fn file(&self, repo: &GitHubRepo, branch: &str, path: &str) -> Result<&Vec<u8>> {
+        let head = self.head(repo, branch)?;
+        self.files(&head).get(path.trim_start_matches('/'))
+            .ok_or_else(|| Error::NotFound(format!("file {} in {}", path, repo.get_full_name())))
+    }
+
+    /// Store a new commit, returning its SHA.
+    fn add_commit(&mut self, parent: Option<String>, message: &str, files: BTreeMap<String, Vec<u8>>) -> String {
+        self.commit_count += 1;
+        let sha = format!("{:040x}", self.commit_count);
+        self.commits.insert(sha.clone(), SandboxCommit {
+            parent,
+            message: message.to_string(),
+            files,
+        });
+        sha
+    }
+
+    /// Resolve a branch or a commit SHA of a repository to a commit SHA.
+    fn resolve(&self, repo: &GitHubRepo, r#ref: &str) -> Result<String> {
+        match self.head(repo, r#ref) {
+            Err(Error::NotFound(_)) if self.commits.contains_key(r#ref) => Ok(r#ref.to_string()),
+            result => result,
+        }
+    }
+
+    /// Commit new file contents on top of the head of a branch, moving the branch to the new commit.
+    fn commit_on(&mut self, repo: &GitHubRepo, branch: &str, message: &str, files: BTreeMap<String, Vec<u8>>) -> Result<String> {
+        let head = self.head(repo, branch)?;
+        let sha = self.add_commit(Some(head), message, files);
+        self.repo_mut(repo)?.branches.insert(branch.to_string(), sha.clone());
+        Ok(sha)
+    }
+
+    /// Get the SHAs of a commit and all of its ancestors, from the newest to the oldest.
+    fn ancestors(&self, sha: &str) -> Vec<String> {
+        let mut ancestors = Vec::new();
+        let mut next = Some(sha.to_string());
+        while let Some(sha) = next {
+            next = self.commits.get(&sha).and_then(|commit| commit.parent.clone());
+            ancestors.push(sha);
+        }
+        ancestors
+    }
+
+    /// Get the newest commit of `head` that is also on `base`, i.e. where a branch was created from.
+    fn merge_base(&self, base: &str, head: &str) -> Option<String> {
+        let base_ancestors: HashSet<String> = self.ancestors(base).into_iter().collect();
+        self.ancestors(head).into_iter().find(|sha| base_ancestors.contains(sha))
+    }
+
+    /// Get a pull request, with the SHA of the head of its branch if it is still open.
+    fn pull_request(&self, upstream: &GitHubRepo, number: u64) -> Result<PullRequest> {
+        let pr = self.pull_requests.iter()
+            .find(|pr| same_repo(&pr.upstream, upstream) && pr.pull_request.number == number)
+            .ok_or_else(|| Error::NotFound(format!("pull request #{} in {}", number, upstream.get_full_name())))?;
+        Ok(self.current(pr))
+    }
+
+    /// Get a pull request as it is now, following the commits pushed to its branch while it is open.
+    fn current(&self, pr: &SandboxPullRequest) -> PullRequest {
+        let mut pull_request = pr.pull_request.clone();
+        if pull_request.state == PullRequestState::Open {
+            let head_repo = pull_request.head_repo.as_ref().unwrap_or(&pr.upstream);
+            if let Ok(sha) = self.head(head_repo, &pull_request.head) {
+                pull_request.head_sha = sha;
+            }
+        }
+        pull_request
+    }
+
+    fn pull_request_mut(&mut self, upstream: &GitHubRepo, number: u64) -> Result<&mut SandboxPullRequest> {
+        self.pull_requests.iter_mut()
+            .find(|pr| same_repo(&pr.upstream, upstream) && pr.pull_request.number == number)
+            .ok_or_else(|| Error::NotFound(format!("pull request #{} in {}", number, upstream.get_full_name())))
+    }
+}
+
+/// An in-memory forge to rehearse contributing without touching any real repository, implementing the [Forge](Forge) trait.
+///
+/// It keeps repositories, forks, branches, files and pull requests the way GitHub does, and reports the same errors,
+/// e.g. [`Error::NotFound`](Error::NotFound) for a missing file or [`Error::Conflict`](Error::Conflict) for an outdated SHA.
+/// A file-backed sandbox saves everything after each change, so the progress is kept across runs.
+///
+/// # Fields
+/// - `username`: The username of the user of the sandbox.
+/// - `state`: Every repository, commit and pull request of the sandbox.
+/// - `path`: The JSON file the sandbox is saved to, `None` for a sandbox that only lives in memory.
+pub struct SandboxForge {
+    username: String,
+    state: Mutex<SandboxState>,
+    path: Option<PathBuf>,
+}
+
+impl SandboxForge {
+    /// Create an empty sandbox that only lives in memory.
+    pub fn new(username: String) -> Self {
+        Self {
+            username,
+            state: Mutex::new(SandboxState::default()),
+            path: None,
+        }
+    }
+
+    /// Create a sandbox that only lives in memory, seeded with the upstream repositories in
+    /// [`SANDBOX_UPSTREAM_REPOS`](SANDBOX_UPSTREAM_REPOS), each containing a sample REPORULE.
+    pub fn seeded(username: String) -> Self {
+        let sandbox = Self::new(username);
+        for (owner, name) in SANDBOX_UPSTREAM_REPOS {
+            let files = [("README.md", SAMPLE_README), ("REPORULE", SAMPLE_REPO_RULE)];
+            // A new sandbox has none of the repositories, so this cannot fail.
+            let _ = sandbox.add_repo(&GitHubRepo::new(owner.to_string(), name.to_string()), &files);
+        }
+        sandbox
+    }
+
+    /// Start the sandbox mode, in which every repository and pull request only exists on this computer.
+    ///
+    /// The sandbox saved in the directory is [opened](SandboxForge::open) for the user `sandbox`.
+    /// The callers keep the rest of the runtime storage of the sandbox mode in the same directory.
+    ///
+    /// # Arguments
+    /// - `dir`: The runtime storage directory of the sandbox mode, e.g. [`SANDBOX_DIR`](crate::SANDBOX_DIR).
+    ///
+    /// # Returns
+    /// - `Result<SandboxForge>`
+    ///     - `Ok(SandboxForge)`: The sandbox.
+    ///     - `Err(Error)`: An error indicating why the sandbox could not be opened.
+    pub fn start(dir: &Path) -> Result<Self> {
+        Self::open("sandbox".to_string(), dir.join("sandbox.json"))
+    }
+
+    /// Open a file-backed sandbox, which is seeded like [`seeded`](SandboxForge::seeded) if the file does not exist yet.
+    ///
+    /// # Arguments
+    /// - `username`: The username of the user of the sandbox.
+    /// - `path`: The JSON file the sandbox is loaded from and saved to.
+    ///
+    /// # Returns
+    /// - `Result<SandboxForge>`
+    ///     - `Ok(SandboxForge)`: The sandbox.
+    ///     - `Err(Error)`: An error indicating why the file could not be read or parsed.
+    pub fn open(username: String, path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        if path.exists() {
+            let json = std::fs::read_to_string(&path)?;
+            let state: SandboxState = serde_json::from_str(&json)
+                .map_err(|e| Error::parse(path.display().to_string(), e))?;
+            return Ok(Self {
+                username,
+                state: Mutex::new(state),
+                path: Some(path),
+            });
+        }
+
+        let mut sandbox = Self::seeded(username);
+        sandbox.path = Some(path);
+        sandbox.save(&*sandbox.lock()?)?;
+        Ok(sandbox)
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, SandboxState>> {
+        self.state.lock().map_err(|_| Error::Forge("The sandbox is unusable after a panic".to_string()))
+    }
+
+    /// Save the state to the file of a file-backed sandbox.
+    fn save(&self, state: &SandboxState) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(state)
+            .map_err(|e| Error::parse("the sandbox", e))?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Read the state of the sandbox.
+    fn read<T>(&self, f: impl FnOnce(&SandboxState) -> Result<T>) -> Result<T> {
+        f(&*self.lock()?)
+    }
+
+    /// Change the state of the sandbox in place, and save it once the change succeeds.
+    ///
+    /// Every operation checks that it can be made before changing anything,
+    /// so a failed operation leaves nothing half done, just like a rejected API request.
+    fn update<T>(&self, f: impl FnOnce(&mut SandboxState) -> Result<T>) -> Result<T> {
+        let mut state = self.lock()?;
+        let result = f(&mut state)?;
+        self.save(&state)?;
+        Ok(result)
+    }
+
+    /// Create a repository that is not a fork, with a first commit on `main` containing the given files.
+    ///
+    /// # Arguments
+    /// - `repo`: The owner and name of the repository.
+    /// - `files`: The path and content of each file.
+    ///
+    /// # Returns
+    /// - `Result<()>`: `Ok` if the repository is created, or [`Error::Conflict`](Error::Conflict) if it already exists.
+    pub fn add_repo(&self, repo: &GitHubRepo, files: &[(&str, &str)]) -> Result<()> {
+        self.update(|state| {
+            if state.repo(repo).is_ok() {
+                return Err(Error::Conflict(format!("repository {} already exists", repo.get_full_name())));
+            }
+            let files = files.iter()
+                .map(|(path, content)| (path.to_string(), content.as_bytes().to_vec()))
+                .collect();
+            let sha = state.add_commit(None, "Initial commit", files);
+            state.repos.insert(repo_key(repo), SandboxRepo {
+                repo: repo.clone(),
+                parent: None,
+                default_branch: "main".to_string(),
+                branches: BTreeMap::from([("main".to_string(), sha)]),
+            });
+            Ok(())
+        })
+    }
+
+    /// Fork a repository of the sandbox, the same as [`Forge::create_fork`](Forge::create_fork)
+    /// but usable where no async runtime is running, e.g. while the app starts.
+    ///
+    /// # Arguments
+    /// - `repo`: The owner and name of the fork.
+    /// - `upstream`: The repository to fork.
+    ///
+    /// # Returns
+    /// - `Result<GitHubRepo>`
+    ///     - `Ok(GitHubRepo)`: The fork, which is the existing one if `repo` is already a fork of `upstream`.
+    ///     - `Err(Error)`: [`Error::Conflict`](Error::Conflict) if `repo` exists but is not a fork of `upstream`,
+    ///       or another error indicating why the method failed.
+    pub fn fork_repo(&self, repo: &GitHubRepo, upstream: &GitHubRepo) -> Result<GitHubRepo> {
+        self.update(|state| {
+            let upstream_repo = state.repo(upstream)?.clone();
+            if let Ok(existing) = state.repo(repo) {
+                // Forking again returns the existing fork, just like on GitHub.
+                return match &existing.parent {
+                    Some(parent) if same_repo(parent, upstream) => Ok(existing.repo.clone()),
+                    _ => Err(Error::Conflict(format!("repository {} already exists", repo.get_full_name()))),
+                };
+            }
+            state.repos.insert(repo_key(repo), SandboxRepo {
+                repo: repo.clone(),
+                parent: Some(upstream_repo.repo),
+                default_branch: upstream_repo.default_branch,
+                branches: upstream_repo.branches,
+            });
+            Ok(repo.clone())
+        })
+    }
+
+    /// Get a pull request of the sandbox.
+    pub fn get_pull_request(&self, upstream: &GitHubRepo, number: u64) -> Result<PullRequest> {
+        self.read(|state| state.pull_request(upstream, number))
+    }
+
+    /// Merge a pull request, as a maintainer would, so the next steps of the workflow can be rehearsed.
+    ///
+    /// The changes of the head branch since it was created from the base branch are committed on top of the base branch.
+    ///
+    /// # Returns
+    /// - `Result<String>`
+    ///     - `Ok(String)`: The SHA of the merge commit.
+    ///     - `Err(Error)`: [`Error::Conflict`](Error::Conflict) if the pull request is not open, or another error indicating why the method failed.
+    pub fn merge_pull_request(&self, upstream: &GitHubRepo, number: u64) -> Result<String> {
+        self.update(|state| {
+            let pr = state.pull_request(upstream, number)?;
+            if pr.state != PullRequestState::Open {
+                return Err(Error::Conflict(format!("pull request #{} is not open", number)));
+            }
+            let head_repo = pr.head_repo.clone().unwrap_or_else(|| upstream.clone());
+            let head = pr.head_sha.clone();
+            let base = state.head(upstream, &pr.base)?;
+
+            let merge_base = state.merge_base(&base, &head);
+            let empty = BTreeMap::new();
+            let merge_base_files = merge_base.as_deref().map_or(&empty, |sha| state.files(sha));
+            let head_files = state.files(&head);
+
+            let mut files = state.files(&base).clone();
+            for (path, content) in head_files {
+                if merge_base_files.get(path) != Some(content) {
+                    files.insert(path.clone(), content.clone());
+                }
+            }
+            for path in merge_base_files.keys() {
+                if !head_files.contains_key(path) {
+                    files.remove(path);
+                }
+            }
+
+            let message = format!("Merge pull request #{} from {}:{}\n\n{}", number, head_repo.owner, pr.head, pr.title);
+            let sha = state.commit_on(upstream, &pr.base, &message, files)?;
+            // The pull request keeps the head it was merged with, whatever is pushed to the branch afterwards.
+            state.pull_request_mut(upstream, number)?.pull_request = PullRequest {
+                state: PullRequestState::Merged,
+                ..pr
+            };
+            Ok(sha)
+        })
+    }
+}
+
+impl Forge for SandboxForge {
+    fn username(&self) -> &str {
+        &self.username
+    }
+
+    async fn get_user(&self) -> Result<String> {
+        Ok(self.username.clone())
+    }
+
+    async fn validate_repo(&self, repo: &GitHubRepo, upstream: &GitHubRepo) -> Result<()> {
+        self.read(|state| {
+            let sandbox_repo = state.repo(repo)?;
+            let full_name = sandbox_repo.repo.get_full_name();
+            match &sandbox_repo.parent {
+                None => Err(Error::NotAFork(full_name)),
+                Some(parent) if !same_repo(parent, upstream) => Err(Error::WrongParent {
+                    repo: full_name,
+                    expected: upstream.get_full_name(),
+                    actual: parent.get_full_name(),
+                }),
+                Some(_) => Ok(()),
+            }
+        })
+    }
+
+    async fn get_user_forks(&self, upstream: &GitHubRepo) -> Result<Vec<GitHubRepo>> {
+        self.read(|state| {
+            let mut forks: Vec<GitHubRepo> = state.repos.values()
+                .filter(|repo| repo.parent.as_ref().is_some_and(|parent| same_repo(parent, upstream)))
+                .filter(|repo| repo.repo.owner.eq_ignore_ascii_case(&self.username))
+                .map(|repo| repo.repo.clone())
+                .collect();
+            forks.sort_by_key(|fork| fork.name != upstream.name);
+            Ok(forks)
+        })
+    }
+
+    async fn create_fork(&self, repo: &GitHubRepo, upstream: &GitHubRepo) -> Result<GitHubRepo> {
+        self.fork_repo(repo, upstream)
+    }
+
+    async fn get_file_content(&self, repo: &GitHubRepo, path: &str) -> Result<String> {
+        self.read(|state| {
+            let content = state.file(repo, &state.repo(repo)?.default_branch, path)?;
+            String::from_utf8(content.clone()).map_err(|e| Error::parse(format!("the content of {}", path), e))
+        })
+    }
+
+    async fn get_branch_sha(&self, repo: &GitHubRepo, branch: &str) -> Result<String> {
+        self.read(|state| state.head(repo, branch))
+    }
+
+    async fn create_reference_from(&self, repo: &GitHubRepo, branch: &str, upstream: &GitHubRepo, base: &str) -> Result<()> {
+        self.update(|state| {
+            let sha = state.head(upstream, base)?;
+            let sandbox_repo = state.repo_mut(repo)?;
+            if sandbox_repo.branches.contains_key(branch) {
+                return Err(Error::Conflict(format!("branch {} already exists in {}", branch, repo.get_full_name())));
+            }
+            sandbox_repo.branches.insert(branch.to_string(), sha);
+            Ok(())
+        })
+    }
+
+    async fn get_file_sha(&self, repo: &GitHubRepo, branch: &str, path: &str) -> Result<String> {
+        self.read(|state| Ok(blob_sha(state.file(repo, branch, path)?)))
+    }
+
+    async fn create_file(&self, repo: &GitHubRepo, branch: &str, path: &str, content: &str, message: &str) -> Result<()> {
+        self.update(|state| {
+            if state.file(repo, branch, path).is_ok() {
+                return Err(Error::Conflict(format!("file {} already exists in {}", path, repo.get_full_name())));
+            }
+            let mut files = state.files(&state.head(repo, branch)?).clone();
+            files.insert(path.to_string(), content.as_bytes().to_vec());
+            state.commit_on(repo, branch, message, files)?;
+            Ok(())
+        })
+    }
+
+    async fn update_file(
+        &self,
+        repo: &GitHubRepo,
+        branch: &str,
+        path: &str,
+        content: &str,
+        sha: &str,
+        message: &str,
+    ) -> Result<()> {
+        self.update(|state| {
+            if blob_sha(state.file(repo, branch, path)?) != sha {
+                return Err(Error::Conflict(format!("file {} in {} does not match {}", path, repo.get_full_name(), sha)));
+            }
+            let mut files = state.files(&state.head(repo, branch)?).clone();
+            files.insert(path.to_string(), content.as_bytes().to_vec());
+            state.commit_on(repo, branch, message, files)?;
+            Ok(())
+        })
+    }
+
+    async fn commit_files(&self, repo: &GitHubRepo, branch: &str, changes: &[FileChange], commit_message: &str) -> Result<String> {
+        self.update(|state| {
+            let mut files = state.files(&state.head(repo, branch)?).clone();
+            let missing = |path: &str| Error::NotFound(format!("file {} in {}", path, repo.get_full_name()));
+            for change in changes {
+                match change {
+                    FileChange::Write { path, content } => {
+                        files.insert(path.clone(), content.clone());
+                    }
+                    FileChange::Delete { path } => {
+                        files.remove(path).ok_or_else(|| missing(path))?;
+                    }
+                    FileChange::Rename { from, to } => {
+                        let content = files.remove(from).ok_or_else(|| missing(from))?;
+                        files.insert(to.clone(), content);
+                    }
+                }
+            }
+            state.commit_on(repo, branch, commit_message, files)
+        })
+    }
+
+    async fn create_pull_request(
+        &self,
+        upstream: &GitHubRepo,
+        contributor: &GitHubRepo,
+        title: &str,
+        body: &str,
+        head: &str,
+        base: &str,
+        draft: bool,
+    ) -> Result<PullRequest> {
+        self.update(|state| {
+            state.head(upstream, base)?;
+            let head_sha = state.head(contributor, head)?;
+            let already_open = state.pull_requests.iter().any(|pr| {
+                same_repo(&pr.upstream, upstream)
+                    && pr.pull_request.state == PullRequestState::Open
+                    && pr.pull_request.head == head
+                    && pr.pull_request.head_repo.as_ref().is_some_and(|repo| same_repo(repo, contributor))
+            });
+            if already_open {
+                return Err(Error::Conflict(format!("A pull request already exists for {}:{}", contributor.owner, head)));
+            }
+
+            let number = state.pull_requests.iter().filter(|pr| same_repo(&pr.upstream, upstream)).count() as u64 + 1;
+            let pull_request = PullRequest {
+                number,
+                node_id: format!("SANDBOX_PR_{}_{}", repo_key(upstream), number),
+                url: format!("{}/{}/pull/{}", SANDBOX_URL, upstream.get_full_name(), number),
+                title: title.to_string(),
+                head: head.to_string(),
+                head_repo: Some(contributor.clone()),
+                head_sha,
+                base: base.to_string(),
+                state: PullRequestState::Open,
+                draft,
+            };
+            state.pull_requests.push(SandboxPullRequest {
+                upstream: upstream.clone(),
+                pull_request: pull_request.clone(),
+                body: body.to_string(),
+                labels: Vec::new(),
+                reviewers: Vec::new(),
+                assignees: Vec::new(),
+            });
+            Ok(pull_request)
+        })
+    }
+
+    async fn add_labels(&self, upstream: &GitHubRepo, number: u64, labels: &[String]) -> Result<()> {
+        self.update(|state| {
+            state.pull_request_mut(upstream, number)?.labels.extend_from_slice(labels);
+            Ok(())
+        })
+    }
+
+    async fn request_reviewers(&self, upstream: &GitHubRepo, number: u64, reviewers: &[String]) -> Result<()> {
+        self.update(|state| {
+            state.pull_request_mut(upstream, number)?.reviewers.extend_from_slice(reviewers);
+            Ok(())
+        })
+    }
+
+    async fn add_assignees(&self, upstream: &GitHubRepo, number: u64, assignees: &[String]) -> Result<()> {
+        self.update(|state| {
+            state.pull_request_mut(upstream, number)?.assignees.extend_from_slice(assignees);
+            Ok(())
+        })
+    }
+
+    async fn fork_status(&self, fork: &GitHubRepo, upstream: &GitHubRepo, branch: &str) -> Result<ForkStatus> {
+        self.read(|state| {
+            let fork_ancestors = state.ancestors(&state.head(fork, branch)?);
+            let upstream_ancestors = state.ancestors(&state.head(upstream, branch)?);
+            let ahead_by = fork_ancestors.iter().filter(|sha| !upstream_ancestors.contains(sha)).count();
+            let behind_by = upstream_ancestors.iter().filter(|sha| !fork_ancestors.contains(sha)).count();
+            Ok(ForkStatus::new(ahead_by as u64, behind_by as u64))
+        })
+    }
+
+    async fn sync_fork(&self, fork: &GitHubRepo, branch: &str) -> Result<()> {
+        self.update(|state| {
+            let parent = state.repo(fork)?.parent.clone().ok_or_else(|| Error::NotAFork(fork.get_full_name()))?;
+            let fork_head = state.head(fork, branch)?;
+            let upstream_head = state.head(&parent, branch)?;
+            if state.ancestors(&fork_head).contains(&upstream_head) {
+                // Already up to date.
+                return Ok(());
+            }
+            if !state.ancestors(&upstream_head).contains(&fork_head) {
+                return Err(Error::ForkDiverged(fork.get_full_name()));
+            }
+            state.repo_mut(fork)?.branches.insert(branch.to_string(), upstream_head);
+            Ok(())
+        })
+    }
+
+    async fn list_branches(&self, repo: &GitHubRepo) -> Result<Vec<Branch>> {
+        self.read(|state| {
+            Ok(state.repo(repo)?.branches.iter()
+                .map(|(name, sha)| Branch::new(name.clone(), sha.clone()))
+                .collect())
+        })
+    }
+
+    async fn delete_branch(&self, repo: &GitHubRepo, branch: &str) -> Result<()> {
+        self.update(|state| {
+            let sandbox_repo = state.repo_mut(repo)?;
+            if sandbox_repo.default_branch == branch {
+                return Err(Error::Conflict(format!("{} is the default branch of {}", branch, repo.get_full_name())));
+            }
+            sandbox_repo.branches.remove(branch)
+                .map(drop)
+                .ok_or_else(|| Error::NotFound(format!("branch {} in {}", branch, repo.get_full_name())))
+        })
+    }
+
+    async fn get_tree(&self, repo: &GitHubRepo, r#ref: &str, recursive: bool) -> Result<RepoTree> {
+        self.read(|state| {
+            let sha = state.resolve(repo, r#ref)?;
+            let mut entries = Vec::new();
+            let mut dirs = HashSet::new();
+            for (path, content) in state.files(&sha) {
+                // Every directory above a file is an entry of the tree as well.
+                let mut segments: Vec<&str> = path.split('/').collect();
+                segments.pop();
+                for depth in 1..=segments.len() {
+                    let dir = segments[..depth].join("/");
+                    if (recursive || depth == 1) && dirs.insert(dir.clone()) {
+                        entries.push(TreeEntry {
+                            sha: blob_sha(dir.as_bytes()),
+                            path: dir,
+                            mode: "040000".to_string(),
+                            kind: TreeEntryKind::Tree,
+                            size: None,
+                        });
+                    }
+                }
+                if recursive || segments.is_empty() {
+                    entries.push(TreeEntry {
+                        path: path.clone(),
+                        mode: "100644".to_string(),
+                        kind: TreeEntryKind::Blob,
+                        sha: blob_sha(content),
+                        size: Some(content.len() as u64),
+                    });
+                }
+            }
+            Ok(RepoTree::new(sha, entries))
+        })
+    }
+
+    async fn get_blob(&self, repo: &GitHubRepo, sha: &str) -> Result<Vec<u8>> {
+        self.read(|state| {
+            state.repo(repo)?;
+            state.commits.values()
+                .flat_map(|commit| commit.files.values())
+                .find(|content| blob_sha(content) == sha)
+                .cloned()
+                .ok_or_else(|| Error::NotFound(format!("blob {} in {}", sha, repo.get_full_name())))
+        })
+    }
+
+    async fn list_open_pull_requests(&self, upstream: &GitHubRepo) -> Result<Vec<PullRequest>> {
+        self.read(|state| {
+            Ok(state.pull_requests.iter().rev()
+                .filter(|pr| same_repo(&pr.upstream, upstream) && pr.pull_request.state == PullRequestState::Open)
+                .map(|pr| state.current(pr))
+                .collect())
+        })
+    }
+
+    async fn list_user_pull_requests(&self, upstream: &GitHubRepo) -> Result<Vec<PullRequest>> {
+        self.read(|state| {
+            Ok(state.pull_requests.iter().rev()
+                .filter(|pr| same_repo(&pr.upstream, upstream) && pr.pull_request.state == PullRequestState::Open)
+                .filter(|pr| pr.pull_request.head_repo.as_ref().is_some_and(|repo| repo.owner.eq_ignore_ascii_case(&self.username)))
+                .map(|pr| state.current(pr))
+                .collect())
+        })
+    }
+
+    async fn list_branch_pull_requests(&self, upstream: &GitHubRepo, contributor: &GitHubRepo, branch: &str) -> Result<Vec<PullRequest>> {
+        self.read(|state| {
+            Ok(state.pull_requests.iter().rev()
+                .filter(|pr| same_repo(&pr.upstream, upstream) && pr.pull_request.head == branch)
+                .filter(|pr| pr.pull_request.head_repo.as_ref().is_some_and(|repo| same_repo(repo, contributor)))
+                .map(|pr| state.current(pr))
+                .collect())
+        })
+    }
+
+    async fn list_pull_request_files(&self, upstream: &GitHubRepo, number: u64) -> Result<Vec<PullRequestFile>> {
+        self.read(|state| {
+            let pr = state.pull_request(upstream, number)?;
+            let base = state.head(upstream, &pr.base)?;
+            let empty = BTreeMap::new();
+            let merge_base_files = state.merge_base(&base, &pr.head_sha).map_or(&empty, |sha| state.files(&sha));
+            let head_files = state.files(&pr.head_sha);
+
+            let mut files: Vec<PullRequestFile> = head_files.iter()
+                .filter(|(path, content)| merge_base_files.get(*path) != Some(*content))
+                .map(|(path, content)| PullRequestFile { path: path.clone(), sha: Some(blob_sha(content)) })
+                .chain(merge_base_files.keys()
+                    .filter(|path| !head_files.contains_key(*path))
+                    .map(|path| PullRequestFile { path: path.clone(), sha: None }))
+                .collect();
+            files.sort_by(|a, b| a.path.cmp(&b.path));
+            Ok(files)
+        })
+    }
+
+    async fn pull_request_status(&self, upstream: &GitHubRepo, number: u64) -> Result<PullRequestStatus> {
+        // Nobody reviews the pull requests of the sandbox, and there are no checks to run.
+        Ok(PullRequestStatus {
+            pull_request: self.get_pull_request(upstream, number)?,
+            review_decision: ReviewDecision::Pending,
+            mergeable: Some(true),
+            mergeable_state: Some("clean".to_string()),
+            checks: Vec::new(),
+        })
+    }
+
+    async fn close_pull_request(&self, upstream: &GitHubRepo, number: u64) -> Result<PullRequest> {
+        self.update(|state| {
+            let pr = state.pull_request(upstream, number)?;
+            if pr.state == PullRequestState::Open {
+                state.pull_request_mut(upstream, number)?.pull_request = PullRequest {
+                    state: PullRequestState::Closed,
+                    ..pr
+                };
+            }
+            state.pull_request(upstream, number)
+        })
+    }
+
+    async fn mark_ready_for_review(&self, upstream: &GitHubRepo, pull_request: &PullRequest) -> Result<()> {
+        self.update(|state| {
+            state.pull_request_mut(upstream, pull_request.number)?.pull_request.draft = false;
+            Ok(())
+        })
+    }
+}
@@ -3,50 +3,53 @@ use std::fs::File;
 use std::io::{BufReader, stdin, stdout, Write};
 use std::path::Path;
 
+use crate::error::{Error, Result};
+
 /// A structure representing all information in the configuration file.
 ///
 /// # Fields
 /// - `editor`: The terminal command that the user uses to open their text editor.
+/// - `local_git`: Whether to commit articles in a local clone of the contributor repository with the `git` command,
+///   instead of through the API. Clones are kept in the `repos` directory of the runtime storage directory.
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct Config {
     pub editor: String,
+    #[serde(default)]
+    pub local_git: bool,
 }
 
 impl Config {
     pub fn new(editor: String) -> Self {
-        Self { editor }
+        Self { editor, local_git: false }
     }
 }
 
-/// Get the configuration from the configuration file. (`.fosscope_toolkit/config.json`)
-pub fn get_config() -> Config {
-    let config_file = File::open(".fosscope_toolkit/config.json").unwrap();
+/// Get the configuration from the configuration file. (`config.json` in the runtime storage directory `workspace`)
+pub fn get_config(workspace: &Path) -> Result<Config> {
+    let config_file = File::open(workspace.join("config.json"))
+        .map_err(|_| Error::Config("Failed to open the configuration file.".to_string()))?;
     let reader = BufReader::new(config_file);
-    let config: Result<Config, _> = serde_json::from_reader(reader);
-    match config {
-        Ok(config) => config,
-        Err(_) => {
-            eprintln!("Failed to read the configuration file.");
-            std::process::exit(1);
-        }
-    }
+    serde_json::from_reader(reader)
+        .map_err(|e| Error::parse("the configuration file", e))
 }
 
 /// Initial Configuration Process
 ///
 /// Create the configuration file if it does not exist, or read the configuration file if it exists.
 ///
+/// # Arguments
+/// - `workspace`: The runtime storage directory, e.g. [`WORKSPACE_DIR`](crate::WORKSPACE_DIR).
+///
 /// # Returns
-/// - `Result<Config, String>`:
+/// - `Result<Config>`:
 ///     - `Ok(Config)`: The newly created or existing configuration.
-///     - `Err(String)`: An error message indicating why the process failed.
-pub fn initial_configuration_process() -> Result<Config, String> {
-    let dir_path = Path::new(".fosscope_toolkit");
-    if !dir_path.exists() {
-        fs::create_dir_all(".fosscope_toolkit").unwrap();
+///     - `Err(Error)`: An error indicating why the process failed.
+pub fn initial_configuration_process(workspace: &Path) -> Result<Config> {
+    if !workspace.exists() {
+        fs::create_dir_all(workspace)?;
     }
 
-    let file_path = Path::new(".fosscope_toolkit/config.json");
+    let file_path = workspace.join("config.json");
 
     if !file_path.exists() {
         // If the configuration file does not exist, create it.
@@ -59,23 +62,29 @@ pub fn initial_configuration_process() -> Result<Config, String> {
         );
         let _ = stdout().flush();
         stdin().read_line(&mut user_input).unwrap_or(0);
-        let user_input = user_input.trim().to_string();
+        let editor = user_input.trim().to_string();
+
+        user_input.clear();
+        print!(
+            "Do you want to commit articles in a local clone of your fork with your own Git setup, \
+        instead of through the GitHub API? (y/N): "
+        );
+        let _ = stdout().flush();
+        stdin().read_line(&mut user_input).unwrap_or(0);
+        let local_git = matches!(user_input.trim().to_lowercase().as_str(), "y" | "yes");
 
         // Create the new configuration.
-        let config = Config::new(user_input);
-        let config_json = serde_json::to_string(&config);
-        match config_json {
-            Ok(config_json) => {
-                // Write the configuration to the configuration file.
-                let mut file = File::create(".fosscope_toolkit/config.json").unwrap();
-                file.write_all(config_json.as_bytes()).unwrap();
-                // Return the newly created configuration.
-                Ok(config)
-            }
-            Err(_) => Err("Failed to create the configuration file.".to_string()),
-        }
+        let mut config = Config::new(editor);
+        config.local_git = local_git;
+        let config_json = serde_json::to_string(&config)
+            .map_err(|_| Error::Config("Failed to create the configuration file.".to_string()))?;
+        // Write the configuration to the configuration file.
+        let mut file = File::create(file_path)?;
+        file.write_all(config_json.as_bytes())?;
+        // Return the newly created configuration.
+        Ok(config)
     } else {
         // If the configuration file exists, read it and return the configuration.
-        Ok(get_config())
+        get_config(workspace)
     }
 }
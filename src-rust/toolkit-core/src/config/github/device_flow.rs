@@ -0,0 +1,206 @@
+use std::time::{Duration, Instant};
+
+use crate::error::{Error, Result};
+
+/// The environment variable holding the client ID of the GitHub OAuth app used for the device flow.
+pub const CLIENT_ID_ENV: &str = "FOSSCOPE_TOOLKIT_GITHUB_CLIENT_ID";
+/// The environment variable overriding the endpoint that issues device codes, e.g. to use a local stand-in.
+pub const DEVICE_CODE_URL_ENV: &str = "FOSSCOPE_TOOLKIT_GITHUB_DEVICE_CODE_URL";
+/// The environment variable overriding the endpoint that exchanges device codes for access tokens.
+pub const ACCESS_TOKEN_URL_ENV: &str = "FOSSCOPE_TOOLKIT_GITHUB_ACCESS_TOKEN_URL";
+
+const GITHUB_HOST: &str = "https://github.com";
+const DEVICE_CODE_ROUTE: &str = "/login/device/code";
+const ACCESS_TOKEN_ROUTE: &str = "/login/oauth/access_token";
+/// The scopes needed to fork repositories, push to the forks and open pull requests.
+const DEFAULT_SCOPES: &[&str] = &["public_repo", "workflow"];
+
+/// A code that the user enters on GitHub to authorize the toolkit.
+///
+/// # Fields
+/// - `device_code`: The code that the toolkit exchanges for an access token once the user has authorized it.
+/// - `user_code`: The code that the user enters at `verification_uri`.
+/// - `verification_uri`: The page where the user enters `user_code`.
+/// - `expires_in`: The number of seconds before the codes expire.
+/// - `interval`: The minimum number of seconds between two attempts to get the access token.
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct DeviceCode {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub expires_in: u64,
+    pub interval: u64,
+}
+
+/// The response of the access token endpoint, which is either the token or the reason there is no token yet.
+#[derive(serde::Deserialize)]
+struct AccessTokenResponse {
+    access_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+    interval: Option<u64>,
+}
+
+/// The GitHub OAuth device flow, which lets the user sign in from a browser instead of creating a personal access token.
+///
+/// # Fields
+/// - `client_id`: The client ID of the GitHub OAuth app.
+/// - `scopes`: The OAuth scopes requested for the token.
+/// - `device_code_url`: The endpoint that issues device codes.
+/// - `access_token_url`: The endpoint that exchanges device codes for access tokens.
+pub struct DeviceFlow {
+    client_id: String,
+    scopes: Vec<String>,
+    device_code_url: String,
+    access_token_url: String,
+    client: reqwest::Client,
+}
+
+impl DeviceFlow {
+    pub fn new(client_id: String) -> Self {
+        Self::for_host(client_id, None)
+    }
+
+    /// Create the device flow of the GitHub instance an account signs in to,
+    /// so the token is issued by the same instance it is sent to.
+    ///
+    /// # Arguments
+    /// - `client_id`: The client ID of the OAuth app, registered on the instance.
+    /// - `base_url`: The base URL of the REST API of the account, e.g. `https://github.example.com/api/v3`
+    ///   for GitHub Enterprise Server, whose endpoints are under `https://github.example.com/login`.
+    ///   Other base URLs (e.g. a local mock server) serve the endpoints under the base URL. `None` for GitHub.com.
+    pub fn for_host(client_id: String, base_url: Option<&str>) -> Self {
+        let host = match base_url.map(|base_url| base_url.trim_end_matches('/')) {
+            None | Some("https://api.github.com") => GITHUB_HOST,
+            Some(base_url) => base_url.strip_suffix("/api/v3").unwrap_or(base_url),
+        };
+        Self {
+            client_id,
+            scopes: DEFAULT_SCOPES.iter().map(|scope| scope.to_string()).collect(),
+            device_code_url: format!("{}{}", host, DEVICE_CODE_ROUTE),
+            access_token_url: format!("{}{}", host, ACCESS_TOKEN_ROUTE),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Create the device flow of the GitHub instance an account signs in to, configured by the environment variables.
+    ///
+    /// The client ID is read from [`CLIENT_ID_ENV`](CLIENT_ID_ENV). The endpoints are derived from `base_url`
+    /// as by [`for_host`](DeviceFlow::for_host), and can be overridden with [`DEVICE_CODE_URL_ENV`](DEVICE_CODE_URL_ENV)
+    /// and [`ACCESS_TOKEN_URL_ENV`](ACCESS_TOKEN_URL_ENV).
+    ///
+    /// # Arguments
+    /// - `base_url`: The base URL of the REST API of the account, `None` for GitHub.com.
+    ///
+    /// # Returns
+    /// - `Result<DeviceFlow>`
+    ///     - `Ok(DeviceFlow)`: The device flow.
+    ///     - `Err(Error)`: [`Error::Config`](Error::Config) if no client ID is configured.
+    pub fn from_env(base_url: Option<&str>) -> Result<Self> {
+        let client_id = std::env::var(CLIENT_ID_ENV).map_err(|_| Error::Config(format!(
+            "The client ID of the GitHub OAuth app is not configured. Set the {} environment variable.", CLIENT_ID_ENV
+        )))?;
+
+        let mut flow = Self::for_host(client_id, base_url);
+        if let Ok(url) = std::env::var(DEVICE_CODE_URL_ENV) {
+            flow.device_code_url = url;
+        }
+        if let Ok(url) = std::env::var(ACCESS_TOKEN_URL_ENV) {
+            flow.access_token_url = url;
+        }
+        Ok(flow)
+    }
+
+    /// Set the OAuth scopes requested for the token.
+    pub fn with_scopes(mut self, scopes: Vec<String>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    /// Set the endpoints of the device flow, e.g. to use a local stand-in of GitHub.
+    pub fn with_endpoints(mut self, device_code_url: String, access_token_url: String) -> Self {
+        self.device_code_url = device_code_url;
+        self.access_token_url = access_token_url;
+        self
+    }
+
+    /// Request a device code, which the user has to enter on GitHub to authorize the toolkit.
+    ///
+    /// # Returns
+    /// - `Result<DeviceCode>`
+    ///     - `Ok(DeviceCode)`: The [DeviceCode](DeviceCode) to show to the user.
+    ///     - `Err(Error)`: An error indicating why the method failed.
+    pub async fn request_code(&self) -> Result<DeviceCode> {
+        let response = self.client.post(&self.device_code_url)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .form(&[("client_id", self.client_id.as_str()), ("scope", &self.scopes.join(" "))])
+            .send()
+            .await?
+            .error_for_status()?;
+
+        response.json().await.map_err(|e| Error::parse("the device code", e))
+    }
+
+    /// Wait for the user to authorize the toolkit, and get the access token.
+    ///
+    /// # Arguments
+    /// - `code`: The [DeviceCode](DeviceCode) shown to the user.
+    ///
+    /// # Returns
+    /// - `Result<String>`
+    ///     - `Ok(String)`: The access token.
+    ///     - `Err(Error)`: [`Error::Unauthorized`](Error::Unauthorized) if the user denied the authorization or the code expired,
+    ///       or another error indicating why the method failed.
+    pub async fn poll_token(&self, code: &DeviceCode) -> Result<String> {
+        let deadline = Instant::now() + Duration::from_secs(code.expires_in);
+        let mut interval = Duration::from_secs(code.interval);
+
+        loop {
+            if Instant::now() >= deadline {
+                return Err(Error::Unauthorized("The device code has expired. Please sign in again.".to_string()));
+            }
+
+            let response: AccessTokenResponse = self.client.post(&self.access_token_url)
+                .header(reqwest::header::ACCEPT, "application/json")
+                .form(&[
+                    ("client_id", self.client_id.as_str()),
+                    ("device_code", code.device_code.as_str()),
+                    ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                ])
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await
+                .map_err(|e| Error::parse("the access token", e))?;
+
+            if let Some(token) = response.access_token {
+                return Ok(token);
+            }
+
+            match response.error.as_deref() {
+                // The user has not entered the code yet.
+                Some("authorization_pending") => {}
+                // Polling too often, GitHub asks to wait longer between attempts.
+                Some("slow_down") => {
+                    interval = response.interval.map(Duration::from_secs).unwrap_or(interval + Duration::from_secs(5));
+                }
+                Some("expired_token") => {
+                    return Err(Error::Unauthorized("The device code has expired. Please sign in again.".to_string()));
+                }
+                Some("access_denied") => {
+                    return Err(Error::Unauthorized("The authorization was denied.".to_string()));
+                }
+                error => {
+                    return Err(Error::Unauthorized(
+                        response.error_description
+                            .or_else(|| error.map(str::to_string))
+                            .unwrap_or_else(|| "No access token was returned.".to_string())
+                    ));
+                }
+            }
+
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
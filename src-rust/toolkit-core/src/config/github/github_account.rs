@@ -1,55 +1,211 @@
+use octocrab::service::middleware::retry::RetryConfig;
 use octocrab::Octocrab;
 
-use crate::apis::GitHubApi;
+use crate::apis::{Forge, GiteaApi, GitHubApi};
+use crate::error::{Error, Result};
 
-/// A representation of the GitHub account that is used to interact with the GitHub API.
+/// The kind of forge an account belongs to.
+///
+/// # Variants
+/// - `GitHub`: GitHub.com or a GitHub Enterprise Server.
+/// - `Gitea`: A Gitea or Forgejo instance.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForgeKind {
+    #[default]
+    GitHub,
+    Gitea,
+}
+
+/// A representation of the account that is used to interact with the API of a forge, GitHub unless `forge` says otherwise.
 ///
 /// # Fields
-/// - `username`: The username of the GitHub account.
-/// - `token`: The personal access token of the GitHub account.
+/// - `forge`: The [kind of forge](ForgeKind) the account belongs to. Accounts saved without it belong to GitHub.
+/// - `username`: The username of the account.
+/// - `token`: The personal access token of the account.
+/// - `base_url`: The base URL of the REST API, e.g. `https://github.example.com/api/v3` for GitHub Enterprise Server.
+///   `None` for GitHub.com. For a Gitea or Forgejo instance, the URL of the instance, e.g. `https://forgejo.example.com`.
+/// - `graphql_url`: The URL of the GraphQL API. Derived from `base_url` if `None`.
+/// - `upload_url`: The base URL of the uploads API. Derived from `base_url` if `None`.
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct GitHubAccount {
+    #[serde(default)]
+    pub forge: ForgeKind,
     pub username: String,
     pub token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graphql_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upload_url: Option<String>,
 }
 
 impl GitHubAccount {
     pub fn new(username: String, token: String) -> Self {
         Self {
+            forge: ForgeKind::GitHub,
             username,
             token,
+            base_url: None,
+            graphql_url: None,
+            upload_url: None,
+        }
+    }
+
+    /// Use a Gitea or Forgejo instance at `base_url`, e.g. `https://forgejo.example.com`, instead of GitHub.
+    pub fn with_gitea(mut self, base_url: String) -> Self {
+        self.forge = ForgeKind::Gitea;
+        self.base_url = Some(base_url);
+        self
+    }
+
+    /// Use the GitHub API at another base URL than `https://api.github.com`,
+    /// e.g. a GitHub Enterprise Server or a local mock server.
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = Some(base_url);
+        self
+    }
+
+    /// Use the GitHub GraphQL API at another URL than the one derived from the base URL.
+    pub fn with_graphql_url(mut self, graphql_url: String) -> Self {
+        self.graphql_url = Some(graphql_url);
+        self
+    }
+
+    /// Use the GitHub uploads API at another URL than the one derived from the base URL.
+    pub fn with_upload_url(mut self, upload_url: String) -> Self {
+        self.upload_url = Some(upload_url);
+        self
+    }
+
+    /// Get the URL of the GraphQL API, if the account does not use GitHub.com.
+    ///
+    /// GitHub Enterprise Server serves the REST API at `/api/v3` and the GraphQL API at `/api/graphql`,
+    /// other servers are expected to serve the GraphQL API at `/graphql` under the base URL.
+    pub fn get_graphql_url(&self) -> Option<String> {
+        if let Some(graphql_url) = &self.graphql_url {
+            return Some(graphql_url.clone());
+        }
+        let base_url = self.base_url.as_ref()?.trim_end_matches('/');
+        Some(match base_url.strip_suffix("/api/v3") {
+            Some(host) => format!("{}/api/graphql", host),
+            None => format!("{}/graphql", base_url),
+        })
+    }
+
+    /// Get the base URL of the uploads API, if the account does not use GitHub.com.
+    ///
+    /// GitHub Enterprise Server serves the uploads API at `/api/uploads`,
+    /// other servers are expected to serve it at `/uploads` under the base URL.
+    pub fn get_upload_url(&self) -> Option<String> {
+        if let Some(upload_url) = &self.upload_url {
+            return Some(upload_url.clone());
+        }
+        let base_url = self.base_url.as_ref()?.trim_end_matches('/');
+        Some(match base_url.strip_suffix("/api/v3") {
+            Some(host) => format!("{}/api/uploads", host),
+            None => format!("{}/uploads", base_url),
+        })
+    }
+
+    /// Create an Octocrab client authenticated with the token, against the given base URL or GitHub.com.
+    fn client(&self, base_url: Option<&str>) -> Result<Octocrab> {
+        // GitHubApi retries transient errors with a backoff, so Octocrab should not retry them on its own.
+        let mut builder = Octocrab::builder();
+        builder.add_retry_config(RetryConfig::None);
+        if let Some(base_url) = base_url {
+            builder = builder.base_uri(base_url)?;
+        }
+        Ok(builder.personal_token(self.token.clone()).build()?)
+    }
+
+    /// Create a [`GitHubApi`](GitHubApi) wrapper for the account, without verifying anything.
+    fn api(&self, username: String) -> Result<GitHubApi> {
+        let mut github = GitHubApi::new(username, self.client(self.base_url.as_deref())?);
+        if let Some(upload_url) = self.get_upload_url() {
+            github = github.with_upload_url(upload_url);
+        }
+        let graphql_url = match self.get_graphql_url() {
+            Some(graphql_url) => graphql_url,
+            None => return Ok(github),
+        };
+
+        // Every route is appended to the path of the base URL of an Octocrab client,
+        // so the GraphQL API is reached with a client at the origin of its URL.
+        let url = url::Url::parse(&graphql_url)
+            .map_err(|e| Error::Config(format!("Invalid GraphQL API URL {}: {}", graphql_url, e)))?;
+        let origin = url.origin().ascii_serialization();
+        Ok(github.with_graphql_endpoint(self.client(Some(&origin))?, url.path().to_string()))
+    }
+
+    /// Verify that the token has every capability required by the toolkit,
+    /// so a missing permission is reported at login instead of in the middle of a workflow.
+    ///
+    /// The capabilities that are not required, or that cannot be known for the token, do not fail the login.
+    /// They are available from [`GitHubApi::token_capabilities`](GitHubApi::token_capabilities) to be reported as warnings.
+    async fn verify_capabilities(github: &GitHubApi) -> Result<()> {
+        let missing = github.check_capabilities().await?.missing_required();
+        if !missing.is_empty() {
+            return Err(Error::MissingPermissions(missing));
         }
+        Ok(())
     }
 
     /// Login to the GitHub API using the personal access token.
     ///
     /// # Returns
-    /// - `Result<GitHubApi, &str>`:
+    /// - `Result<GitHubApi>`:
     ///     - `Ok(GitHubApi)`: The [`GitHubApi`](GitHubApi) wrapper if the login is successful.
-    ///     - `Err(&str)`: An error message indicating the reason for the login failure.
-    pub async fn login(&self) -> Result<GitHubApi, &str> {
-        // Create an Octocrab client with the personal access token.
-        let octocrab = Octocrab::builder().personal_token(
-            self.token.clone()
-        ).build();
-
-        match octocrab {
-            Ok(octocrab) => {
-                // Create a GitHubApi wrapper with the username and the Octocrab client.
-                let github = GitHubApi::new(
-                    self.username.clone(),
-                    octocrab,
-                );
-                // Verify the GitHub user.
-                if !github.verify_user().await {
-                    Err("Failed to verify the GitHub user. Check your token.")
-                } else {
-                    Ok(github)
-                }
-            }
-
-            // If the Octocrab client cannot be created, return an error message.
-            Err(_) => Err("Failed to login to GitHub. Check your token.")
+    ///     - `Err(Error)`: [`Error::Unauthorized`](Error::Unauthorized) if the token is invalid or belongs to another user,
+    ///       [`Error::MissingPermissions`](Error::MissingPermissions) if the token lacks capabilities required by the toolkit, or another error indicating the reason for the login failure.
+    pub async fn login(&self) -> Result<GitHubApi> {
+        let github = self.api(self.username.clone())?;
+        // Verify the GitHub user.
+        let user = github.get_user().await?;
+        if user != self.username {
+            return Err(Error::Unauthorized(format!(
+                "The token belongs to {} instead of {}. Check your token.", user, self.username
+            )));
+        }
+        Self::verify_capabilities(&github).await?;
+        Ok(github)
+    }
+
+    /// Login to the GitHub API with the token alone, e.g. one obtained from the [DeviceFlow](super::DeviceFlow),
+    /// filling in the username from the user the token belongs to.
+    ///
+    /// # Returns
+    /// - `Result<(GitHubAccount, GitHubApi)>`:
+    ///     - `Ok((GitHubAccount, GitHubApi))`: The account with its username, to be saved for later logins, and the [`GitHubApi`](GitHubApi) wrapper.
+    ///     - `Err(Error)`: [`Error::Unauthorized`](Error::Unauthorized) if the token is invalid,
+    ///       [`Error::MissingPermissions`](Error::MissingPermissions) if the token lacks capabilities required by the toolkit, or another error indicating the reason for the login failure.
+    pub async fn login_with_token(mut self) -> Result<(Self, GitHubApi)> {
+        self.username = self.api(String::new())?.get_user().await?;
+
+        let github = self.api(self.username.clone())?;
+        Self::verify_capabilities(&github).await?;
+        Ok((self, github))
+    }
+
+    /// Login to the Gitea or Forgejo instance at the base URL using the personal access token.
+    ///
+    /// # Returns
+    /// - `Result<GiteaApi>`:
+    ///     - `Ok(GiteaApi)`: The [`GiteaApi`](GiteaApi) wrapper if the login is successful.
+    ///     - `Err(Error)`: [`Error::Config`](Error::Config) if the account has no base URL,
+    ///       [`Error::Unauthorized`](Error::Unauthorized) if the token is invalid or belongs to another user,
+    ///       or another error indicating the reason for the login failure.
+    pub async fn login_gitea(&self) -> Result<GiteaApi> {
+        let base_url = self.base_url.clone()
+            .ok_or_else(|| Error::Config("The URL of the Gitea or Forgejo instance is missing.".to_string()))?;
+        let gitea = GiteaApi::new(self.username.clone(), base_url, self.token.clone());
+        let user = gitea.get_user().await?;
+        if !user.eq_ignore_ascii_case(&self.username) {
+            return Err(Error::Unauthorized(format!(
+                "The token belongs to {} instead of {}. Check your token.", user, self.username
+            )));
         }
+        Ok(gitea)
     }
 }
@@ -1,3 +1,5 @@
-pub use github_account::GitHubAccount;
+pub use device_flow::{DeviceCode, DeviceFlow};
+pub use github_account::{ForgeKind, GitHubAccount};
 
+pub mod device_flow;
 mod github_account;
@@ -0,0 +1,206 @@
+use reqwest::StatusCode;
+
+use crate::models::TokenCapability;
+
+/// A type alias for `Result<T, Error>`, used across the core crate.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The error type of the core crate.
+///
+/// Every fallible function in the core crate returns this error, so that the CLI and the Tauri frontend
+/// can react to specific failures (e.g. a repository that does not exist) without matching on error messages.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The requested resource (repository, file, reference, etc.) does not exist.
+    #[error("Not found: {0}")]
+    NotFound(String),
+    /// The repository exists but is not a fork.
+    #[error("Repository {0} is not a fork")]
+    NotAFork(String),
+    /// The repository is a fork, but not a fork of the expected upstream repository.
+    #[error("Repository {repo} is a fork of {actual} instead of {expected}")]
+    WrongParent {
+        /// The full name of the repository.
+        repo: String,
+        /// The full name of the upstream repository that the repository should be a fork of.
+        expected: String,
+        /// The full name of the actual parent of the repository.
+        actual: String,
+    },
+    /// The resource has been changed since it was read, e.g. a file was updated with an outdated SHA.
+    #[error("Conflict: {0}")]
+    Conflict(String),
+    /// The user is not authenticated, or is not allowed to perform the operation.
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    /// The token of the user lacks capabilities that the toolkit needs.
+    #[error("The token is missing the permissions to {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    MissingPermissions(Vec<TokenCapability>),
+    /// The fork has diverged from the upstream repository and cannot be synchronized automatically.
+    #[error("Fork {0} has diverged from the upstream repository and cannot be synchronized automatically")]
+    ForkDiverged(String),
+    /// The fork has been requested, but its Git data is still not available after waiting for it.
+    ///
+    /// Forks are created asynchronously, so the fork usually becomes available a while later.
+    #[error("Fork {repo} is still being created after waiting for {seconds} second(s), please try again later")]
+    ForkTimeout {
+        /// The full name of the fork.
+        repo: String,
+        /// How long the fork has been waited for, in seconds.
+        seconds: u64,
+    },
+    /// The GitHub API rate limit has been exceeded.
+    #[error("GitHub API rate limit exceeded: {0}")]
+    RateLimited(String),
+    /// The GitHub API quota of the user is exhausted, so no request can be made until it resets.
+    #[error("GitHub API quota of {limit} requests per hour is exhausted, it resets in {} minute(s)", minutes_until(*.reset))]
+    QuotaExhausted {
+        /// The maximum number of requests the user can make per hour.
+        limit: u64,
+        /// The time at which the quota resets, in seconds since the Unix epoch.
+        reset: u64,
+    },
+    /// A network error occurred while sending a request or receiving a response.
+    #[error("Network error: {0}")]
+    Network(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// Any other error returned by the GitHub API.
+    #[error("GitHub API error: {0}")]
+    GitHub(#[source] Box<octocrab::Error>),
+    /// Any other error returned by the API of a forge other than GitHub, e.g. Gitea or Forgejo.
+    #[error("Forge API error: {0}")]
+    Forge(String),
+    /// The GitHub GraphQL API returned errors for a query or mutation.
+    #[error("GitHub GraphQL API error: {0}")]
+    GraphQL(String),
+    /// A response or a file could not be parsed.
+    #[error("Failed to parse {what}: {source}")]
+    Parse {
+        /// A description of what was being parsed.
+        what: String,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// A Handlebars template could not be rendered.
+    #[error("Failed to render the template {template}: {source}")]
+    TemplateRender {
+        /// What the template is, e.g. `git.commit_message`.
+        template: String,
+        #[source]
+        source: Box<handlebars::RenderError>,
+    },
+    /// The repository rule could not be deserialized.
+    #[error("Failed to deserialize the repository rule: {0}")]
+    RepoRule(#[source] Box<toml::de::Error>),
+    /// The configuration of the toolkit is missing or invalid.
+    #[error("Invalid configuration: {0}")]
+    Config(String),
+    /// A `git` command failed in a local clone.
+    #[error("Git error: {0}")]
+    Git(String),
+    /// An I/O error occurred while reading or writing local files.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The input given to the toolkit is invalid.
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+}
+
+impl Error {
+    /// Create a [`Parse`](Error::Parse) error with a description of what was being parsed.
+    pub fn parse(what: impl Into<String>, source: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self::Parse {
+            what: what.into(),
+            source: source.into(),
+        }
+    }
+
+    /// Get the name of the variant of the error, e.g. `NotFound`.
+    ///
+    /// This is used as a stable identifier of the error for the frontend.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::NotFound(_) => "NotFound",
+            Error::NotAFork(_) => "NotAFork",
+            Error::WrongParent { .. } => "WrongParent",
+            Error::ForkDiverged(_) => "ForkDiverged",
+            Error::ForkTimeout { .. } => "ForkTimeout",
+            Error::Conflict(_) => "Conflict",
+            Error::Unauthorized(_) => "Unauthorized",
+            Error::MissingPermissions(_) => "MissingPermissions",
+            Error::RateLimited(_) => "RateLimited",
+            Error::QuotaExhausted { .. } => "QuotaExhausted",
+            Error::Network(_) => "Network",
+            Error::GitHub(_) => "GitHub",
+            Error::Forge(_) => "Forge",
+            Error::GraphQL(_) => "GraphQL",
+            Error::Parse { .. } => "Parse",
+            Error::TemplateRender { .. } => "TemplateRender",
+            Error::RepoRule(_) => "RepoRule",
+            Error::Config(_) => "Config",
+            Error::Git(_) => "Git",
+            Error::Io(_) => "Io",
+            Error::InvalidInput(_) => "InvalidInput",
+        }
+    }
+}
+
+/// Get the number of minutes (rounded up) until a time given in seconds since the Unix epoch.
+fn minutes_until(time: u64) -> u64 {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    time.saturating_sub(now).div_ceil(60)
+}
+
+impl From<octocrab::Error> for Error {
+    /// Map an Octocrab error to the matching variant based on the HTTP status code returned by GitHub.
+    fn from(err: octocrab::Error) -> Self {
+        match err {
+            octocrab::Error::GitHub { ref source, .. } => {
+                let message = source.message.clone();
+                match source.status_code {
+                    StatusCode::UNAUTHORIZED => Error::Unauthorized(message),
+                    StatusCode::TOO_MANY_REQUESTS => Error::RateLimited(message),
+                    StatusCode::FORBIDDEN if message.to_lowercase().contains("rate limit") => {
+                        Error::RateLimited(message)
+                    }
+                    StatusCode::FORBIDDEN => Error::Unauthorized(message),
+                    StatusCode::NOT_FOUND => Error::NotFound(message),
+                    StatusCode::CONFLICT => Error::Conflict(message),
+                    _ => Error::GitHub(Box::new(err)),
+                }
+            }
+            octocrab::Error::Hyper { .. } | octocrab::Error::Service { .. } => Error::Network(Box::new(err)),
+            octocrab::Error::Serde { .. } | octocrab::Error::Json { .. } => {
+                Error::parse("the GitHub API response", err)
+            }
+            _ => Error::GitHub(Box::new(err)),
+        }
+    }
+}
+
+impl From<toml::de::Error> for Error {
+    fn from(err: toml::de::Error) -> Self {
+        Error::RepoRule(Box::new(err))
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Error::Network(Box::new(err))
+    }
+}
+
+impl serde::Serialize for Error {
+    /// Serialize the error as `{ "kind": ..., "message": ... }`,
+    /// so it can be returned from Tauri commands and matched on by the frontend.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Error", 2)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
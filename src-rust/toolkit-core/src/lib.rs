@@ -1,63 +1,67 @@
 use std::io::Write;
+use std::path::Path;
 
+pub use crate::error::{Error, Result};
 use crate::models::GitHubRepo;
 
 pub mod apis;
 pub mod models;
 pub mod workflow;
 pub mod config;
+pub mod template;
+mod error;
 mod utils;
 
+/// The directory the toolkit keeps its runtime storage in across runs, e.g. the configuration and the contributor repositories.
+/// The sandbox mode uses [`SANDBOX_DIR`](SANDBOX_DIR) instead.
+pub const WORKSPACE_DIR: &str = ".fosscope_toolkit";
+
+/// The runtime storage directory of the sandbox mode, used instead of [`WORKSPACE_DIR`](WORKSPACE_DIR)
+/// so it never touches the configuration and the repositories of the real workspace.
+pub const SANDBOX_DIR: &str = ".fosscope_toolkit/sandbox";
+
 /// Set the contributor repository of an upstream repository.
 /// The information of the contributor repository will be stored in a JSON file.
 /// The file will be kept in the directory across different runs.
 ///
 /// # Arguments
+/// - `workspace`: The runtime storage directory, e.g. [`WORKSPACE_DIR`](WORKSPACE_DIR).
 /// - `repo`: The contributor repository.
 /// - `upstream`: The pointer to the upstream repository.
-pub fn set_contributor_repo(repo: GitHubRepo, upstream: &GitHubRepo) {
-    println!("Setting the contributor repository to: {}", repo.get_full_name());
-
-    let json_str = serde_json::to_string(&repo);
-    match json_str {
-        Ok(json_str) => {
-            // Write to the runtime storage file.
-            // If the directory does not exist, create it.
-            std::fs::create_dir_all(".fosscope_toolkit").unwrap();
-            let path = format!(
-                ".fosscope_toolkit/contributor_repo_{}_{}.json",
-                upstream.owner, upstream.name
-            );
-            let mut file = std::fs::File::create(path).unwrap();
-            file.write_all(json_str.as_bytes()).unwrap();
-        }
-        Err(_) => {
-            eprintln!("Failed to set the contributor repository.");
-            std::process::exit(1);
-        }
-    }
+pub fn set_contributor_repo(workspace: &Path, repo: GitHubRepo, upstream: &GitHubRepo) -> Result<()> {
+    let json_str = serde_json::to_string(&repo)
+        .map_err(|e| Error::parse("the contributor repository", e))?;
+
+    // Write to the runtime storage file.
+    // If the directory does not exist, create it.
+    std::fs::create_dir_all(workspace)?;
+    let path = contributor_repo_path(workspace, upstream);
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(json_str.as_bytes())?;
+    Ok(())
 }
 
 /// Get the contributor repository of an upstream repository from the JSON file.
 ///
 /// # Arguments
+/// - `workspace`: The runtime storage directory, e.g. [`WORKSPACE_DIR`](WORKSPACE_DIR).
 /// - `upstream`: The pointer to the upstream repository.
-pub fn get_contributor_repo(upstream: &GitHubRepo) -> Option<GitHubRepo> {
-    let path_str = format!(
-        ".fosscope_toolkit/contributor_repo_{}_{}.json",
-        upstream.owner, upstream.name
-    );
-    let path = std::path::Path::new(&path_str);
+///
+/// # Returns
+/// - `Result<Option<GitHubRepo>>`: The contributor repository, or `None` if it has not been set yet.
+pub fn get_contributor_repo(workspace: &Path, upstream: &GitHubRepo) -> Result<Option<GitHubRepo>> {
+    let path = contributor_repo_path(workspace, upstream);
     if !path.exists() {
-        return None;
-    }
-    let file = std::fs::File::open(path);
-    match file {
-        Ok(file) => {
-            let reader = std::io::BufReader::new(file);
-            let contributor_repo: GitHubRepo = serde_json::from_reader(reader).unwrap();
-            Some(contributor_repo)
-        }
-        Err(_) => None
+        return Ok(None);
     }
+    let file = std::fs::File::open(&path)?;
+    let reader = std::io::BufReader::new(file);
+    let contributor_repo: GitHubRepo = serde_json::from_reader(reader)
+        .map_err(|e| Error::parse(path.display().to_string(), e))?;
+    Ok(Some(contributor_repo))
+}
+
+/// The file the contributor repository of an upstream repository is stored in.
+fn contributor_repo_path(workspace: &Path, upstream: &GitHubRepo) -> std::path::PathBuf {
+    workspace.join(format!("contributor_repo_{}_{}.json", upstream.owner, upstream.name))
 }
@@ -0,0 +1,36 @@
+use crate::models::gitea_api_responses;
+
+/// A representation of a branch of a repository.
+///
+/// # Fields
+/// - `name`: The name of the branch. e.g. `select/news/20240715-hello`.
+/// - `sha`: The SHA of the latest commit on the branch.
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Branch {
+    pub name: String,
+    pub sha: String,
+}
+
+impl Branch {
+    pub fn new(name: String, sha: String) -> Self {
+        Self { name, sha }
+    }
+}
+
+impl From<octocrab::models::repos::Branch> for Branch {
+    fn from(branch: octocrab::models::repos::Branch) -> Self {
+        Self {
+            name: branch.name,
+            sha: branch.commit.sha,
+        }
+    }
+}
+
+impl From<gitea_api_responses::branch::Branch> for Branch {
+    fn from(branch: gitea_api_responses::branch::Branch) -> Self {
+        Self {
+            name: branch.name,
+            sha: branch.commit.id,
+        }
+    }
+}
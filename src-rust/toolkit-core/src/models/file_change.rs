@@ -0,0 +1,48 @@
+/// A representation of a change to a file in a repository, to be committed together with other changes.
+///
+/// # Variants
+/// - `Write`: Add a new file, or modify an existing file, with the given content.
+/// - `Delete`: Delete an existing file.
+/// - `Rename`: Move an existing file to a new path, keeping its content. e.g. moving an article from `sources/news` to `translated/news`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum FileChange {
+    /// Add a new file, or modify an existing file, with the given content.
+    Write {
+        /// The path of the file in the repository.
+        path: String,
+        /// The raw content of the file. Binary content (e.g. images) is supported.
+        content: Vec<u8>,
+    },
+    /// Delete an existing file.
+    Delete {
+        /// The path of the file in the repository.
+        path: String,
+    },
+    /// Move an existing file to a new path, keeping its content.
+    Rename {
+        /// The current path of the file in the repository.
+        from: String,
+        /// The new path of the file in the repository.
+        to: String,
+    },
+}
+
+impl FileChange {
+    pub fn write(path: impl Into<String>, content: impl Into<Vec<u8>>) -> Self {
+        Self::Write {
+            path: path.into(),
+            content: content.into(),
+        }
+    }
+
+    pub fn delete(path: impl Into<String>) -> Self {
+        Self::Delete { path: path.into() }
+    }
+
+    pub fn rename(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self::Rename {
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+}
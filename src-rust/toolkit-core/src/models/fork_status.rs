@@ -0,0 +1,30 @@
+/// A representation of how a fork compares to a branch of its upstream repository.
+///
+/// # Fields
+/// - `ahead_by`: The number of commits in the fork that are not in the upstream repository.
+/// - `behind_by`: The number of commits in the upstream repository that are not in the fork.
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ForkStatus {
+    /// The number of commits in the fork that are not in the upstream repository.
+    pub ahead_by: u64,
+    /// The number of commits in the upstream repository that are not in the fork.
+    pub behind_by: u64,
+}
+
+impl ForkStatus {
+    pub fn new(ahead_by: u64, behind_by: u64) -> Self {
+        Self { ahead_by, behind_by }
+    }
+
+    /// Whether the fork contains every commit of the upstream repository.
+    pub fn is_up_to_date(&self) -> bool {
+        self.behind_by == 0
+    }
+
+    /// Whether the fork and the upstream repository both have commits that the other does not.
+    ///
+    /// A diverged fork cannot be synchronized automatically, since GitHub only fast-forwards or merges cleanly.
+    pub fn has_diverged(&self) -> bool {
+        self.ahead_by > 0 && self.behind_by > 0
+    }
+}
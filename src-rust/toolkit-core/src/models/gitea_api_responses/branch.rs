@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct BranchCommit {
+    pub id: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Branch {
+    pub name: String,
+    pub commit: BranchCommit,
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct ContentsResponse {
+    pub name: String,
+    pub path: String,
+    pub sha: String,
+    #[serde(rename = "type")]
+    pub r#type: String,
+    pub encoding: Option<String>,
+    pub content: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FileCommit {
+    pub sha: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FilesResponse {
+    pub commit: FileCommit,
+}
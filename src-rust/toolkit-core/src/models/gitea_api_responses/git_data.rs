@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct TreeEntry {
+    pub path: String,
+    pub mode: String,
+    #[serde(rename = "type")]
+    pub r#type: String,
+    pub sha: String,
+    pub size: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Tree {
+    pub sha: String,
+    #[serde(default)]
+    pub tree: Vec<TreeEntry>,
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BlobContent {
+    pub sha: String,
+    pub content: Option<String>,
+    pub encoding: Option<String>,
+    pub size: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Compare {
+    pub total_commits: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MergeUpstream {
+    pub merge_type: Option<String>,
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::gitea_api_responses::repository::User;
+
+#[derive(Serialize, Deserialize)]
+pub struct IssueRepository {
+    pub full_name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Issue {
+    pub number: u64,
+    pub assignees: Option<Vec<User>>,
+    pub repository: Option<IssueRepository>,
+}
@@ -0,0 +1,6 @@
+pub mod branch;
+pub mod contents;
+pub mod git_data;
+pub mod issue;
+pub mod pull_request;
+pub mod repository;
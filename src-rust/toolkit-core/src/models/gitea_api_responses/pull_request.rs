@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::gitea_api_responses::repository::{Repository, User};
+
+#[derive(Serialize, Deserialize)]
+pub struct PullRequestBranch {
+    #[serde(rename = "ref")]
+    pub r#ref: String,
+    pub sha: String,
+    pub repo: Option<Repository>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PullRequest {
+    pub number: u64,
+    pub html_url: String,
+    pub title: String,
+    pub state: String,
+    #[serde(default)]
+    pub merged: bool,
+    #[serde(default)]
+    pub draft: bool,
+    pub mergeable: Option<bool>,
+    pub head: PullRequestBranch,
+    pub base: PullRequestBranch,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ChangedFile {
+    pub filename: String,
+    pub status: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PullReview {
+    pub user: Option<User>,
+    pub state: String,
+    #[serde(default)]
+    pub dismissed: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CommitStatus {
+    pub context: String,
+    pub status: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CombinedStatus {
+    pub state: String,
+    pub statuses: Option<Vec<CommitStatus>>,
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct User {
+    pub login: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Repository {
+    pub name: String,
+    pub full_name: String,
+    pub owner: User,
+    #[serde(default)]
+    pub fork: bool,
+    pub parent: Option<Box<Repository>>,
+    pub default_branch: Option<String>,
+    pub clone_url: Option<String>,
+}
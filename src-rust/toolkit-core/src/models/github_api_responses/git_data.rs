@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct ObjectRef {
+    pub sha: String,
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Blob {
+    pub sha: String,
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BlobContent {
+    pub sha: String,
+    pub content: String,
+    pub encoding: String,
+    pub size: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TreeEntry {
+    pub path: String,
+    pub mode: String,
+    #[serde(rename = "type")]
+    pub r#type: String,
+    pub sha: String,
+    pub size: Option<u64>,
+    pub url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Tree {
+    pub sha: String,
+    pub url: String,
+    pub tree: Vec<TreeEntry>,
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Commit {
+    pub sha: String,
+    pub node_id: String,
+    pub url: String,
+    pub message: String,
+    pub tree: ObjectRef,
+    pub parents: Vec<ObjectRef>,
+}
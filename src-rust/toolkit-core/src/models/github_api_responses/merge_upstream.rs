@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct MergeUpstream {
+    pub message: String,
+    pub merge_type: Option<String>,
+    pub base_branch: Option<String>,
+}
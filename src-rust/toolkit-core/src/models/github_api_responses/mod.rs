@@ -1,2 +1,9 @@
+pub mod check_runs;
 pub mod commit;
+pub mod compare;
+pub mod git_data;
+pub mod merge_upstream;
+pub mod rate_limit;
+pub mod reference;
 pub mod repository_content;
+pub mod user_forks;
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::RateLimit;
+
+#[derive(Serialize, Deserialize)]
+pub struct RateLimitResponse {
+    pub rate: RateLimit,
+}
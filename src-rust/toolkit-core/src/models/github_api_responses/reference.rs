@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct GitObject {
+    pub sha: String,
+    #[serde(rename = "type")]
+    pub r#type: String,
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Reference {
+    #[serde(rename = "ref")]
+    pub r#ref: String,
+    pub node_id: String,
+    pub url: String,
+    pub object: GitObject,
+}
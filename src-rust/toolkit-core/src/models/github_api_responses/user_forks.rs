@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Owner {
+    pub login: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Parent {
+    pub name_with_owner: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Repository {
+    pub name: String,
+    pub owner: Owner,
+    pub parent: Option<Parent>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub end_cursor: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryConnection {
+    pub nodes: Vec<Repository>,
+    pub page_info: PageInfo,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Viewer {
+    pub repositories: RepositoryConnection,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct UserForks {
+    pub viewer: Viewer,
+}
@@ -1,11 +1,26 @@
+pub use branch::Branch;
+pub use file_change::FileChange;
+pub use fork_status::ForkStatus;
 pub use github_repo::GitHubRepo;
 pub use html_filter_rule::HTMLFilterRule;
+pub use pull_request::{CheckRun, PullRequest, PullRequestFile, PullRequestState, PullRequestStatus, ReviewDecision};
+pub use rate_limit::RateLimit;
 pub use repo_rule::RepoRule;
+pub use repo_tree::{RepoTree, TreeEntry, TreeEntryKind};
 pub use supported_websites::SupportedWebsites;
+pub use token_capability::{TokenCapabilities, TokenCapability};
 
 pub mod article_metadata;
+mod branch;
+mod file_change;
+mod fork_status;
+pub mod gitea_api_responses;
 pub mod github_api_responses;
 mod github_repo;
 mod html_filter_rule;
+mod pull_request;
+mod rate_limit;
 pub mod repo_rule;
+mod repo_tree;
 mod supported_websites;
+mod token_capability;
@@ -0,0 +1,159 @@
+use octocrab::models::IssueState;
+
+use crate::models::{gitea_api_responses, GitHubRepo};
+
+/// The state of a pull request.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub enum PullRequestState {
+    /// The pull request is open, waiting to be reviewed and merged.
+    Open,
+    /// The pull request is closed without being merged.
+    Closed,
+    /// The pull request has been merged.
+    Merged,
+}
+
+/// A representation of a pull request, which includes the information the toolkit shows to contributors.
+///
+/// # Fields
+/// - `number`: The number of the pull request. e.g. `42`.
+/// - `node_id`: The GraphQL node ID of the pull request.
+/// - `url`: The URL of the pull request on the web. e.g. `https://github.com/FOSScope/TranslateProject/pull/42`.
+/// - `title`: The title of the pull request.
+/// - `head`: The name of the branch that contains the changes. e.g. `select/news/20240715-hello`.
+/// - `head_repo`: The repository that contains the head branch, `None` if the repository has been deleted.
+/// - `head_sha`: The SHA of the latest commit on the head branch.
+/// - `base`: The name of the branch that the changes are pulled into. e.g. `main`.
+/// - `state`: The [state](PullRequestState) of the pull request.
+/// - `draft`: Whether the pull request is a draft.
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct PullRequest {
+    pub number: u64,
+    pub node_id: String,
+    pub url: String,
+    pub title: String,
+    pub head: String,
+    pub head_repo: Option<GitHubRepo>,
+    pub head_sha: String,
+    pub base: String,
+    pub state: PullRequestState,
+    pub draft: bool,
+}
+
+impl From<octocrab::models::pulls::PullRequest> for PullRequest {
+    fn from(pr: octocrab::models::pulls::PullRequest) -> Self {
+        let state = if pr.merged_at.is_some() {
+            PullRequestState::Merged
+        } else if pr.state == Some(IssueState::Closed) {
+            PullRequestState::Closed
+        } else {
+            PullRequestState::Open
+        };
+        let head_repo = pr.head.repo.as_ref().and_then(|repo| {
+            repo.owner.as_ref().map(|owner| GitHubRepo::new(owner.login.clone(), repo.name.clone()))
+        });
+
+        Self {
+            number: pr.number,
+            node_id: pr.node_id.unwrap_or_default(),
+            url: pr.html_url.map(|url| url.to_string()).unwrap_or(pr.url),
+            title: pr.title.unwrap_or_default(),
+            head: pr.head.ref_field,
+            head_repo,
+            head_sha: pr.head.sha,
+            base: pr.base.ref_field,
+            state,
+            draft: pr.draft.unwrap_or(false),
+        }
+    }
+}
+
+impl From<gitea_api_responses::pull_request::PullRequest> for PullRequest {
+    fn from(pr: gitea_api_responses::pull_request::PullRequest) -> Self {
+        let state = if pr.merged {
+            PullRequestState::Merged
+        } else if pr.state == "closed" {
+            PullRequestState::Closed
+        } else {
+            PullRequestState::Open
+        };
+        let head_repo = pr.head.repo.as_ref()
+            .map(|repo| GitHubRepo::new(repo.owner.login.clone(), repo.name.clone()));
+
+        Self {
+            number: pr.number,
+            // Gitea and Forgejo have no GraphQL API.
+            node_id: String::new(),
+            url: pr.html_url,
+            title: pr.title,
+            head: pr.head.r#ref,
+            head_repo,
+            head_sha: pr.head.sha,
+            base: pr.base.r#ref,
+            state,
+            draft: pr.draft,
+        }
+    }
+}
+
+/// A representation of a file added, changed or removed by a pull request.
+///
+/// # Fields
+/// - `path`: The path of the file. e.g. `sources/news/20240715-hello.md`.
+/// - `sha`: The blob SHA of the file in the head of the pull request, `None` if the pull request removes the file.
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct PullRequestFile {
+    pub path: String,
+    pub sha: Option<String>,
+}
+
+impl From<octocrab::models::repos::DiffEntry> for PullRequestFile {
+    fn from(file: octocrab::models::repos::DiffEntry) -> Self {
+        let removed = file.status == octocrab::models::repos::DiffEntryStatus::Removed;
+        Self {
+            path: file.filename,
+            sha: (!removed).then_some(file.sha),
+        }
+    }
+}
+
+/// The overall review decision of a pull request, based on the latest review of each reviewer.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub enum ReviewDecision {
+    /// No reviewer has approved or requested changes yet.
+    Pending,
+    /// At least one reviewer has approved, and no reviewer is requesting changes.
+    Approved,
+    /// At least one reviewer is requesting changes.
+    ChangesRequested,
+}
+
+/// A representation of a check run (e.g. a GitHub Actions job) on the head commit of a pull request.
+///
+/// # Fields
+/// - `name`: The name of the check. e.g. `lint`.
+/// - `status`: The status of the check, one of `queued`, `in_progress` or `completed`.
+/// - `conclusion`: The conclusion of a completed check. e.g. `success`, `failure`. `None` if the check is not completed.
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct CheckRun {
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+}
+
+/// A representation of what has happened to a pull request: its reviews, whether it can be merged, and its checks.
+///
+/// # Fields
+/// - `pull_request`: The [PullRequest](PullRequest) itself.
+/// - `review_decision`: The overall [review decision](ReviewDecision).
+/// - `mergeable`: Whether the pull request can be merged without conflicts, `None` if GitHub has not computed it yet.
+/// - `mergeable_state`: The mergeable state reported by GitHub. e.g. `clean`, `dirty`, `blocked`.
+/// - `checks`: The [CheckRun](CheckRun)s on the head commit.
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct PullRequestStatus {
+    pub pull_request: PullRequest,
+    pub review_decision: ReviewDecision,
+    pub mergeable: Option<bool>,
+    pub mergeable_state: Option<String>,
+    pub checks: Vec<CheckRun>,
+}
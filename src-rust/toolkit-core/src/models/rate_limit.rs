@@ -0,0 +1,37 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A representation of the GitHub API quota of the signed in user.
+///
+/// # Fields
+/// - `limit`: The maximum number of requests the user can make per hour.
+/// - `remaining`: The number of requests remaining in the current rate limit window.
+/// - `used`: The number of requests made in the current rate limit window.
+/// - `reset`: The time at which the current rate limit window resets, in seconds since the Unix epoch.
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct RateLimit {
+    /// The maximum number of requests the user can make per hour.
+    pub limit: u64,
+    /// The number of requests remaining in the current rate limit window.
+    pub remaining: u64,
+    /// The number of requests made in the current rate limit window.
+    pub used: u64,
+    /// The time at which the current rate limit window resets, in seconds since the Unix epoch.
+    pub reset: u64,
+}
+
+impl RateLimit {
+    pub fn new(limit: u64, remaining: u64, used: u64, reset: u64) -> Self {
+        Self { limit, remaining, used, reset }
+    }
+
+    /// Whether there is no request remaining in the current rate limit window.
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// How long it takes until the current rate limit window resets, zero if it has already reset.
+    pub fn resets_in(&self) -> Duration {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        Duration::from_secs(self.reset.saturating_sub(now))
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use crate::error::{Error, Result};
+use crate::template::TemplateRenderer;
+use crate::utils::slug;
+
+/// How the characters of a title outside of ASCII are handled when turning it into the slug of an article ID.
+///
+/// - `None`: They are kept if they are letters or digits. The default.
+/// - `Pinyin`: The common Chinese characters are replaced by their pinyin, without tones, and the others are removed.
+///   e.g. `开源软件` becomes `kai-yuan-ruan-jian`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Transliteration {
+    #[default]
+    None,
+    Pinyin,
+}
+
+/// A representation of the article ID rule in the FOSScope repository rule, which defines how the ID of a new article,
+/// used as its file name without the `.md` extension, is generated from its title.
+///
+/// # Fields
+/// - `pattern`: The template of the ID. `{{slug}}` is the slug of the title, the workflow variables (e.g. `{{publishing_date}}`,
+///   `{{type_name}}`) and the declared variables are available as well. `{{publishing_date}}-{{slug}}` if not provided.
+/// - `transliteration`: The [Transliteration](Transliteration) of the title. `none` if not provided.
+/// - `max_length`: The maximum number of characters of the ID. The slug is shortened, between words where possible, to fit.
+///   `80` if not provided.
+///
+/// # Example
+/// With the default rule, an article titled `Hello, World!` published on `20240715` gets the ID `20240715-hello-world`.
+///
+/// Check the [related design documentation](https://github.com/FOSScope/Toolkit/blob/main/docs/dev/design/repo-rule.md)
+/// and [RepoRule](super::RepoRule) definition for more information.
+#[derive(Clone, PartialEq, Eq, Debug, serde::Deserialize)]
+pub struct ArticleIdRule {
+    /// The template of the ID. Has to be formatted with real values.
+    #[serde(default = "default_pattern")]
+    pub pattern: String,
+    /// The transliteration of the title.
+    #[serde(default)]
+    pub transliteration: Transliteration,
+    /// The maximum number of characters of the ID.
+    #[serde(default = "default_max_length")]
+    pub max_length: usize,
+}
+
+fn default_pattern() -> String {
+    "{{publishing_date}}-{{slug}}".to_string()
+}
+
+fn default_max_length() -> usize {
+    80
+}
+
+impl Default for ArticleIdRule {
+    fn default() -> Self {
+        Self {
+            pattern: default_pattern(),
+            transliteration: Transliteration::default(),
+            max_length: default_max_length(),
+        }
+    }
+}
+
+/// Remove the dashes left at the ends of an ID, and the repeated ones, e.g. by a variable or a slug that is empty.
+fn tidy(id: &str) -> String {
+    let mut tidy = String::with_capacity(id.len());
+    for c in id.trim_matches('-').chars() {
+        if !(c == '-' && tidy.ends_with('-')) {
+            tidy.push(c);
+        }
+    }
+    tidy
+}
+
+impl ArticleIdRule {
+    /// Turn a title into a slug, with the transliteration of the rule.
+    ///
+    /// # Arguments
+    /// - `title`: The title of the article. e.g. `Hello, World!`.
+    ///
+    /// # Returns
+    /// - `String`: The slug of the title. e.g. `hello-world`.
+    pub fn slugify(&self, title: &str) -> String {
+        slug::slugify(title, self.transliteration)
+    }
+
+    /// Generate the ID of an article from its title.
+    ///
+    /// The ID does not check whether another article has the same ID,
+    /// use [`workflow::article_id::generate`](crate::workflow::article_id::generate) for that.
+    ///
+    /// # Arguments
+    /// - `title`: The title of the article. e.g. `Hello, World!`.
+    /// - `vars`: The variables used in the pattern. e.g. `publishing_date`.
+    ///
+    /// # Returns
+    /// - `Result<String>`
+    ///     - `Ok(String)`: The ID, at most `max_length` characters long. e.g. `20240715-hello-world`.
+    ///     - `Err(Error)`: [`Error::InvalidInput`](crate::Error::InvalidInput) if the ID is empty,
+    ///       contains a character that cannot be used in a file name, or does not fit `max_length` even without the slug,
+    ///       or an error indicating why the pattern could not be rendered.
+    pub fn generate(&self, title: &str, vars: &HashMap<&str, String>) -> Result<String> {
+        let slug = self.slugify(title);
+        let renderer = TemplateRenderer::new();
+        let render = |slug: &str| -> Result<String> {
+            let mut local_vars = vars.clone();
+            local_vars.insert("slug", slug.to_string());
+            Ok(tidy(renderer.render("article_id.pattern", &self.pattern, &local_vars)?.trim()))
+        };
+
+        let mut id = render(&slug)?;
+        let length = id.chars().count();
+        if length > self.max_length {
+            // Shorten the slug by the number of characters the ID is over the limit.
+            let slug_length = slug.chars().count();
+            let available = (slug_length + self.max_length).saturating_sub(length);
+            id = render(slug::truncate(&slug, available))?;
+            if id.chars().count() > self.max_length {
+                return Err(Error::InvalidInput(format!(
+                    "the article ID `{}` is longer than {} characters even without the title", id, self.max_length
+                )));
+            }
+        }
+
+        if id.is_empty() {
+            return Err(Error::InvalidInput(format!("the article ID of `{}` is empty", title)));
+        }
+        if let Some(c) = id.chars().find(|c| matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') || c.is_whitespace()) {
+            return Err(Error::InvalidInput(format!("the article ID `{}` cannot contain `{}`", id, c)));
+        }
+        Ok(id)
+    }
+}
@@ -0,0 +1,419 @@
+use std::collections::HashMap;
+
+use crate::apis::Forge;
+use crate::error::{Error, Result};
+use crate::models::GitHubRepo;
+
+pub use article_id::{ArticleIdRule, Transliteration};
+pub use validation::{RuleDiagnostic, Severity, validate_repo_rule};
+pub use variable::{Variable, VariableType};
+
+mod article_id;
+mod validation;
+mod variable;
+
+/// A representation of an article type in the FOSScope repository rule, which defines the types of articles that can be found in the repository.
+///
+/// # Fields
+/// - `article_type`(`type` in TOML file): The type of the article. e.g. `news`, `tech`.
+/// - `description`: The description of the article type. e.g. `News Articles`, `Tech Articles`.
+/// - `directory`: The directory where the article type is stored. e.g. `{{step}}/news`, `{{step}}/tech`.
+/// - `article_template`: An optional article template to use when creating a new source file for an article.
+///     If not provided, the `article_template` from the [`RepoRule`](struct.RepoRule.html) will be used.
+///
+/// Check the [related design documentation](https://github.com/FOSScope/Toolkit/blob/main/docs/dev/design/repo-rule.md)
+/// and [RepoRule](struct.RepoRule.html) definition for more information.
+#[derive(PartialEq, Eq, Debug, serde::Deserialize)]
+pub struct Article {
+    /// The type of the article.
+    #[serde(rename = "type")]
+    pub r#type: String,
+    /// The description of the article type.
+    pub description: String,
+    /// The directory where the article type is stored.
+    pub directory: String,
+    /// An optional article template to use when creating a new source file for an article.
+    pub article_template: Option<String>,
+}
+
+impl Article {
+    pub fn new(r#type: String, description: String, directory: String, article_template: Option<String>) -> Self {
+        Self {
+            r#type,
+            description,
+            directory,
+            article_template,
+        }
+    }
+}
+
+/// A representation of an action in the FOSScope repository rule, which is a step of the contribution workflow.
+///
+/// An action commits an article to the directory of its `target_step`, moving it from the directory of its `source_step`
+/// if there is one. e.g. `translate` moves an article from `sources/news` to `translated/news`.
+///
+/// # Fields
+/// - `name`: The name of the action, available as `{{action_name}}` in the templates. e.g. `select`, `translate`.
+/// - `description`: The description of the action, available as `{{action_desc}}` in the templates. e.g. `选题`, `翻译`.
+/// - `source_step`: The step the article is taken from, available as `{{source_step}}` in the templates.
+///   `None` if the action creates the article, e.g. `select`.
+/// - `target_step`: The step the article is committed to, available as `{{step}}` in the templates. e.g. `translated`.
+/// - `required_vars`: The variables that have to be given to perform the action. e.g. `["article_id", "article_title"]`.
+/// - `branch_naming`: An optional branch naming template overriding `branch_naming` of the [GitRule](struct.GitRule.html).
+/// - `commit_message`: An optional commit message template overriding `commit_message` of the [GitRule](struct.GitRule.html).
+/// - `pull_request`: The [PullRequestRule](struct.PullRequestRule.html) overriding `pull_request` of the [GitRule](struct.GitRule.html),
+///   defined directly in the `[[actions]]` table. e.g. `labels = ["选题"]`.
+///
+/// Check the [related design documentation](https://github.com/FOSScope/Toolkit/blob/main/docs/dev/design/repo-rule.md)
+/// and [RepoRule](struct.RepoRule.html) definition for more information.
+#[derive(Clone, PartialEq, Eq, Debug, serde::Deserialize)]
+pub struct Action {
+    /// The name of the action.
+    pub name: String,
+    /// The description of the action.
+    pub description: String,
+    /// The step the article is taken from, `None` if the action creates the article.
+    #[serde(default)]
+    pub source_step: Option<String>,
+    /// The step the article is committed to.
+    pub target_step: String,
+    /// The variables that have to be given to perform the action.
+    #[serde(default)]
+    pub required_vars: Vec<String>,
+    /// The branch naming template of the action. Has to be formatted with real values.
+    #[serde(default)]
+    pub branch_naming: Option<String>,
+    /// The commit message template of the action. Has to be formatted with real values.
+    #[serde(default)]
+    pub commit_message: Option<String>,
+    /// The pull request rule of the action.
+    #[serde(flatten)]
+    pub pull_request: PullRequestRule,
+}
+
+impl Action {
+    pub fn new(name: String, description: String, source_step: Option<String>, target_step: String) -> Self {
+        Self {
+            name,
+            description,
+            source_step,
+            target_step,
+            required_vars: Vec::new(),
+            branch_naming: None,
+            commit_message: None,
+            pull_request: PullRequestRule::default(),
+        }
+    }
+
+    /// Whether the action moves the article from one step to another, rather than creating or editing it in place.
+    pub fn moves_article(&self) -> bool {
+        self.source_step.as_ref().is_some_and(|source_step| *source_step != self.target_step)
+    }
+
+    /// Get the branch naming template of the action.
+    ///
+    /// # Returns
+    /// - `&str`: The `branch_naming` of the action if defined, the `branch_naming` of the [GitRule](struct.GitRule.html) otherwise.
+    pub fn branch_naming<'a>(&'a self, git: &'a GitRule) -> &'a str {
+        self.branch_naming.as_deref().unwrap_or(&git.branch_naming)
+    }
+
+    /// Get the commit message template of the action.
+    ///
+    /// # Returns
+    /// - `&str`: The `commit_message` of the action if defined, the `commit_message` of the [GitRule](struct.GitRule.html) otherwise.
+    pub fn commit_message<'a>(&'a self, git: &'a GitRule) -> &'a str {
+        self.commit_message.as_deref().unwrap_or(&git.commit_message)
+    }
+
+    /// Get the pull request rule of the action.
+    ///
+    /// The fields of the action take precedence over the deprecated `[git.actions.<action_name>]` table,
+    /// which takes precedence over the fields of the `[git]` table.
+    ///
+    /// # Returns
+    /// - `PullRequestRule`: The [PullRequestRule](struct.PullRequestRule.html) to apply to the pull request of the action.
+    pub fn pull_request_rule(&self, git: &GitRule) -> PullRequestRule {
+        let rule = match git.actions.get(&self.name) {
+            Some(rule) => git.pull_request.merge(rule),
+            None => git.pull_request.clone(),
+        };
+        rule.merge(&self.pull_request)
+    }
+
+    /// Add the variables of the action (`action_name`, `action_desc`, `step` and `source_step`) to the given variables.
+    ///
+    /// # Returns
+    /// - `HashMap<&str, String>`: A copy of `vars` with the variables of the action added.
+    pub fn vars<'a>(&self, vars: &HashMap<&'a str, String>) -> HashMap<&'a str, String> {
+        let mut local_vars = vars.clone();
+        local_vars.insert("action_name", self.name.clone());
+        local_vars.insert("action_desc", self.description.clone());
+        local_vars.insert("step", self.target_step.clone());
+        if let Some(source_step) = &self.source_step {
+            local_vars.insert("source_step", source_step.clone());
+        }
+        local_vars
+    }
+
+    /// Check that every required variable of the action is given and not empty.
+    ///
+    /// # Returns
+    /// - `Result<()>`: `Ok` if every required variable is given, [`Error::InvalidInput`](crate::Error::InvalidInput) listing the missing variables otherwise.
+    pub fn check_vars(&self, vars: &HashMap<&str, String>) -> Result<()> {
+        let missing: Vec<&str> = self.missing_vars(vars);
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InvalidInput(format!("the {} action requires the variables {}", self.name, missing.join(", "))))
+        }
+    }
+
+    /// Get the required variables of the action that are not given, or empty.
+    pub fn missing_vars(&self, vars: &HashMap<&str, String>) -> Vec<&str> {
+        self.required_vars.iter()
+            .filter(|var| vars.get(var.as_str()).is_none_or(|value| value.trim().is_empty()))
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// The actions of the translation workflow, used if the repository rule does not define any action.
+fn default_actions() -> Vec<Action> {
+    let required_vars = vec!["article_id".to_string(), "article_title".to_string()];
+    [
+        ("select", "选题", None, "sources"),
+        ("translate", "翻译", Some("sources"), "translated"),
+        ("proofread", "校对", Some("translated"), "translated"),
+        ("publish", "发布", Some("translated"), "published"),
+    ]
+        .into_iter()
+        .map(|(name, description, source_step, target_step)| Action {
+            required_vars: required_vars.clone(),
+            ..Action::new(name.to_string(), description.to_string(), source_step.map(str::to_string), target_step.to_string())
+        })
+        .collect()
+}
+
+/// A representation of the pull request rule in the FOSScope repository rule, which defines how contribution pull requests are opened.
+///
+/// Every field is optional. The fields defined directly in the `[git]` table apply to every action,
+/// and can be overridden per action in its `[[actions]]` table.
+///
+/// # Fields
+/// - `pr_title`: The pull request title template. If not provided, the `commit_message` of the [GitRule](struct.GitRule.html) will be used.
+/// - `pr_body`: The pull request body template. If not provided, the pull request body will be empty.
+/// - `labels`: The labels to add to the pull request. e.g. `["选题"]`.
+/// - `reviewers`: The users to request a review from.
+/// - `assignees`: The users to assign the pull request to.
+/// - `draft`: Whether to open the pull request as a draft.
+///
+/// Check the [related design documentation](https://github.com/FOSScope/Toolkit/blob/main/docs/dev/design/repo-rule.md)
+/// and [RepoRule](struct.RepoRule.html) definition for more information.
+#[derive(Clone, Default, PartialEq, Eq, Debug, serde::Deserialize)]
+pub struct PullRequestRule {
+    /// The pull request title template. Has to be formatted with real values.
+    pub pr_title: Option<String>,
+    /// The pull request body template. Has to be formatted with real values.
+    pub pr_body: Option<String>,
+    /// The labels to add to the pull request.
+    pub labels: Option<Vec<String>>,
+    /// The users to request a review from.
+    pub reviewers: Option<Vec<String>>,
+    /// The users to assign the pull request to.
+    pub assignees: Option<Vec<String>>,
+    /// Whether to open the pull request as a draft.
+    pub draft: Option<bool>,
+}
+
+impl PullRequestRule {
+    /// Override the fields of this rule with the fields defined in another rule.
+    ///
+    /// # Arguments
+    /// - `other`: The rule to take precedence, usually the rule of a specific action.
+    ///
+    /// # Returns
+    /// - `PullRequestRule`: A new rule with the fields of `other` where defined, and the fields of this rule otherwise.
+    pub fn merge(&self, other: &PullRequestRule) -> PullRequestRule {
+        PullRequestRule {
+            pr_title: other.pr_title.clone().or_else(|| self.pr_title.clone()),
+            pr_body: other.pr_body.clone().or_else(|| self.pr_body.clone()),
+            labels: other.labels.clone().or_else(|| self.labels.clone()),
+            reviewers: other.reviewers.clone().or_else(|| self.reviewers.clone()),
+            assignees: other.assignees.clone().or_else(|| self.assignees.clone()),
+            draft: other.draft.or(self.draft),
+        }
+    }
+}
+
+/// A representation of the Git rule in the FOSScope repository rule, which defines how repositories should be managed.
+///
+/// # Fields
+/// - `branch_naming`: The naming convention for branches. Which is a string containing placeholders that will be replaced with the actual values.
+/// - `commit_message`: The commit message template. Which is a string containing placeholders that will be replaced with the actual values.
+/// - `pull_request`: The [PullRequestRule](struct.PullRequestRule.html) for every action, defined directly in the `[git]` table.
+/// - `actions`: Deprecated, the [PullRequestRule](struct.PullRequestRule.html)s overriding `pull_request` for specific actions,
+///   keyed by the action name. The overrides belong in the `[[actions]]` tables, see [Action](struct.Action.html).
+///
+/// # Example
+/// - `branch_naming`: `{{action_name}}/{{type_name}}/{{article_id}}`
+/// - `commit_message`: `[{{action_desc}}][{{type_desc}}]: {{article_title}}`
+///
+/// Check the [related design documentation](https://github.com/FOSScope/Toolkit/blob/main/docs/dev/design/repo-rule.md)
+/// and [RepoRule](struct.RepoRule.html) definition for more information.
+#[derive(PartialEq, Eq, Debug, serde::Deserialize)]
+pub struct GitRule {
+    /// The name of the branch to create contribution pull request to.
+    pub base: String,
+    /// The naming convention for branches. Has to be formatted with real values.
+    pub branch_naming: String,
+    /// The commit message template. Has to be formatted with real values.
+    pub commit_message: String,
+    /// The pull request rule for every action.
+    #[serde(flatten)]
+    pub pull_request: PullRequestRule,
+    /// Deprecated, the pull request rules for specific actions, keyed by the action name.
+    /// Still applied by [Action::pull_request_rule](struct.Action.html#method.pull_request_rule) so older rules keep working.
+    #[serde(default)]
+    pub actions: HashMap<String, PullRequestRule>,
+}
+
+impl GitRule {
+    pub fn new(base: String, branch_naming: String, commit_message: String) -> Self {
+        Self {
+            base,
+            branch_naming,
+            commit_message,
+            pull_request: PullRequestRule::default(),
+            actions: HashMap::new(),
+        }
+    }
+}
+
+/// A representation of the FOSScope repository rule, which defines how repositories should be managed.
+///
+/// The rule includes a list of articles, a list of actions, and a Git rule.
+///
+/// # Fields
+/// - `article_template`(String): The article template to use when creating a new source file for an article.
+/// - `articles`([Article](struct.Article.html)): A list of types of articles that can be found in the repository.
+/// - `actions`([Action](struct.Action.html)): A list of actions that can be performed on the repository.
+///   If not provided, the select, translate, proofread and publish actions of the translation workflow are used.
+/// - `variables`([Variable](struct.Variable.html)): A list of variables the contributors give when performing the actions,
+///   e.g. `cover_image` or `tags` in the article template. Empty if not provided.
+/// - `partials`(HashMap<String, String>): Templates that can be included in the other templates by their name,
+///   e.g. `{{> footer}}` includes the `footer` partial. Empty if not provided.
+/// - `article_id`([ArticleIdRule](struct.ArticleIdRule.html)): How the IDs of new articles are generated from their titles.
+///   `{{publishing_date}}-{{slug}}`, without transliteration and at most 80 characters long, if not provided.
+/// - `git`([GitRule](struct.GitRule.html)): The Git rule that defines how the repository should be managed.
+///
+/// Check the [related design documentation](https://github.com/FOSScope/Toolkit/blob/main/docs/dev/design/repo-rule.md) for more information.
+#[derive(PartialEq, Eq, Debug, serde::Deserialize)]
+pub struct RepoRule {
+    /// The article template to use when creating a new source file for an article.
+    pub article_template: String,
+    /// The list of types of articles that can be found in the repository.
+    pub articles: Vec<Article>,
+    /// The list of actions that can be performed on the repository.
+    #[serde(default = "default_actions")]
+    pub actions: Vec<Action>,
+    /// The list of variables the contributors give when performing the actions.
+    #[serde(default)]
+    pub variables: Vec<Variable>,
+    /// The templates that can be included in the other templates, keyed by their name.
+    #[serde(default)]
+    pub partials: HashMap<String, String>,
+    /// How the IDs of new articles are generated from their titles.
+    #[serde(default)]
+    pub article_id: ArticleIdRule,
+    /// The Git rule that defines how the repository should be managed.
+    pub git: GitRule,
+}
+
+impl RepoRule {
+    pub fn new(article_template: String, articles: Vec<Article>, git: GitRule) -> Self {
+        Self {
+            article_template,
+            articles,
+            actions: default_actions(),
+            variables: Vec::new(),
+            partials: HashMap::new(),
+            article_id: ArticleIdRule::default(),
+            git,
+        }
+    }
+
+    /// Get an action of the repository rule by its name.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the action. e.g. `select`.
+    ///
+    /// # Returns
+    /// - `Result<&Action>`
+    ///     - `Ok(&Action)`: The [Action](struct.Action.html).
+    ///     - `Err(Error)`: [`Error::NotFound`](crate::Error::NotFound) if the repository rule does not define the action.
+    pub fn action(&self, name: &str) -> Result<&Action> {
+        self.actions.iter()
+            .find(|action| action.name == name)
+            .ok_or_else(|| Error::NotFound(format!("action {} in the repository rule", name)))
+    }
+
+    /// Get a variable declared in the repository rule by its name.
+    ///
+    /// # Returns
+    /// - `Option<&Variable>`: The [Variable](struct.Variable.html), `None` if the repository rule does not declare it.
+    pub fn variable(&self, name: &str) -> Option<&Variable> {
+        self.variables.iter().find(|variable| variable.name == name)
+    }
+
+    /// Get the variables an action asks for, in the order they are declared.
+    ///
+    /// # Arguments
+    /// - `action_name`: The name of the action. e.g. `select`.
+    pub fn variables_of(&self, action_name: &str) -> Vec<&Variable> {
+        self.variables.iter()
+            .filter(|variable| variable.is_asked_by(action_name))
+            .collect()
+    }
+
+    /// Check the values of the variables an action asks for, before rendering the templates with them.
+    ///
+    /// # Arguments
+    /// - `action_name`: The name of the action. e.g. `select`.
+    /// - `vars`: The variables given to the action.
+    ///
+    /// # Returns
+    /// - `Result<()>`: `Ok` if every value is valid, [`Error::InvalidInput`](crate::Error::InvalidInput) describing the first invalid
+    ///   or missing value otherwise. See [`Variable::parse`](struct.Variable.html#method.parse).
+    pub fn check_variables(&self, action_name: &str, vars: &HashMap<&str, String>) -> Result<()> {
+        self.variables_of(action_name).into_iter()
+            .try_for_each(|variable| variable.check(vars))
+    }
+
+    pub fn get_article_template(&self, article: &Article) -> String {
+        match &article.article_template {
+            Some(template) => template.clone(),
+            None => self.article_template.clone(),
+        }
+    }
+}
+
+/// Get the deserialized repository rule of a repository.
+/// The repository rule is stored in a file named `REPORULE` in the root directory of the repository.
+///
+/// # Arguments
+/// - `repo`: The repository to get the repository rule from.
+/// - `api`: The [Forge](Forge) hosting the repository, which provides the method to get the file content.
+///
+/// # Returns
+/// - `Result<RepoRule>`: The deserialized repository rule if successful, otherwise:
+///     - [`Error::NotFound`](crate::Error::NotFound) if the repository has no `REPORULE` file.
+///     - [`Error::RepoRule`](crate::Error::RepoRule) if the file is not a valid repository rule.
+pub async fn get_repo_rule(repo: &GitHubRepo, api: &impl Forge) -> Result<RepoRule> {
+    let rule = api.get_file_content(
+        repo,
+        "REPORULE",
+    ).await?;
+
+    Ok(toml::from_str(&rule)?)
+}
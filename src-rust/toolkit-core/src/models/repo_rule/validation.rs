@@ -0,0 +1,550 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Range;
+
+use handlebars::template::{Parameter, TemplateElement};
+use handlebars::{Path, Template};
+use regex::Regex;
+use toml::Spanned;
+
+use super::{RepoRule, VariableType};
+use crate::template::unsafe_path;
+
+/// The variables provided when rendering the `directory` of an article type.
+const DIRECTORY_VARS: [&str; 3] = ["step", "type_name", "type_desc"];
+
+/// The variables provided by the workflows when rendering the branch names, commit messages and pull requests,
+/// on top of the `required_vars` of the actions.
+const WORKFLOW_VARS: [&str; 12] = [
+    "action_name", "action_desc", "step", "source_step", "type_name", "type_desc",
+    "article_id", "article_title", "via", "selector", "contributor", "publishing_date",
+];
+
+/// The severity of a [RuleDiagnostic](RuleDiagnostic).
+///
+/// - `Error`: The repository rule cannot be used as it is, e.g. a template does not parse.
+/// - `Warning`: The repository rule can be used, but probably does not do what was intended.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A problem found in a repository rule by [`RepoRule::validate`](RepoRule::validate) or [`validate_repo_rule`](validate_repo_rule).
+///
+/// # Fields
+/// - `severity`: The [Severity](Severity) of the problem.
+/// - `field`: The path of the field the problem is in. e.g. `articles[1].directory`, `git.branch_naming`.
+///   Empty if the problem is not in a single field, e.g. the file is not valid TOML.
+/// - `message`: The description of the problem.
+/// - `line`: The line (starting at 1) of the `REPORULE` file the problem is at, `None` if unknown.
+/// - `column`: The column (starting at 1) of the `REPORULE` file the problem is at, `None` if unknown.
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct RuleDiagnostic {
+    pub severity: Severity,
+    pub field: String,
+    pub message: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl RuleDiagnostic {
+    fn new(severity: Severity, field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            field: field.into(),
+            message: message.into(),
+            line: None,
+            column: None,
+        }
+    }
+
+    fn error(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, field, message)
+    }
+
+    fn warning(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, field, message)
+    }
+
+    /// Whether the problem prevents the repository rule from being used.
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for RuleDiagnostic {
+    /// Format the diagnostic like a compiler does, e.g. `12:13: error: articles[1].directory: the directory is empty`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            write!(f, "{}:{}: ", line, column)?;
+        }
+        match self.severity {
+            Severity::Error => write!(f, "error: ")?,
+            Severity::Warning => write!(f, "warning: ")?,
+        }
+        if !self.field.is_empty() {
+            write!(f, "{}: ", self.field)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Collect the variables a template uses, e.g. `article_id` in `{{action_name}}/{{article_id}}`.
+///
+/// The variables inside `#each` and `#with` blocks are relative to another value, so they are not collected.
+fn template_variables(template: &Template, variables: &mut Vec<String>) {
+    for element in &template.elements {
+        let helper = match element {
+            TemplateElement::Expression(helper)
+            | TemplateElement::HtmlExpression(helper)
+            | TemplateElement::HelperBlock(helper) => helper,
+            _ => continue,
+        };
+        // A lone name is a variable, otherwise it is the name of a helper, whose parameters are the variables.
+        if helper.params.is_empty() && helper.hash.is_empty() && !helper.block {
+            parameter_variables(&helper.name, variables);
+        }
+        for param in helper.params.iter().chain(helper.hash.values()) {
+            parameter_variables(param, variables);
+        }
+        if matches!(helper.name.as_name(), Some("if" | "unless")) {
+            for block in [&helper.template, &helper.inverse].into_iter().flatten() {
+                template_variables(block, variables);
+            }
+        }
+    }
+}
+
+/// Collect the partials a template includes, e.g. `footer` in `{{> footer}}`.
+fn template_partials(template: &Template, partials: &mut Vec<String>) {
+    for element in &template.elements {
+        match element {
+            TemplateElement::PartialExpression(partial) | TemplateElement::PartialBlock(partial) => {
+                let name = match &partial.name {
+                    Parameter::Literal(serde_json::Value::String(name)) => Some(name.as_str()),
+                    name => name.as_name(),
+                };
+                // `@partial-block` is the block given to a partial, not a partial of the rule.
+                if let Some(name) = name.filter(|name| !name.starts_with('@')) {
+                    partials.push(name.to_string());
+                }
+                if let Some(block) = &partial.template {
+                    template_partials(block, partials);
+                }
+            }
+            TemplateElement::HelperBlock(helper) => {
+                for block in [&helper.template, &helper.inverse].into_iter().flatten() {
+                    template_partials(block, partials);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Collect the variable a parameter of a template refers to, if any.
+fn parameter_variables(parameter: &Parameter, variables: &mut Vec<String>) {
+    let raw = match parameter {
+        Parameter::Name(name) => name,
+        Parameter::Path(Path::Relative((_, raw))) => raw,
+        Parameter::Subexpression(subexpression) => {
+            for param in subexpression.params().into_iter().flatten() {
+                parameter_variables(param, variables);
+            }
+            return;
+        }
+        _ => return,
+    };
+    let name = raw.split(['.', '/']).next().unwrap_or_default();
+    if !name.is_empty() && name != "this" && !variables.iter().any(|variable| variable == name) {
+        variables.push(name.to_string());
+    }
+}
+
+/// Check that a template parses, only includes the `partials` of the rule, and only uses the `provided` variables, if given.
+fn check_template(
+    diagnostics: &mut Vec<RuleDiagnostic>,
+    partials: &HashSet<&str>,
+    field: &str,
+    template: &str,
+    provided: Option<&HashSet<&str>>,
+) {
+    match Template::compile(template) {
+        Ok(template) => {
+            let mut included = Vec::new();
+            template_partials(&template, &mut included);
+            for partial in included.iter().filter(|partial| !partials.contains(partial.as_str())) {
+                diagnostics.push(RuleDiagnostic::error(field, format!("the partial `{}` is not defined", partial)));
+            }
+            let Some(provided) = provided else { return };
+            let mut variables = Vec::new();
+            template_variables(&template, &mut variables);
+            for variable in variables.iter().filter(|variable| !provided.contains(variable.as_str())) {
+                diagnostics.push(RuleDiagnostic::error(
+                    field, format!("the placeholder `{{{{{}}}}}` is not provided by any workflow", variable),
+                ));
+            }
+        }
+        Err(e) => {
+            let position = match e.pos() {
+                Some((line, column)) => format!(" at line {}, column {} of the template", line, column),
+                None => String::new(),
+            };
+            diagnostics.push(RuleDiagnostic::error(
+                field, format!("the template does not parse{}: {}", position, e.reason()),
+            ));
+        }
+    }
+}
+
+impl RepoRule {
+    /// Check the repository rule for problems that would make the workflows fail or misbehave.
+    ///
+    /// The checks are:
+    /// - No two article types or actions share the same name.
+    /// - The directories of the article types and the steps of the actions are not empty, and stay inside the repository.
+    /// - Every template parses, and only includes the partials the rule defines.
+    /// - The `directory`, `branch_naming`, `commit_message`, `pr_title` and `pr_body` templates only use placeholders
+    ///   the workflows provide (see the `required_vars` of the [Action](super::Action)s) or the rule declares.
+    /// - No two variables share the same name, `enum` variables have options, and the patterns and defaults of the variables are valid.
+    /// - The article ID pattern only uses `{{slug}}` and the placeholders above, stays in a single file name, and fits a positive `max_length`.
+    ///
+    /// # Returns
+    /// - `Vec<RuleDiagnostic>`: The [RuleDiagnostic](RuleDiagnostic)s, without line and column, as the rule has been parsed already.
+    ///   Empty if no problem has been found. Use [`validate_repo_rule`](validate_repo_rule) to validate a `REPORULE` file
+    ///   with the positions of the problems.
+    pub fn validate(&self) -> Vec<RuleDiagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let partials: HashSet<&str> = self.partials.keys().map(String::as_str).collect();
+        let mut partial_names: Vec<&String> = self.partials.keys().collect();
+        partial_names.sort();
+        for name in partial_names {
+            check_template(&mut diagnostics, &partials, &format!("partials.{}", name), &self.partials[name], None);
+        }
+        check_template(&mut diagnostics, &partials, "article_template", &self.article_template, None);
+
+        let directory_vars: HashSet<&str> = DIRECTORY_VARS.into_iter().collect();
+        let mut types = HashSet::new();
+        for (i, article) in self.articles.iter().enumerate() {
+            if !types.insert(article.r#type.as_str()) {
+                diagnostics.push(RuleDiagnostic::error(
+                    format!("articles[{}].type", i), format!("the article type `{}` is defined more than once", article.r#type),
+                ));
+            }
+
+            let field = format!("articles[{}].directory", i);
+            let directory = article.directory.trim().trim_matches('/');
+            if directory.is_empty() {
+                diagnostics.push(RuleDiagnostic::error(&field, "the directory is empty"));
+            } else {
+                if let Some(reason) = unsafe_path(article.directory.trim()) {
+                    diagnostics.push(RuleDiagnostic::error(&field, format!("the directory is unsafe, as {}", reason)));
+                }
+                if !directory.contains("{{step}}") {
+                    diagnostics.push(RuleDiagnostic::warning(
+                        &field, "the directory does not contain `{{step}}`, so the articles stay in it through every step",
+                    ));
+                }
+            }
+            check_template(&mut diagnostics, &partials, &field, &article.directory, Some(&directory_vars));
+
+            if let Some(template) = &article.article_template {
+                check_template(&mut diagnostics, &partials, &format!("articles[{}].article_template", i), template, None);
+            }
+        }
+
+        let mut variable_names = HashSet::new();
+        for (i, variable) in self.variables.iter().enumerate() {
+            if !variable_names.insert(variable.name.as_str()) {
+                diagnostics.push(RuleDiagnostic::error(
+                    format!("variables[{}].name", i), format!("the variable `{}` is declared more than once", variable.name),
+                ));
+            }
+            match (variable.r#type, variable.options.is_empty()) {
+                (VariableType::Enum, true) => diagnostics.push(RuleDiagnostic::error(
+                    format!("variables[{}].type", i), "an `enum` variable needs `options`",
+                )),
+                (VariableType::Enum, false) | (_, true) => {}
+                (_, false) => diagnostics.push(RuleDiagnostic::warning(
+                    format!("variables[{}].type", i), "`options` are only used by `enum` variables",
+                )),
+            }
+            let pattern_valid = match &variable.pattern {
+                Some(pattern) => match Regex::new(pattern) {
+                    Ok(_) => true,
+                    Err(e) => {
+                        diagnostics.push(RuleDiagnostic::error(
+                            format!("variables[{}].pattern", i), format!("the pattern does not parse: {}", e),
+                        ));
+                        false
+                    }
+                },
+                None => true,
+            };
+            if let (Some(default), true) = (&variable.default, pattern_valid) {
+                if let Err(e) = variable.parse(default) {
+                    diagnostics.push(RuleDiagnostic::error(
+                        format!("variables[{}].default", i), format!("the default is not valid: {}", e),
+                    ));
+                }
+            }
+            for action in variable.actions.iter().filter(|name| !self.actions.iter().any(|action| action.name == **name)) {
+                diagnostics.push(RuleDiagnostic::warning(
+                    format!("variables[{}].name", i), format!("no action is named `{}`", action),
+                ));
+            }
+        }
+
+        let mut names = HashSet::new();
+        let mut required_vars: HashSet<&str> = WORKFLOW_VARS.into_iter().collect();
+        required_vars.extend(variable_names.iter().copied());
+        for (i, action) in self.actions.iter().enumerate() {
+            if !names.insert(action.name.as_str()) {
+                diagnostics.push(RuleDiagnostic::error(
+                    format!("actions[{}].name", i), format!("the action `{}` is defined more than once", action.name),
+                ));
+            }
+            let steps = [("source_step", action.source_step.as_deref()), ("target_step", Some(action.target_step.as_str()))];
+            for (key, step) in steps {
+                let Some(step) = step else { continue };
+                let field = format!("actions[{}].{}", i, key);
+                if step.trim().trim_matches('/').is_empty() {
+                    diagnostics.push(RuleDiagnostic::error(&field, "the step is empty"));
+                } else if let Some(reason) = unsafe_path(step.trim()) {
+                    diagnostics.push(RuleDiagnostic::error(&field, format!("the step is unsafe, as {}", reason)));
+                }
+            }
+            required_vars.extend(action.required_vars.iter().map(String::as_str));
+
+            let mut provided: HashSet<&str> = WORKFLOW_VARS.into_iter().collect();
+            provided.extend(action.required_vars.iter().map(String::as_str));
+            provided.extend(variable_names.iter().copied());
+            if let Some(template) = &action.branch_naming {
+                check_template(&mut diagnostics, &partials, &format!("actions[{}].branch_naming", i), template, Some(&provided));
+            }
+            if let Some(template) = &action.commit_message {
+                check_template(&mut diagnostics, &partials, &format!("actions[{}].commit_message", i), template, Some(&provided));
+            }
+            if let Some(template) = &action.pull_request.pr_title {
+                check_template(&mut diagnostics, &partials, &format!("actions[{}].pr_title", i), template, Some(&provided));
+            }
+            if let Some(template) = &action.pull_request.pr_body {
+                check_template(&mut diagnostics, &partials, &format!("actions[{}].pr_body", i), template, Some(&provided));
+            }
+        }
+
+        let article_id = &self.article_id;
+        let mut id_vars: HashSet<&str> = WORKFLOW_VARS.into_iter().filter(|var| *var != "article_id").collect();
+        id_vars.insert("slug");
+        id_vars.extend(variable_names.iter().copied());
+        check_template(&mut diagnostics, &partials, "article_id.pattern", &article_id.pattern, Some(&id_vars));
+        if article_id.pattern.contains('/') || article_id.pattern.contains('\\') {
+            diagnostics.push(RuleDiagnostic::error("article_id.pattern", "the article ID is a file name, so it cannot contain a slash"));
+        }
+        if !article_id.pattern.contains("slug") {
+            diagnostics.push(RuleDiagnostic::warning(
+                "article_id.pattern", "the pattern does not contain `{{slug}}`, so articles with different titles get the same ID",
+            ));
+        }
+        if article_id.max_length == 0 {
+            diagnostics.push(RuleDiagnostic::error("article_id.max_length", "the maximum length has to be greater than 0"));
+        }
+
+        // The templates of the Git rule are shared by every action.
+        let git = &self.git;
+        check_template(&mut diagnostics, &partials, "git.branch_naming", &git.branch_naming, Some(&required_vars));
+        check_template(&mut diagnostics, &partials, "git.commit_message", &git.commit_message, Some(&required_vars));
+        let mut pull_request_rules = vec![("git".to_string(), &git.pull_request)];
+        let mut overridden: Vec<(&String, _)> = git.actions.iter().collect();
+        overridden.sort_by_key(|(name, _)| name.as_str());
+        for (name, rule) in overridden {
+            pull_request_rules.push((format!("git.actions.{}", name), rule));
+            diagnostics.push(RuleDiagnostic::warning(
+                format!("git.actions.{}", name),
+                format!("`[git.actions.{}]` is deprecated, move its settings to the `[[actions]]` named `{}`", name, name),
+            ));
+            if !self.actions.iter().any(|action| action.name == *name) {
+                diagnostics.push(RuleDiagnostic::warning(
+                    format!("git.actions.{}", name), format!("no action is named `{}`", name),
+                ));
+            }
+        }
+        for (prefix, rule) in pull_request_rules {
+            if let Some(template) = &rule.pr_title {
+                check_template(&mut diagnostics, &partials, &format!("{}.pr_title", prefix), template, Some(&required_vars));
+            }
+            if let Some(template) = &rule.pr_body {
+                check_template(&mut diagnostics, &partials, &format!("{}.pr_body", prefix), template, Some(&required_vars));
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// The positions of the string values of a `REPORULE` file, read alongside the [RepoRule](RepoRule).
+#[derive(Default, serde::Deserialize)]
+struct SpannedRule {
+    article_template: Option<Spanned<String>>,
+    #[serde(default)]
+    articles: Vec<SpannedArticle>,
+    #[serde(default)]
+    actions: Vec<SpannedAction>,
+    #[serde(default)]
+    variables: Vec<SpannedVariable>,
+    #[serde(default)]
+    partials: HashMap<String, Spanned<String>>,
+    article_id: Option<SpannedArticleId>,
+    git: Option<SpannedGit>,
+}
+
+#[derive(serde::Deserialize)]
+struct SpannedArticle {
+    #[serde(rename = "type")]
+    r#type: Option<Spanned<String>>,
+    directory: Option<Spanned<String>>,
+    article_template: Option<Spanned<String>>,
+}
+
+#[derive(serde::Deserialize)]
+struct SpannedAction {
+    name: Option<Spanned<String>>,
+    source_step: Option<Spanned<String>>,
+    target_step: Option<Spanned<String>>,
+    branch_naming: Option<Spanned<String>>,
+    commit_message: Option<Spanned<String>>,
+    pr_title: Option<Spanned<String>>,
+    pr_body: Option<Spanned<String>>,
+}
+
+#[derive(serde::Deserialize)]
+struct SpannedVariable {
+    name: Option<Spanned<String>>,
+    #[serde(rename = "type")]
+    r#type: Option<Spanned<String>>,
+    pattern: Option<Spanned<String>>,
+    default: Option<Spanned<String>>,
+}
+
+#[derive(serde::Deserialize)]
+struct SpannedArticleId {
+    pattern: Option<Spanned<String>>,
+    max_length: Option<Spanned<usize>>,
+}
+
+#[derive(serde::Deserialize)]
+struct SpannedGit {
+    branch_naming: Option<Spanned<String>>,
+    commit_message: Option<Spanned<String>>,
+    pr_title: Option<Spanned<String>>,
+    pr_body: Option<Spanned<String>>,
+    #[serde(default)]
+    actions: HashMap<String, SpannedPullRequestRule>,
+}
+
+#[derive(serde::Deserialize)]
+struct SpannedPullRequestRule {
+    pr_title: Option<Spanned<String>>,
+    pr_body: Option<Spanned<String>>,
+}
+
+impl SpannedRule {
+    /// Map the path of every field, as used by the [RuleDiagnostic](RuleDiagnostic)s, to the position of its value.
+    fn spans(self) -> HashMap<String, Range<usize>> {
+        let mut spans = HashMap::new();
+        let mut insert = |field: String, value: Option<Spanned<String>>| {
+            if let Some(value) = value {
+                spans.insert(field, value.span());
+            }
+        };
+
+        insert("article_template".to_string(), self.article_template);
+        for (i, article) in self.articles.into_iter().enumerate() {
+            insert(format!("articles[{}].type", i), article.r#type);
+            insert(format!("articles[{}].directory", i), article.directory);
+            insert(format!("articles[{}].article_template", i), article.article_template);
+        }
+        for (i, action) in self.actions.into_iter().enumerate() {
+            insert(format!("actions[{}].name", i), action.name);
+            insert(format!("actions[{}].source_step", i), action.source_step);
+            insert(format!("actions[{}].target_step", i), action.target_step);
+            insert(format!("actions[{}].branch_naming", i), action.branch_naming);
+            insert(format!("actions[{}].commit_message", i), action.commit_message);
+            insert(format!("actions[{}].pr_title", i), action.pr_title);
+            insert(format!("actions[{}].pr_body", i), action.pr_body);
+        }
+        for (i, variable) in self.variables.into_iter().enumerate() {
+            insert(format!("variables[{}].name", i), variable.name);
+            insert(format!("variables[{}].type", i), variable.r#type);
+            insert(format!("variables[{}].pattern", i), variable.pattern);
+            insert(format!("variables[{}].default", i), variable.default);
+        }
+        for (name, partial) in self.partials {
+            insert(format!("partials.{}", name), Some(partial));
+        }
+        let (pattern, max_length) = self.article_id.map_or((None, None), |rule| (rule.pattern, rule.max_length));
+        insert("article_id.pattern".to_string(), pattern);
+        if let Some(git) = self.git {
+            insert("git.branch_naming".to_string(), git.branch_naming);
+            insert("git.commit_message".to_string(), git.commit_message);
+            insert("git.pr_title".to_string(), git.pr_title);
+            insert("git.pr_body".to_string(), git.pr_body);
+            for (name, rule) in git.actions {
+                insert(format!("git.actions.{}.pr_title", name), rule.pr_title);
+                insert(format!("git.actions.{}.pr_body", name), rule.pr_body);
+            }
+        }
+        if let Some(max_length) = max_length {
+            spans.insert("article_id.max_length".to_string(), max_length.span());
+        }
+        spans
+    }
+}
+
+/// Convert a byte offset in a text to a line and a column, both starting at 1.
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or_default().chars().count() + 1;
+    (line, column)
+}
+
+/// Validate the content of a `REPORULE` file, e.g. before pushing it to the repository.
+///
+/// The file is parsed, then checked with [`RepoRule::validate`](RepoRule::validate).
+///
+/// # Arguments
+/// - `source`: The content of the `REPORULE` file.
+///
+/// # Returns
+/// - `Vec<RuleDiagnostic>`: The [RuleDiagnostic](RuleDiagnostic)s, with the line and column of the problem where possible,
+///   sorted by position. A file that is not a valid repository rule gives a single error, with the position reported by the TOML parser.
+///   Empty if no problem has been found.
+pub fn validate_repo_rule(source: &str) -> Vec<RuleDiagnostic> {
+    let rule: RepoRule = match toml::from_str(source) {
+        Ok(rule) => rule,
+        Err(e) => {
+            let mut diagnostic = RuleDiagnostic::error("", e.message().trim());
+            if let Some(span) = e.span() {
+                let (line, column) = line_column(source, span.start);
+                diagnostic.line = Some(line);
+                diagnostic.column = Some(column);
+            }
+            return vec![diagnostic];
+        }
+    };
+
+    let spans = toml::from_str::<SpannedRule>(source).unwrap_or_default().spans();
+    let mut diagnostics = rule.validate();
+    for diagnostic in &mut diagnostics {
+        if let Some(span) = spans.get(&diagnostic.field) {
+            let (line, column) = line_column(source, span.start);
+            diagnostic.line = Some(line);
+            diagnostic.column = Some(column);
+        }
+    }
+    diagnostics.sort_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+    diagnostics
+}
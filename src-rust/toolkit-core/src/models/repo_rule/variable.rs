@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use regex::Regex;
+
+use crate::error::{Error, Result};
+
+/// The type of a [Variable](Variable), defining which values are valid and how they are normalized.
+///
+/// - `Text`: Any text. The default type.
+/// - `Date`: A calendar date, written `YYYY-MM-DD` or `YYYYMMDD`. e.g. `2024-07-15`.
+/// - `Url`: An HTTP or HTTPS URL. e.g. `https://example.com/news/hello`.
+/// - `List`: A comma-separated list, normalized to items separated by `, `. e.g. `linux, open source`.
+/// - `Enum`: One of the `options` of the variable.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VariableType {
+    #[default]
+    Text,
+    Date,
+    Url,
+    List,
+    Enum,
+}
+
+/// A representation of a variable declaration in the FOSScope repository rule, describing a value the contributor has to give,
+/// so the CLI and the UI can prompt for it and validate it before the templates are rendered.
+///
+/// # Fields
+/// - `name`: The name of the variable, used as `{{name}}` in the templates. e.g. `cover_image`.
+/// - `prompt`: The text shown to the contributor when asking for the value. e.g. `请输入封面图片的 URL`.
+/// - `type`: The [VariableType](VariableType) of the value. `text` if not provided.
+/// - `default`: The value to use when the contributor gives none.
+/// - `pattern`: A regular expression the whole value has to match. e.g. `^[a-z0-9-]+$`.
+/// - `options`: The valid values of an `enum` variable.
+/// - `actions`: The names of the [Action](super::Action)s that ask for the variable. e.g. `["select"]`.
+///   Every action asks for it if not provided.
+/// - `required`: Whether a value has to be given (or defaulted). `true` if not provided.
+///
+/// Check the [related design documentation](https://github.com/FOSScope/Toolkit/blob/main/docs/dev/design/repo-rule.md)
+/// and [RepoRule](super::RepoRule) definition for more information.
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Variable {
+    /// The name of the variable.
+    pub name: String,
+    /// The text shown to the contributor when asking for the value.
+    pub prompt: String,
+    /// The type of the value.
+    #[serde(rename = "type", default)]
+    pub r#type: VariableType,
+    /// The value to use when the contributor gives none.
+    #[serde(default)]
+    pub default: Option<String>,
+    /// A regular expression the whole value has to match.
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// The valid values of an `enum` variable.
+    #[serde(default)]
+    pub options: Vec<String>,
+    /// The names of the actions that ask for the variable, every action if empty.
+    #[serde(default)]
+    pub actions: Vec<String>,
+    /// Whether a value has to be given.
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+/// Check that a date is written `YYYY-MM-DD` or `YYYYMMDD`, and exists in the calendar.
+///
+/// The dates are parsed the same way as by the `date` helper of the templates.
+fn is_date(value: &str) -> bool {
+    // chrono also accepts months and days without their leading zero, e.g. `2024-7-15`, so the length is checked as well.
+    match value.len() {
+        10 => NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+        8 => NaiveDate::parse_from_str(value, "%Y%m%d").is_ok(),
+        _ => false,
+    }
+}
+
+impl Variable {
+    pub fn new(name: String, prompt: String, r#type: VariableType) -> Self {
+        Self {
+            name,
+            prompt,
+            r#type,
+            default: None,
+            pattern: None,
+            options: Vec::new(),
+            actions: Vec::new(),
+            required: true,
+        }
+    }
+
+    /// Whether an action asks for the variable.
+    ///
+    /// # Arguments
+    /// - `action_name`: The name of the action. e.g. `select`.
+    pub fn is_asked_by(&self, action_name: &str) -> bool {
+        self.actions.is_empty() || self.actions.iter().any(|action| action == action_name)
+    }
+
+    /// Validate and normalize a value given by the contributor.
+    ///
+    /// The value is trimmed, and replaced by the `default` of the variable if empty.
+    ///
+    /// # Arguments
+    /// - `input`: The value given by the contributor.
+    ///
+    /// # Returns
+    /// - `Result<String>`
+    ///     - `Ok(String)`: The normalized value. Empty if the variable is not required and no value has been given.
+    ///     - `Err(Error)`: [`Error::InvalidInput`](crate::Error::InvalidInput) describing why the value is not valid for the variable.
+    pub fn parse(&self, input: &str) -> Result<String> {
+        let mut value = input.trim().to_string();
+        if value.is_empty() {
+            value = self.default.clone().unwrap_or_default();
+        }
+        if value.is_empty() {
+            return if self.required {
+                Err(Error::InvalidInput(format!("`{}` is required", self.name)))
+            } else {
+                Ok(value)
+            };
+        }
+
+        match self.r#type {
+            VariableType::Text => {}
+            VariableType::Date => {
+                if !is_date(&value) {
+                    return Err(Error::InvalidInput(format!(
+                        "`{}` must be a date written YYYY-MM-DD or YYYYMMDD, not `{}`", self.name, value
+                    )));
+                }
+            }
+            VariableType::Url => {
+                let valid = url::Url::parse(&value).is_ok_and(|url| matches!(url.scheme(), "http" | "https"));
+                if !valid {
+                    return Err(Error::InvalidInput(format!("`{}` must be an HTTP(S) URL, not `{}`", self.name, value)));
+                }
+            }
+            VariableType::List => {
+                value = value.split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+            }
+            VariableType::Enum => {
+                if !self.options.contains(&value) {
+                    return Err(Error::InvalidInput(format!(
+                        "`{}` must be one of {}, not `{}`", self.name, self.options.join(", "), value
+                    )));
+                }
+            }
+        }
+
+        if let Some(pattern) = &self.pattern {
+            let regex = Regex::new(&format!("^(?:{})$", pattern))
+                .map_err(|e| Error::parse(format!("the pattern of the variable {}", self.name), e))?;
+            if !regex.is_match(&value) {
+                return Err(Error::InvalidInput(format!("`{}` must match `{}`, not `{}`", self.name, pattern, value)));
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// Check the value of the variable among the variables given to an action.
+    ///
+    /// # Returns
+    /// - `Result<()>`: `Ok` if the value is valid, or missing but not required or defaulted (the default is applied when asking for it),
+    ///   [`Error::InvalidInput`](crate::Error::InvalidInput) otherwise.
+    pub(super) fn check(&self, vars: &HashMap<&str, String>) -> Result<()> {
+        match vars.get(self.name.as_str()) {
+            Some(value) => self.parse(value).map(|_| ()),
+            None if self.required && self.default.is_none() => {
+                Err(Error::InvalidInput(format!("`{}` is required", self.name)))
+            }
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::{gitea_api_responses, github_api_responses::git_data};
+
+/// The kind of object an entry of a [RepoTree](RepoTree) points to.
+///
+/// # Variants
+/// - `Blob`: A file.
+/// - `Tree`: A directory.
+/// - `Commit`: A submodule.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TreeEntryKind {
+    /// A file.
+    Blob,
+    /// A directory.
+    Tree,
+    /// A submodule.
+    Commit,
+}
+
+/// An entry of a [RepoTree](RepoTree), i.e. a file, a directory or a submodule of the repository.
+///
+/// The content of a file is not included, it can be fetched on demand with
+/// [`GitHubApi::get_blob`](crate::apis::GitHubApi::get_blob) using the `sha` of the entry.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct TreeEntry {
+    /// The path of the entry, relative to the root of the repository.
+    pub path: String,
+    /// The file mode of the entry, e.g. `100644` for a regular file.
+    pub mode: String,
+    /// The kind of object the entry points to.
+    pub kind: TreeEntryKind,
+    /// The SHA of the object the entry points to.
+    pub sha: String,
+    /// The size of the file in bytes. Only available for files.
+    pub size: Option<u64>,
+}
+
+impl TreeEntry {
+    /// Whether the entry is a file.
+    pub fn is_file(&self) -> bool {
+        self.kind == TreeEntryKind::Blob
+    }
+}
+
+impl TreeEntryKind {
+    /// Get the kind of the object from its Git type, e.g. `tree`.
+    fn from_type(r#type: &str) -> Self {
+        match r#type {
+            "tree" => TreeEntryKind::Tree,
+            "commit" => TreeEntryKind::Commit,
+            _ => TreeEntryKind::Blob,
+        }
+    }
+}
+
+impl From<git_data::TreeEntry> for TreeEntry {
+    fn from(entry: git_data::TreeEntry) -> Self {
+        Self {
+            kind: TreeEntryKind::from_type(&entry.r#type),
+            path: entry.path,
+            mode: entry.mode,
+            sha: entry.sha,
+            size: entry.size,
+        }
+    }
+}
+
+impl From<gitea_api_responses::git_data::TreeEntry> for TreeEntry {
+    fn from(entry: gitea_api_responses::git_data::TreeEntry) -> Self {
+        Self {
+            kind: TreeEntryKind::from_type(&entry.r#type),
+            path: entry.path,
+            mode: entry.mode,
+            sha: entry.sha,
+            size: entry.size,
+        }
+    }
+}
+
+/// A listing of the files and directories of a repository at a given reference, without the content of the files.
+///
+/// # Fields
+/// - `sha`: The SHA of the root tree.
+/// - `entries`: The entries of the tree. Listed recursively if the tree was requested recursively.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct RepoTree {
+    /// The SHA of the root tree.
+    pub sha: String,
+    /// The entries of the tree.
+    pub entries: Vec<TreeEntry>,
+}
+
+impl RepoTree {
+    pub fn new(sha: String, entries: Vec<TreeEntry>) -> Self {
+        Self { sha, entries }
+    }
+
+    /// Get the entry at the given path, if there is one.
+    pub fn get(&self, path: &str) -> Option<&TreeEntry> {
+        self.entries.iter().find(|entry| entry.path == path)
+    }
+
+    /// Iterate over every file of the tree.
+    pub fn files(&self) -> impl Iterator<Item = &TreeEntry> {
+        self.entries.iter().filter(|entry| entry.is_file())
+    }
+
+    /// Iterate over every file under a directory of the tree, e.g. every article under `sources/`.
+    ///
+    /// # Arguments
+    /// - `dir`: The path of the directory, with or without a trailing slash.
+    pub fn files_in<'a>(&'a self, dir: &str) -> impl Iterator<Item = &'a TreeEntry> {
+        let prefix = format!("{}/", dir.trim_matches('/'));
+        self.files().filter(move |entry| entry.path.starts_with(&prefix))
+    }
+}
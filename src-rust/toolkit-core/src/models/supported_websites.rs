@@ -1,3 +1,5 @@
+use crate::error::{Error, Result};
+
 /// An enum representing all the websites supported by the toolkit.
 /// Each supported variant contains two strings:
 /// - The host part of the URL.
@@ -32,17 +34,17 @@ impl SupportedWebsites {
     ///
     /// # Returns
     /// - `SupportedWebsites`: The website supported by the toolkit.
-    pub fn get_website(url: &str) -> Result<SupportedWebsites, String> {
+    pub fn get_website(url: &str) -> Result<SupportedWebsites> {
         let website = url::Url::parse(url);
         let website = match website {
             Ok(website) => website,
-            Err(_) => {
-                return Err("Failed to parse the URL.".to_string());
+            Err(e) => {
+                return Err(Error::parse(format!("the URL {:?}", url), e));
             }
         };
         let host = website.host_str();
         let host = if host.is_none() {
-            return Err("Failed to get the host of the URL.".to_string());
+            return Err(Error::InvalidInput(format!("The URL {:?} has no host.", url)));
         } else {
             host.unwrap()
         };
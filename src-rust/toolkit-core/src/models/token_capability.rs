@@ -0,0 +1,91 @@
+/// A capability that the token of the user needs for the workflows of the toolkit.
+///
+/// # Variants
+/// - `CreateFork`: Fork the upstream repository.
+/// - `WriteContents`: Create branches and commit files to the fork.
+/// - `WritePullRequests`: Open, update and close pull requests to the upstream repository.
+/// - `Workflow`: Update GitHub Actions workflow files, which happens when the fork is synchronized with the upstream repository
+///   and the upstream repository changed its workflows. Not [required](TokenCapability::is_required), as most synchronizations
+///   do not need it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub enum TokenCapability {
+    CreateFork,
+    WriteContents,
+    WritePullRequests,
+    Workflow,
+}
+
+impl TokenCapability {
+    /// Every capability needed by the toolkit.
+    pub const ALL: [TokenCapability; 4] = [
+        TokenCapability::CreateFork,
+        TokenCapability::WriteContents,
+        TokenCapability::WritePullRequests,
+        TokenCapability::Workflow,
+    ];
+
+    /// Whether the toolkit cannot work without the capability, so a token lacking it is refused at login.
+    pub fn is_required(&self) -> bool {
+        !matches!(self, TokenCapability::Workflow)
+    }
+
+    /// The OAuth scopes of a classic token granting the capability, any one of them is enough.
+    pub fn scopes(&self) -> &'static [&'static str] {
+        match self {
+            TokenCapability::CreateFork
+            | TokenCapability::WriteContents
+            | TokenCapability::WritePullRequests => &["repo", "public_repo"],
+            TokenCapability::Workflow => &["workflow"],
+        }
+    }
+
+    /// Get the capabilities that are not granted by the given OAuth scopes.
+    pub fn missing_from(scopes: &[String]) -> Vec<TokenCapability> {
+        Self::ALL.into_iter()
+            .filter(|capability| !capability.scopes().iter().any(|scope| scopes.iter().any(|s| s == scope)))
+            .collect()
+    }
+}
+
+/// The capabilities of a token, as far as they can be known before they are used.
+///
+/// # Variants
+/// - `Missing`: The token is a classic token, whose OAuth scopes lack the listed capabilities. Empty if it has them all.
+/// - `Unknown`: The token is a fine-grained token, whose permissions are granted per repository and are not exposed by GitHub,
+///   so a missing permission is only reported when a request needing it fails.
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub enum TokenCapabilities {
+    Missing(Vec<TokenCapability>),
+    Unknown,
+}
+
+impl TokenCapabilities {
+    /// Get the [required](TokenCapability::is_required) capabilities that the token is known to lack.
+    pub fn missing_required(&self) -> Vec<TokenCapability> {
+        match self {
+            TokenCapabilities::Missing(missing) => missing.iter().copied().filter(TokenCapability::is_required).collect(),
+            TokenCapabilities::Unknown => Vec::new(),
+        }
+    }
+
+    /// Get the capabilities that the token is known to lack but that are not required, to be reported as warnings.
+    pub fn missing_optional(&self) -> Vec<TokenCapability> {
+        match self {
+            TokenCapabilities::Missing(missing) => missing.iter().copied().filter(|capability| !capability.is_required()).collect(),
+            TokenCapabilities::Unknown => Vec::new(),
+        }
+    }
+}
+
+impl std::fmt::Display for TokenCapability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            TokenCapability::CreateFork => "create forks",
+            TokenCapability::WriteContents => "write repository contents",
+            TokenCapability::WritePullRequests => "write pull requests",
+            TokenCapability::Workflow => "update workflows",
+        };
+        let scopes: Vec<String> = self.scopes().iter().map(|scope| format!("`{}`", scope)).collect();
+        write!(f, "{} (scope {})", description, scopes.join(" or "))
+    }
+}
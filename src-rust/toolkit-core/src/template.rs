@@ -0,0 +1,233 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, NaiveDate};
+use handlebars::{Context, Handlebars, Helper, HelperResult, JsonValue, Output, RenderContext, RenderErrorReason};
+
+use crate::error::{Error, Result};
+use crate::models::RepoRule;
+use crate::models::repo_rule::Transliteration;
+use crate::utils::slug;
+
+/// The format of the dates rendered by the `date` helper if none is given.
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Get a value of a template as text, with the items of an array separated by `, ` and a missing value empty.
+fn value_text(value: &JsonValue) -> String {
+    match value {
+        JsonValue::Null => String::new(),
+        JsonValue::String(text) => text.clone(),
+        JsonValue::Array(items) => items.iter().map(value_text).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
+
+/// Get a parameter of a helper as text, `None` if it is not given.
+fn param_text(h: &Helper, index: usize) -> Option<String> {
+    h.param(index).map(|param| value_text(param.value()))
+}
+
+/// Get the first parameter of a helper as text, failing if it is not given.
+fn required_param_text(h: &Helper, helper_name: &'static str) -> Result<String, RenderErrorReason> {
+    param_text(h, 0).ok_or(RenderErrorReason::ParamNotFoundForIndex(helper_name, 0))
+}
+
+/// `{{slugify text}}` or `{{slugify text "pinyin"}}`: The text as a slug, e.g. `hello-world` for `Hello, World!`.
+fn slugify_helper(h: &Helper, _: &Handlebars, _: &Context, _: &mut RenderContext, out: &mut dyn Output) -> HelperResult {
+    let text = required_param_text(h, "slugify")?;
+    let transliteration = match param_text(h, 1).as_deref() {
+        None | Some("none") => Transliteration::None,
+        Some("pinyin") => Transliteration::Pinyin,
+        Some(other) => {
+            return Err(RenderErrorReason::Other(format!("unknown transliteration `{}` given to slugify", other)).into());
+        }
+    };
+    out.write(&slug::slugify(&text, transliteration))?;
+    Ok(())
+}
+
+/// `{{date value "%Y/%m/%d"}}`: The date written `YYYY-MM-DD`, `YYYYMMDD` or in RFC 3339 in another format,
+/// `%Y-%m-%d` if not given. Today is used if the value is empty, e.g. `{{date ""}}`.
+fn date_helper(h: &Helper, _: &Handlebars, _: &Context, _: &mut RenderContext, out: &mut dyn Output) -> HelperResult {
+    let value = required_param_text(h, "date")?;
+    let value = value.trim();
+    let date = if value.is_empty() {
+        Local::now().date_naive()
+    } else {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .or_else(|_| NaiveDate::parse_from_str(value, "%Y%m%d"))
+            .or_else(|_| DateTime::parse_from_rfc3339(value).map(|date| date.date_naive()))
+            .map_err(|_| RenderErrorReason::Other(format!("`{}` is not a date", value)))?
+    };
+
+    let format = param_text(h, 1).unwrap_or_else(|| DEFAULT_DATE_FORMAT.to_string());
+    let items: Vec<Item> = StrftimeItems::new(&format).collect();
+    if items.contains(&Item::Error) {
+        return Err(RenderErrorReason::Other(format!("`{}` is not a valid date format", format)).into());
+    }
+    out.write(&date.format_with_items(items.into_iter()).to_string())?;
+    Ok(())
+}
+
+/// `{{lowercase text}}`: The text in lowercase.
+fn lowercase_helper(h: &Helper, _: &Handlebars, _: &Context, _: &mut RenderContext, out: &mut dyn Output) -> HelperResult {
+    out.write(&required_param_text(h, "lowercase")?.to_lowercase())?;
+    Ok(())
+}
+
+/// `{{join list " / "}}`: The items of a list, or of a comma-separated text like the `list` variables, joined with a separator,
+/// `, ` if not given.
+fn join_helper(h: &Helper, _: &Handlebars, _: &Context, _: &mut RenderContext, out: &mut dyn Output) -> HelperResult {
+    let items: Vec<String> = match h.param(0).map(|param| param.value()) {
+        Some(JsonValue::Array(items)) => items.iter().map(value_text).collect(),
+        Some(value) => value_text(value).split(',').map(|item| item.trim().to_string()).collect(),
+        None => return Err(RenderErrorReason::ParamNotFoundForIndex("join", 0).into()),
+    };
+    let separator = param_text(h, 1).unwrap_or_else(|| ", ".to_string());
+    let items: Vec<&str> = items.iter().map(String::as_str).filter(|item| !item.is_empty()).collect();
+    out.write(&items.join(&separator))?;
+    Ok(())
+}
+
+/// `{{default value "fallback"}}`: The value, or the fallback if the value is missing or blank.
+fn default_helper(h: &Helper, _: &Handlebars, _: &Context, _: &mut RenderContext, out: &mut dyn Output) -> HelperResult {
+    let value = param_text(h, 0).unwrap_or_default();
+    if value.trim().is_empty() {
+        out.write(&param_text(h, 1).unwrap_or_default())?;
+    } else {
+        out.write(&value)?;
+    }
+    Ok(())
+}
+
+/// `helperMissing`: The placeholder itself, so it can be filled in by hand later, e.g. `{{cover_image}}`.
+fn keep_placeholder_helper(h: &Helper, _: &Handlebars, _: &Context, _: &mut RenderContext, out: &mut dyn Output) -> HelperResult {
+    out.write(&format!("{{{{{}}}}}", h.name()))?;
+    Ok(())
+}
+
+/// Check that a path stays inside the repository.
+///
+/// # Returns
+/// - `Option<&str>`: Why the path is unsafe, `None` if it is safe.
+pub(crate) fn unsafe_path(path: &str) -> Option<&'static str> {
+    if path.starts_with('/') || path.starts_with('\\') {
+        return Some("it is an absolute path");
+    }
+    if path.contains('\\') {
+        return Some("it contains a backslash");
+    }
+    if path.len() >= 2 && path.as_bytes()[1] == b':' && path.as_bytes()[0].is_ascii_alphabetic() {
+        return Some("it starts with a drive letter");
+    }
+    for segment in path.split('/') {
+        match segment {
+            ".." => return Some("it leaves the repository with `..`"),
+            ".git" => return Some("it points into the `.git` directory"),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The renderer of the Handlebars templates of the repository rules, used by every workflow so the templates
+/// behave the same everywhere.
+///
+/// - The values are not HTML-escaped, as the templates render Markdown, file names, branch names and commit messages,
+///   e.g. `Rust & Linux` stays `Rust & Linux`.
+/// - The helpers `slugify`, `date`, `lowercase`, `join` and `default` are available, e.g. `{{date publishing_date "%Y/%m/%d"}}`.
+/// - The `partials` of the repository rule are available, e.g. `{{> footer}}`.
+#[derive(Clone, Debug)]
+pub struct TemplateRenderer {
+    handlebars: Handlebars<'static>,
+}
+
+impl Default for TemplateRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateRenderer {
+    /// Create a renderer with the helpers, but without any partial.
+    pub fn new() -> Self {
+        let mut handlebars = Handlebars::new();
+        handlebars.register_escape_fn(handlebars::no_escape);
+        handlebars.register_helper("slugify", Box::new(slugify_helper));
+        handlebars.register_helper("date", Box::new(date_helper));
+        handlebars.register_helper("lowercase", Box::new(lowercase_helper));
+        handlebars.register_helper("join", Box::new(join_helper));
+        handlebars.register_helper("default", Box::new(default_helper));
+        Self { handlebars }
+    }
+
+    /// Create a renderer with the helpers and the partials of a repository rule.
+    ///
+    /// # Returns
+    /// - `Result<TemplateRenderer>`: The renderer, or a [`Parse`](crate::Error::Parse) error if a partial does not parse.
+    pub fn for_rule(repo_rule: &RepoRule) -> Result<Self> {
+        let mut renderer = Self::new();
+        for (name, template) in &repo_rule.partials {
+            renderer.register_partial(name, template)?;
+        }
+        Ok(renderer)
+    }
+
+    /// Make the placeholders whose value is not given render as themselves instead of nothing,
+    /// e.g. `{{cover_image}}` in an article template, for the contributor to fill in by hand.
+    pub fn keep_missing_placeholders(mut self) -> Self {
+        self.handlebars.register_helper("helperMissing", Box::new(keep_placeholder_helper));
+        self
+    }
+
+    /// Register a partial, to be included in the templates with `{{> name}}`.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the partial. e.g. `footer`.
+    /// - `template`: The template of the partial.
+    ///
+    /// # Returns
+    /// - `Result<()>`: `Ok` if the partial is registered, or a [`Parse`](crate::Error::Parse) error if it does not parse.
+    pub fn register_partial(&mut self, name: &str, template: &str) -> Result<()> {
+        self.handlebars.register_partial(name, template)
+            .map_err(|e| Error::parse(format!("the partial {}", name), e))
+    }
+
+    /// Render a template.
+    ///
+    /// # Arguments
+    /// - `name`: What the template is, used in the errors. e.g. `git.commit_message`.
+    /// - `template`: The template. e.g. `[{{action_desc}}] {{article_title}}`.
+    /// - `vars`: The values of the placeholders.
+    ///
+    /// # Returns
+    /// - `Result<String>`
+    ///     - `Ok(String)`: The rendered template.
+    ///     - `Err(Error)`: A [`TemplateRender`](crate::Error::TemplateRender) error naming the template
+    ///       if it does not parse, or a helper fails, e.g. `date` is given something that is not a date.
+    pub fn render(&self, name: &str, template: &str, vars: &impl serde::Serialize) -> Result<String> {
+        self.handlebars.render_template(template, vars)
+            .map_err(|e| Error::TemplateRender { template: name.to_string(), source: Box::new(e) })
+    }
+
+    /// Render a template of a path in the repository, e.g. the directory of an article type.
+    ///
+    /// The rendered path is trimmed of whitespace and of slashes at its ends.
+    ///
+    /// # Returns
+    /// - `Result<String>`
+    ///     - `Ok(String)`: The rendered path. e.g. `sources/news`.
+    ///     - `Err(Error)`: An error indicating why the template could not be rendered (see [`render`](TemplateRenderer::render)),
+    ///       or [`Error::InvalidInput`](crate::Error::InvalidInput) if the path is empty or leaves the repository,
+    ///       e.g. `../news` or `/news`.
+    pub fn render_path(&self, name: &str, template: &str, vars: &impl serde::Serialize) -> Result<String> {
+        let rendered = self.render(name, template, vars)?;
+        let rendered = rendered.trim();
+        if let Some(reason) = unsafe_path(rendered) {
+            return Err(Error::InvalidInput(format!("the path `{}` rendered from {} is unsafe, as {}", rendered, name, reason)));
+        }
+        let path = rendered.trim_matches('/');
+        if path.is_empty() {
+            return Err(Error::InvalidInput(format!("the path rendered from {} is empty", name)));
+        }
+        Ok(path.to_string())
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::models::repo_rule::Transliteration;
+
+/// The pinyin of the common Chinese characters, read from `resources/pinyin.txt`.
+fn pinyin_table() -> &'static HashMap<char, &'static str> {
+    static TABLE: OnceLock<HashMap<char, &'static str>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        include_str!("../../resources/pinyin.txt").lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| line.split_once(' '))
+            .flat_map(|(syllable, characters)| characters.chars().map(move |c| (c, syllable)))
+            .collect()
+    })
+}
+
+/// Turn a text, usually the title of an article, into a slug that can be used in file and branch names.
+///
+/// The slug is in lowercase, with the words separated by a single dash (`-`). Apostrophes are removed,
+/// so `Don't` becomes `dont`, and every other punctuation or whitespace separates words.
+///
+/// # Arguments
+/// - `text`: The text to turn into a slug. e.g. `Hello, World!`, `开源软件的未来`.
+/// - `transliteration`: How the characters outside of ASCII are handled.
+///   - [`Transliteration::None`](Transliteration::None): They are kept if they are letters or digits.
+///   - [`Transliteration::Pinyin`](Transliteration::Pinyin): The common Chinese characters are replaced by their pinyin,
+///     one word per character, and the others are removed.
+///
+/// # Returns
+/// - `String`: The slug. e.g. `hello-world`, `kai-yuan-ruan-jian-de-wei-lai`. Empty if the text has no letter or digit.
+pub fn slugify(text: &str, transliteration: Transliteration) -> String {
+    let mut words: Vec<String> = Vec::new();
+    // Whether the next letter or digit starts a new word.
+    let mut separate = true;
+    let mut push = |word: &str, separate: &mut bool| {
+        match words.last_mut() {
+            Some(last) if !*separate => last.push_str(word),
+            _ => words.push(word.to_string()),
+        }
+        *separate = false;
+    };
+
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            push(&c.to_ascii_lowercase().to_string(), &mut separate);
+        } else if matches!(c, '\'' | '’') {
+            continue;
+        } else if transliteration == Transliteration::Pinyin {
+            // Every Chinese character is a word of its own.
+            separate = true;
+            if let Some(syllable) = pinyin_table().get(&c) {
+                push(syllable, &mut separate);
+                separate = true;
+            }
+        } else if c.is_alphanumeric() {
+            push(&c.to_lowercase().to_string(), &mut separate);
+        } else {
+            separate = true;
+        }
+    }
+    words.join("-")
+}
+
+/// Shorten a slug to at most `max_length` characters, cutting between words where possible.
+///
+/// # Returns
+/// - `&str`: The slug if it is short enough, otherwise its longest prefix of whole words that fits,
+///   or the first `max_length` characters if even its first word does not fit.
+pub fn truncate(slug: &str, max_length: usize) -> &str {
+    let Some((end, _)) = slug.char_indices().nth(max_length) else {
+        return slug;
+    };
+    if slug[end..].starts_with('-') {
+        return &slug[..end];
+    }
+    match slug[..end].rfind('-') {
+        Some(dash) => &slug[..dash],
+        None => &slug[..end],
+    }
+}
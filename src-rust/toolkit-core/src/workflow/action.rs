@@ -0,0 +1,363 @@
+use std::collections::HashMap;
+
+use crate::apis::{Forge, LocalGitRepo};
+use crate::error::{Error, Result};
+use crate::models::{FileChange, GitHubRepo, PullRequest, PullRequestState, RepoRule};
+use crate::models::repo_rule::{Action, Article};
+use crate::template::TemplateRenderer;
+
+/// An article waiting in the source step of an action, e.g. a selected article in `sources/news` waiting to be translated.
+///
+/// # Fields
+/// - `article_type`: The type of the article. e.g. `news`.
+/// - `file_name`: The file name of the article. e.g. `20240715-hello.md`.
+/// - `path`: The path of the article. e.g. `sources/news/20240715-hello.md`.
+/// - `sha`: The SHA of the blob of the article, to read its content with [`Forge::get_blob`](Forge::get_blob).
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct StepArticle {
+    pub article_type: String,
+    pub file_name: String,
+    pub path: String,
+    pub sha: String,
+}
+
+/// The rendered branch name, commit message and paths of an article an action is performed on.
+struct RenderedCommit {
+    branch_name: String,
+    commit_message: String,
+    /// The path the article is committed to.
+    path: String,
+    /// The path the article is moved from, `None` unless the action moves the article.
+    source_path: Option<String>,
+}
+
+/// Render the directory of an article type for a step, failing if it leaves the repository.
+pub(crate) fn step_directory(
+    renderer: &TemplateRenderer,
+    article_type: &Article,
+    step: &str,
+    vars: &HashMap<&str, String>,
+) -> Result<String> {
+    let mut local_vars = vars.clone();
+    local_vars.insert("step", step.to_string());
+    renderer.render_path(&format!("directory of {}", article_type.r#type), &article_type.directory, &local_vars)
+}
+
+/// Render the branch name, the commit message and the paths of the article from the action and the repository rule.
+fn render_commit(
+    action: &Action,
+    file_name: &str,
+    article_type: &Article,
+    repo_rule: &RepoRule,
+    vars: &HashMap<&str, String>,
+) -> Result<RenderedCommit> {
+    action.check_vars(vars)?;
+    repo_rule.check_variables(&action.name, vars)?;
+    let mut local_vars = action.vars(vars);
+    local_vars.insert("type_name", article_type.r#type.clone());
+    local_vars.insert("type_desc", article_type.description.clone());
+
+    let renderer = TemplateRenderer::for_rule(repo_rule)?;
+    let branch_name = renderer.render("branch_naming", action.branch_naming(&repo_rule.git), &local_vars)?;
+    let commit_message = renderer.render("commit_message", action.commit_message(&repo_rule.git), &local_vars)?;
+    let path = format!(
+        "{}/{}",
+        step_directory(&renderer, article_type, &action.target_step, &local_vars)?,
+        file_name,
+    );
+    let source_path = match &action.source_step {
+        Some(source_step) if action.moves_article() => Some(format!(
+            "{}/{}",
+            step_directory(&renderer, article_type, source_step, &local_vars)?,
+            file_name,
+        )),
+        _ => None,
+    };
+    Ok(RenderedCommit { branch_name, commit_message, path, source_path })
+}
+
+/// Create or update the article on a branch, unless the branch already contains the same content.
+///
+/// If the article is not on the branch yet and the action moves it, it is removed from its source step in the same commit.
+async fn commit_article(
+    forge: &impl Forge,
+    contributor_repo: &GitHubRepo,
+    rendered: &RenderedCommit,
+    content: &str,
+) -> Result<()> {
+    let branch_name = &rendered.branch_name;
+    // Update the article if it has been committed to the branch before, otherwise create it.
+    match forge.get_file_sha(contributor_repo, branch_name, &rendered.path).await {
+        Ok(sha) => {
+            // A previous attempt may have committed the article already.
+            let unchanged = forge.get_blob(contributor_repo, &sha).await
+                .is_ok_and(|existing| existing == content.as_bytes());
+            if !unchanged {
+                forge.update_file(
+                    contributor_repo, branch_name, &rendered.path, content, &sha, &rendered.commit_message
+                ).await?;
+            }
+        }
+        Err(Error::NotFound(_)) => {
+            // The article is moved, unless it is not in the source step (any more).
+            let source_path = match &rendered.source_path {
+                Some(source_path) => match forge.get_file_sha(contributor_repo, branch_name, source_path).await {
+                    Ok(_) => Some(source_path),
+                    Err(Error::NotFound(_)) => None,
+                    Err(e) => return Err(e),
+                },
+                None => None,
+            };
+            match source_path {
+                Some(source_path) => {
+                    forge.commit_files(
+                        contributor_repo,
+                        branch_name,
+                        &[FileChange::delete(source_path), FileChange::write(&rendered.path, content)],
+                        &rendered.commit_message,
+                    ).await?;
+                }
+                None => {
+                    forge.create_file(
+                        contributor_repo, branch_name, &rendered.path, content, &rendered.commit_message
+                    ).await?;
+                }
+            }
+        }
+        Err(e) => return Err(e),
+    }
+
+    Ok(())
+}
+
+/// Commit an article to a branch in the contributor repository, performing an action of the repository rule.
+///
+/// The article is committed to the directory of the `target_step` of the action. If the action moves the article
+/// (e.g. `translate` moves it from `sources` to `translated`), it is removed from the directory of the `source_step`
+/// in the same commit.
+///
+/// The branch is created from the current head of the base branch (`git.base` in the [`RepoRule`](RepoRule))
+/// of the upstream repository, so that the contribution does not carry any stale history of the fork.
+///
+/// Committing is safe to retry:
+/// - If the branch already exists (e.g. the article has been submitted before and the pull request is still open,
+///   or a previous attempt failed after creating it), the article is committed to it again, updating the existing file,
+///   so follow-up revisions end up in the same pull request. Nothing is committed if the branch already contains the article.
+/// - If the article cannot be committed to a branch created by this call, the branch is deleted again,
+///   so no empty branch is left behind.
+///
+/// # Arguments
+/// - `forge`: The forge hosting the repositories.
+/// - `upstream_repo`: The upstream repository the base branch is taken from.
+/// - `contributor_repo`: The contributor repository (usually a fork of `upstream_repo`) to commit to.
+/// - `action`: The [Action](Action) to perform.
+/// - `file_name`: The file name of the article. e.g. `20240715-hello.md`.
+/// - `content`: The content of the article.
+/// - `article_type`: The type of the article.
+/// - `repo_rule`: The rule of the upstream repository.
+/// - `vars`: The variables to render the templates with, including every `required_vars` of the action.
+///
+/// # Returns
+/// - `Result<()>`
+///     - `Ok(())`: The article has been committed.
+///     - `Err(Error)`: [`Error::InvalidInput`](Error::InvalidInput) if a required variable of the action is missing,
+///       or a variable declared in the repository rule is invalid, or an error indicating why the article could not be committed.
+#[allow(clippy::too_many_arguments)]
+pub async fn commit(
+    forge: &impl Forge,
+    upstream_repo: &GitHubRepo,
+    contributor_repo: &GitHubRepo,
+    action: &Action,
+    file_name: &str,
+    content: &str,
+    article_type: &Article,
+    repo_rule: &RepoRule,
+    vars: &HashMap<&str, String>,
+) -> Result<()> {
+    let rendered = render_commit(action, file_name, article_type, repo_rule, vars)?;
+
+    // Only create the branch if it does not exist yet, otherwise commit to the existing branch.
+    let created_branch = match forge.get_branch_sha(contributor_repo, &rendered.branch_name).await {
+        Ok(_) => false,
+        Err(Error::NotFound(_)) => {
+            forge.create_reference_from(
+                contributor_repo, &rendered.branch_name, upstream_repo, &repo_rule.git.base,
+            ).await?;
+            true
+        }
+        Err(e) => return Err(e),
+    };
+
+    let result = commit_article(forge, contributor_repo, &rendered, content).await;
+    if result.is_err() && created_branch {
+        // Roll back the branch, the error of the commit is the one to report.
+        // A branch that cannot be deleted is reused by the next attempt instead.
+        let _ = forge.delete_branch(contributor_repo, &rendered.branch_name).await;
+    }
+    result
+}
+
+/// Commit an article in a local clone of the contributor repository performing an action of the repository rule,
+/// and push it, returning the SHA of the commit.
+///
+/// This is the counterpart of [`commit`](commit) for the [LocalGitRepo](LocalGitRepo) backend:
+/// the branch is created from the head of the base branch of the upstream repository (fetched into the `upstream` remote),
+/// or from the branch pushed to the contributor repository before if there is one, and the article is committed
+/// with the Git configuration of the user before being pushed to the `origin` remote.
+///
+/// # Arguments
+/// - `local_repo`: The local clone of the contributor repository, whose `origin` remote is the contributor repository.
+/// - `upstream_url`: The URL of the upstream repository to fetch the base branch from.
+#[allow(clippy::too_many_arguments)]
+pub async fn commit_locally(
+    local_repo: &LocalGitRepo,
+    upstream_url: &str,
+    action: &Action,
+    file_name: &str,
+    content: &str,
+    article_type: &Article,
+    repo_rule: &RepoRule,
+    vars: &HashMap<&str, String>,
+) -> Result<String> {
+    let rendered = render_commit(action, file_name, article_type, repo_rule, vars)?;
+    let branch_name = &rendered.branch_name;
+
+    local_repo.set_remote("upstream", upstream_url).await?;
+    local_repo.fetch("upstream", &repo_rule.git.base).await?;
+    // Follow-up revisions continue the branch that has been pushed before.
+    let start_point = if local_repo.remote_has_branch("origin", branch_name).await? {
+        local_repo.fetch("origin", branch_name).await?;
+        format!("origin/{}", branch_name)
+    } else {
+        format!("upstream/{}", repo_rule.git.base)
+    };
+    local_repo.checkout_branch(branch_name, &start_point).await?;
+
+    let mut changes = Vec::new();
+    if let Some(source_path) = &rendered.source_path {
+        if local_repo.path().join(source_path).is_file() && !local_repo.path().join(&rendered.path).exists() {
+            changes.push(FileChange::delete(source_path));
+        }
+    }
+    changes.push(FileChange::write(&rendered.path, content));
+    local_repo.apply_changes(&changes).await?;
+    let sha = local_repo.commit(&rendered.commit_message).await?;
+    local_repo.push("origin", branch_name).await?;
+    Ok(sha)
+}
+
+/// Create a pull request for an action performed on an article, returning the created [`PullRequest`](PullRequest)
+/// so its number and URL can be shown to the contributor.
+///
+/// The title, body, labels, reviewers, assignees and draft flag of the pull request
+/// follow the [`PullRequestRule`](crate::models::repo_rule::PullRequestRule) of the action in the repository rule.
+///
+/// If a pull request from the branch is open already (e.g. a previous attempt failed after opening it),
+/// it is returned instead of opening another one, and the labels, reviewers and assignees are added to it again.
+pub async fn create_pr(
+    forge: &impl Forge,
+    upstream_repo: &GitHubRepo,
+    contributor_repo: &GitHubRepo,
+    action: &Action,
+    repo_rule: &RepoRule,
+    vars: &HashMap<&str, String>,
+) -> Result<PullRequest> {
+    action.check_vars(vars)?;
+    repo_rule.check_variables(&action.name, vars)?;
+    let local_vars = action.vars(vars);
+
+    let rule = action.pull_request_rule(&repo_rule.git);
+
+    let renderer = TemplateRenderer::for_rule(repo_rule)?;
+    let branch_name = renderer.render("branch_naming", action.branch_naming(&repo_rule.git), &local_vars)?;
+    let pr_title = renderer.render(
+        "pr_title", rule.pr_title.as_deref().unwrap_or(action.commit_message(&repo_rule.git)), &local_vars
+    )?;
+    let pr_body = match &rule.pr_body {
+        Some(template) => renderer.render("pr_body", template, &local_vars)?,
+        None => String::new(),
+    };
+
+    let open_pull_request = forge.list_branch_pull_requests(upstream_repo, contributor_repo, &branch_name).await?
+        .into_iter()
+        .find(|pr| pr.state == PullRequestState::Open);
+    let pull_request = match open_pull_request {
+        Some(pull_request) => pull_request,
+        None => forge.create_pull_request(
+            upstream_repo,
+            contributor_repo,
+            &pr_title,
+            &pr_body,
+            &branch_name,
+            &repo_rule.git.base,
+            rule.draft.unwrap_or(false),
+        ).await?,
+    };
+
+    if let Some(labels) = rule.labels.filter(|labels| !labels.is_empty()) {
+        forge.add_labels(upstream_repo, pull_request.number, &labels).await?;
+    }
+    // GitHub does not allow requesting a review from the author of the pull request.
+    let reviewers: Vec<String> = rule.reviewers.unwrap_or_default().into_iter()
+        .filter(|reviewer| reviewer != forge.username())
+        .collect();
+    if !reviewers.is_empty() {
+        forge.request_reviewers(upstream_repo, pull_request.number, &reviewers).await?;
+    }
+    if let Some(assignees) = rule.assignees.filter(|assignees| !assignees.is_empty()) {
+        forge.add_assignees(upstream_repo, pull_request.number, &assignees).await?;
+    }
+
+    Ok(pull_request)
+}
+
+/// List the articles an action can be performed on, i.e. the articles in the directories of its `source_step`
+/// on the base branch of the upstream repository. e.g. the selected articles waiting to be translated.
+///
+/// # Arguments
+/// - `forge`: The forge hosting the upstream repository.
+/// - `upstream`: The upstream repository.
+/// - `action`: The [Action](Action) to perform.
+/// - `repo_rule`: The rule of the upstream repository, defining the directories of the articles.
+///
+/// # Returns
+/// - `Result<Vec<StepArticle>>`
+///     - `Ok(Vec<StepArticle>)`: The [StepArticle](StepArticle)s, sorted by article type and path.
+///       Empty if the action has no source step, as it creates the article.
+///     - `Err(Error)`: An error indicating why the upstream repository could not be listed.
+pub async fn list_step_articles(
+    forge: &impl Forge,
+    upstream: &GitHubRepo,
+    action: &Action,
+    repo_rule: &RepoRule,
+) -> Result<Vec<StepArticle>> {
+    let Some(source_step) = &action.source_step else {
+        return Ok(Vec::new());
+    };
+
+    let renderer = TemplateRenderer::for_rule(repo_rule)?;
+    let tree = forge.get_tree(upstream, &repo_rule.git.base, true).await?;
+    let mut articles = Vec::new();
+    for article_type in &repo_rule.articles {
+        let vars = HashMap::from([
+            ("type_name", article_type.r#type.clone()),
+            ("type_desc", article_type.description.clone()),
+        ]);
+        let directory = step_directory(&renderer, article_type, source_step, &vars)?;
+        let mut entries: Vec<StepArticle> = tree.files_in(&directory)
+            .filter_map(|entry| {
+                // Only the articles directly in the directory, not in its subdirectories.
+                let file_name = entry.path.strip_prefix(&format!("{}/", directory))?;
+                (!file_name.contains('/') && file_name.ends_with(".md")).then(|| StepArticle {
+                    article_type: article_type.r#type.clone(),
+                    file_name: file_name.to_string(),
+                    path: entry.path.clone(),
+                    sha: entry.sha.clone(),
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        articles.extend(entries);
+    }
+    Ok(articles)
+}
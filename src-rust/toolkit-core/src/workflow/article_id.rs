@@ -0,0 +1,115 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::apis::Forge;
+use crate::error::{Error, Result};
+use crate::models::{GitHubRepo, RepoRule};
+use crate::models::repo_rule::{Article, ArticleIdRule};
+use crate::template::TemplateRenderer;
+use crate::workflow::action::step_directory;
+
+/// The ID of a new article generated by [`generate`](generate).
+///
+/// # Fields
+/// - `id`: The ID of the article. e.g. `20240715-hello-world`.
+/// - `unchecked`: Why the ID could not be checked against the existing articles, e.g. the forge cannot list the files
+///   of a repository, to be reported as a warning. `None` if the ID was checked.
+#[derive(Debug)]
+pub struct GeneratedId {
+    pub id: String,
+    pub unchecked: Option<Error>,
+}
+
+/// Collect the paths of the files in the directories of an article type, on the base branch of a repository.
+///
+/// A repository or a branch that does not exist, e.g. a fork that has not been created yet, has no file.
+async fn existing_files(
+    forge: &impl Forge,
+    repo: &GitHubRepo,
+    directories: &BTreeSet<String>,
+    repo_rule: &RepoRule,
+    files: &mut HashSet<String>,
+) -> Result<()> {
+    let tree = match forge.get_tree(repo, &repo_rule.git.base, true).await {
+        Ok(tree) => tree,
+        Err(Error::NotFound(_)) => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    for directory in directories {
+        files.extend(tree.files_in(directory).map(|entry| entry.path.clone()));
+    }
+    Ok(())
+}
+
+/// Generate the ID of a new article from its title, with the [ArticleIdRule](crate::models::repo_rule::ArticleIdRule)
+/// of the repository rule, making sure no other article already uses it.
+///
+/// The ID is taken if a file named after it exists in the directory of the article type, in any step of the actions
+/// (e.g. `sources/news` and `translated/news`), on the base branch of the upstream repository or of the contributor repository.
+/// A taken ID gets a number appended, e.g. `20240715-hello-world-2`, shortening the slug if needed to stay within `max_length`.
+///
+/// If the files of a repository cannot be listed, the ID is generated without the check, so the selection can go on,
+/// and the reason is given in [`GeneratedId::unchecked`](GeneratedId::unchecked).
+///
+/// # Arguments
+/// - `forge`: The forge hosting the repositories.
+/// - `upstream`: The upstream repository.
+/// - `contributor_repo`: The repository that the user is contributing to.
+/// - `article_type`: The [Article](Article) type of the article.
+/// - `repo_rule`: The rule of the upstream repository.
+/// - `title`: The title of the article. e.g. `Hello, World!`.
+/// - `vars`: The variables used in the pattern of the ID. e.g. `publishing_date`.
+///
+/// # Returns
+/// - `Result<GeneratedId>`
+///     - `Ok(GeneratedId)`: The [GeneratedId](GeneratedId) of the article.
+///     - `Err(Error)`: An error indicating why the ID could not be generated, see [`ArticleIdRule::generate`](crate::models::repo_rule::ArticleIdRule::generate).
+#[allow(clippy::too_many_arguments)]
+pub async fn generate(
+    forge: &impl Forge,
+    upstream: &GitHubRepo,
+    contributor_repo: &GitHubRepo,
+    article_type: &Article,
+    repo_rule: &RepoRule,
+    title: &str,
+    vars: &HashMap<&str, String>,
+) -> Result<GeneratedId> {
+    let mut local_vars = vars.clone();
+    local_vars.insert("type_name", article_type.r#type.clone());
+    local_vars.insert("type_desc", article_type.description.clone());
+
+    let renderer = TemplateRenderer::for_rule(repo_rule)?;
+    let mut directories = BTreeSet::new();
+    let steps = repo_rule.actions.iter()
+        .flat_map(|action| action.source_step.iter().chain([&action.target_step]));
+    for step in steps {
+        directories.insert(step_directory(&renderer, article_type, step, &local_vars)?);
+    }
+
+    let mut files = HashSet::new();
+    let listed = match existing_files(forge, upstream, &directories, repo_rule, &mut files).await {
+        Ok(()) => existing_files(forge, contributor_repo, &directories, repo_rule, &mut files).await,
+        Err(e) => Err(e),
+    };
+    let rule = &repo_rule.article_id;
+    if let Err(e) = listed {
+        // The ID cannot be checked, but it is still generated so the selection can go on.
+        return Ok(GeneratedId { id: rule.generate(title, &local_vars)?, unchecked: Some(e) });
+    }
+    let taken = |id: &str| directories.iter().any(|directory| files.contains(&format!("{}/{}.md", directory, id)));
+
+    let id = rule.generate(title, &local_vars)?;
+    if !taken(&id) {
+        return Ok(GeneratedId { id, unchecked: None });
+    }
+    for number in 2.. {
+        let suffix = format!("-{}", number);
+        let Some(max_length) = rule.max_length.checked_sub(suffix.len()) else { break };
+        let shortened = ArticleIdRule { max_length, ..rule.clone() };
+        let Ok(base) = shortened.generate(title, &local_vars) else { break };
+        let id = format!("{}{}", base, suffix);
+        if !taken(&id) {
+            return Ok(GeneratedId { id, unchecked: None });
+        }
+    }
+    Err(Error::InvalidInput(format!("no article ID for `{}` is available within {} characters", title, rule.max_length)))
+}
@@ -0,0 +1,146 @@
+use futures::{stream, StreamExt, TryStreamExt};
+use regex::Regex;
+
+use crate::apis::Forge;
+use crate::error::{Error, Result};
+use crate::models::{Branch, GitHubRepo, PullRequest, PullRequestState, RepoRule};
+
+/// The number of requests sent concurrently when looking up the pull requests of the branches.
+const LOOKUP_CONCURRENCY: usize = 8;
+
+/// A branch of the contributor repository that a contribution has been (or is being) made from.
+///
+/// # Fields
+/// - `name`: The name of the branch. e.g. `select/news/20240715-hello`.
+/// - `sha`: The SHA of the latest commit on the branch.
+/// - `pull_request`: The pull request opened from the branch: the open one if there is one, the newest one otherwise.
+///   `None` if no pull request has been opened from the branch.
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ContributionBranch {
+    pub name: String,
+    pub sha: String,
+    pub pull_request: Option<PullRequest>,
+}
+
+impl ContributionBranch {
+    /// Whether the branch can be deleted without losing any work.
+    ///
+    /// That is the case once its pull request has been merged or closed,
+    /// and no commit has been pushed to the branch since then.
+    pub fn is_deletable(&self) -> bool {
+        self.pull_request.as_ref().is_some_and(|pr| {
+            pr.state != PullRequestState::Open && pr.head_sha == self.sha
+        })
+    }
+}
+
+/// Build a regular expression matching the names of the branches created following the branch naming of the repository rule,
+/// or of any of its actions, e.g. `{{action_name}}/{{type_name}}/{{article_id}}` matches `select/news/20240715-hello`.
+fn branch_naming_regex(repo_rule: &RepoRule) -> Result<Regex> {
+    let placeholder = Regex::new(r"\{\{\{?[^{}]*\}?\}\}").unwrap();
+    let mut namings: Vec<&str> = repo_rule.actions.iter()
+        .map(|action| action.branch_naming(&repo_rule.git).trim())
+        .collect();
+    namings.push(repo_rule.git.branch_naming.trim());
+    namings.sort_unstable();
+    namings.dedup();
+
+    let mut patterns = Vec::new();
+    for naming in namings {
+        let mut pattern = String::new();
+        let mut last = 0;
+        for placeholder in placeholder.find_iter(naming) {
+            pattern.push_str(&regex::escape(&naming[last..placeholder.start()]));
+            pattern.push_str(".+?");
+            last = placeholder.end();
+        }
+        pattern.push_str(&regex::escape(&naming[last..]));
+        patterns.push(format!("(?:{})", pattern));
+    }
+
+    Regex::new(&format!("^(?:{})$", patterns.join("|")))
+        .map_err(|e| Error::parse("the branch naming of the repository rule", e))
+}
+
+/// List the contribution branches of the contributor repository, with the pull requests opened from them.
+///
+/// The contribution branches are the branches named following `git.branch_naming` of the repository rule,
+/// or the `branch_naming` of one of its actions, apart from the base branch.
+///
+/// # Arguments
+/// - `forge`: The forge hosting the repositories.
+/// - `upstream`: The upstream repository the pull requests are opened against.
+/// - `contributor`: The contributor repository (usually a fork of `upstream`) containing the branches.
+/// - `repo_rule`: The rule of the upstream repository, defining the branch naming.
+///
+/// # Returns
+/// - `Result<Vec<ContributionBranch>>`
+///     - `Ok(Vec<ContributionBranch>)`: The [ContributionBranch](ContributionBranch)es, sorted by name.
+///     - `Err(Error)`: An error indicating why the branches or pull requests could not be listed.
+pub async fn list_contribution_branches(
+    forge: &impl Forge,
+    upstream: &GitHubRepo,
+    contributor: &GitHubRepo,
+    repo_rule: &RepoRule,
+) -> Result<Vec<ContributionBranch>> {
+    let naming = branch_naming_regex(repo_rule)?;
+    let branches: Vec<Branch> = forge.list_branches(contributor).await?
+        .into_iter()
+        .filter(|branch| branch.name != repo_rule.git.base && naming.is_match(&branch.name))
+        .collect();
+
+    stream::iter(branches)
+        .map(|branch| async move {
+            let pull_requests = forge.list_branch_pull_requests(upstream, contributor, &branch.name).await?;
+            let pull_request = match pull_requests.iter().find(|pr| pr.state == PullRequestState::Open) {
+                Some(open) => Some(open.clone()),
+                None => pull_requests.into_iter().next(),
+            };
+            Ok::<_, Error>(ContributionBranch {
+                name: branch.name,
+                sha: branch.sha,
+                pull_request,
+            })
+        })
+        .buffered(LOOKUP_CONCURRENCY)
+        .try_collect()
+        .await
+}
+
+/// Delete the contribution branches whose pull requests have been merged or closed.
+///
+/// Branches without a pull request, with an open pull request, or with commits pushed after their pull request was closed
+/// are kept, see [`ContributionBranch::is_deletable`](ContributionBranch::is_deletable).
+///
+/// # Arguments
+/// - `forge`: The forge hosting the repositories.
+/// - `upstream`: The upstream repository the pull requests are opened against.
+/// - `contributor`: The contributor repository containing the branches.
+/// - `repo_rule`: The rule of the upstream repository, defining the branch naming.
+/// - `dry_run`: Only list the branches that would be deleted, without deleting them.
+///
+/// # Returns
+/// - `Result<Vec<ContributionBranch>>`
+///     - `Ok(Vec<ContributionBranch>)`: The deleted branches, or the branches that would be deleted in a dry run.
+///     - `Err(Error)`: An error indicating why the branches could not be listed or deleted.
+///       The branches deleted before the error stay deleted.
+pub async fn clean_up_branches(
+    forge: &impl Forge,
+    upstream: &GitHubRepo,
+    contributor: &GitHubRepo,
+    repo_rule: &RepoRule,
+    dry_run: bool,
+) -> Result<Vec<ContributionBranch>> {
+    let deletable: Vec<ContributionBranch> = list_contribution_branches(forge, upstream, contributor, repo_rule).await?
+        .into_iter()
+        .filter(ContributionBranch::is_deletable)
+        .collect();
+
+    if !dry_run {
+        for branch in &deletable {
+            forge.delete_branch(contributor, &branch.name).await?;
+        }
+    }
+
+    Ok(deletable)
+}
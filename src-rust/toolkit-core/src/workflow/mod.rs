@@ -1 +1,4 @@
+pub mod action;
+pub mod article_id;
+pub mod branches;
 pub mod translate;
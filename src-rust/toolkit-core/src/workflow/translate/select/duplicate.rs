@@ -0,0 +1,251 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use futures::{stream, StreamExt, TryStreamExt};
+use regex::Regex;
+
+use crate::apis::Forge;
+use crate::error::{Error, Result};
+use crate::models::{GitHubRepo, PullRequest, RepoRule};
+use crate::template::TemplateRenderer;
+
+/// The number of requests sent concurrently when reading the articles of the upstream repository.
+const READ_CONCURRENCY: usize = 8;
+
+/// The query parameters that only track where a visitor came from, and do not change the article a URL points to.
+const TRACKING_PARAMS: [&str; 5] = ["fbclid", "gclid", "mc_cid", "mc_eid", "spm"];
+
+/// A selection of an article that already exists in the upstream repository.
+///
+/// # Fields
+/// - `path`: The path of the article.
+/// - `pull_request`: The open pull request the article is in, i.e. someone else is selecting (or translating) it right now.
+///   `None` if the article is in a step directory of the base branch of the upstream repository,
+///   i.e. it has been selected (and maybe translated or published) before.
+#[derive(Clone, PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ExistingSelection {
+    pub path: String,
+    pub pull_request: Option<PullRequest>,
+}
+
+/// Canonicalize the URL of an article, so different ways of writing the same URL compare equal.
+///
+/// The scheme, a `www.` prefix of the host, a trailing slash, the fragment and tracking parameters (e.g. `utm_source`)
+/// do not change the article a URL points to, so they are removed. e.g. both `http://www.example.com/news/?utm_source=rss#top`
+/// and `https://example.com/news` are canonicalized to `example.com/news`.
+///
+/// # Arguments
+/// - `url`: The URL to canonicalize.
+///
+/// # Returns
+/// - `String`: The canonical URL, or the trimmed and lowercased input if it is not a valid URL.
+pub fn canonical_url(url: &str) -> String {
+    let url = url.trim();
+    let Ok(parsed) = url::Url::parse(url) else {
+        return url.trim_end_matches('/').to_lowercase();
+    };
+
+    let host = parsed.host_str().unwrap_or_default().to_lowercase();
+    let mut canonical = host.strip_prefix("www.").unwrap_or(&host).to_string();
+    if let Some(port) = parsed.port() {
+        canonical.push_str(&format!(":{}", port));
+    }
+    canonical.push_str(parsed.path().trim_end_matches('/'));
+
+    let params: Vec<(String, String)> = parsed.query_pairs()
+        .filter(|(key, _)| !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_ref()))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    if !params.is_empty() {
+        let query = url::form_urlencoded::Serializer::new(String::new()).extend_pairs(params).finish();
+        canonical.push('?');
+        canonical.push_str(&query);
+    }
+    canonical
+}
+
+/// Get the `via` field of the front matter of an article, i.e. the URL of the original article.
+///
+/// The field may be nested, e.g. in the `authorInfo` block of the default article template.
+///
+/// # Returns
+/// - `Option<String>`: The URL, or `None` if the article has no front matter or no `via` field.
+pub fn front_matter_via(article: &str) -> Option<String> {
+    let rest = article.trim_start_matches('\u{feff}').strip_prefix("---")?;
+    let end = rest.find("\n---")?;
+    let front_matter = &rest[..end];
+
+    let via = Regex::new(r"(?m)^\s*via:\s*(\S+)\s*$").unwrap();
+    let url = via.captures(front_matter)?.get(1)?.as_str();
+    Some(url.trim_matches(|c| c == '"' || c == '\'').to_string())
+}
+
+/// Build a regular expression matching the path of an article in any step directory of any article type of the repository rule,
+/// e.g. `sources/news/20240715-hello.md` or `published/tech/20240101-rust.md`.
+fn article_path_regex(repo_rule: &RepoRule) -> Result<Regex> {
+    // The step is any single directory, so it is rendered as a placeholder to be replaced in the regular expression.
+    const STEP: &str = "FOSSCOPESTEPPLACEHOLDER";
+
+    let renderer = TemplateRenderer::for_rule(repo_rule)?;
+    let mut patterns = Vec::new();
+    for article in &repo_rule.articles {
+        let vars = HashMap::from([
+            ("step", STEP.to_string()),
+            ("type_name", article.r#type.clone()),
+            ("type_desc", article.description.clone()),
+        ]);
+        let directory = renderer.render_path(&format!("directory of {}", article.r#type), &article.directory, &vars)?;
+        let directory = regex::escape(&directory).replace(STEP, "[^/]+");
+        patterns.push(format!("(?:{})", directory));
+    }
+
+    Regex::new(&format!(r"^(?:{})/[^/]+\.md$", patterns.join("|")))
+        .map_err(|e| Error::parse("the article directories of the repository rule", e))
+}
+
+/// The `via` fields of the articles read while searching for existing selections, so they are not read again.
+///
+/// The articles are keyed by their blob SHA, which changes whenever their content does,
+/// so an article is only read again once it is changed, wherever it is moved to.
+/// The index is saved per upstream repository, in the runtime storage directory of the toolkit.
+///
+/// # Fields
+/// - `articles`: The [canonical](canonical_url) `via` field of every article read, `None` if the article has none.
+#[derive(Clone, PartialEq, Eq, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct ArticleIndex {
+    articles: HashMap<String, Option<String>>,
+}
+
+impl ArticleIndex {
+    fn path(workspace: &Path, upstream: &GitHubRepo) -> PathBuf {
+        workspace.join(format!("article_index_{}_{}.json", upstream.owner, upstream.name))
+    }
+
+    /// Load the index of an upstream repository.
+    ///
+    /// # Arguments
+    /// - `workspace`: The runtime storage directory, e.g. [`WORKSPACE_DIR`](crate::WORKSPACE_DIR).
+    /// - `upstream`: The upstream repository.
+    ///
+    /// # Returns
+    /// - `Result<ArticleIndex>`: The saved index, or an empty one if it has not been saved yet.
+    pub fn load(workspace: &Path, upstream: &GitHubRepo) -> Result<Self> {
+        let path = Self::path(workspace, upstream);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let file = std::fs::File::open(&path)?;
+        let reader = std::io::BufReader::new(file);
+        serde_json::from_reader(reader).map_err(|e| Error::parse(path.display().to_string(), e))
+    }
+
+    /// Save the index of an upstream repository in the runtime storage directory `workspace`,
+    /// to be [loaded](ArticleIndex::load) by the next search.
+    pub fn save(&self, workspace: &Path, upstream: &GitHubRepo) -> Result<()> {
+        let json_str = serde_json::to_string(self)
+            .map_err(|e| Error::parse("the article index", e))?;
+        std::fs::create_dir_all(workspace)?;
+        std::fs::write(Self::path(workspace, upstream), json_str)?;
+        Ok(())
+    }
+
+    /// Read the articles that are not in the index yet, and add their `via` field to it.
+    async fn read(&mut self, forge: &impl Forge, repo: &GitHubRepo, articles: &[(String, String)]) -> Result<()> {
+        let unread: HashSet<&str> = articles.iter()
+            .map(|(_, sha)| sha.as_str())
+            .filter(|sha| !self.articles.contains_key(*sha))
+            .collect();
+        let read: Vec<(&str, Vec<u8>)> = stream::iter(unread)
+            .map(|sha| async move {
+                Ok::<_, Error>((sha, forge.get_blob(repo, sha).await?))
+            })
+            .buffered(READ_CONCURRENCY)
+            .try_collect()
+            .await?;
+
+        for (sha, content) in read {
+            let via = front_matter_via(&String::from_utf8_lossy(&content)).map(|via| canonical_url(&via));
+            self.articles.insert(sha.to_string(), via);
+        }
+        Ok(())
+    }
+
+    /// Get the paths of the articles whose `via` field points to the same article as `canonical`.
+    fn matching(&self, articles: &[(String, String)], canonical: &str) -> Vec<String> {
+        articles.iter()
+            .filter(|(_, sha)| self.articles.get(sha).is_some_and(|via| via.as_deref() == Some(canonical)))
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+}
+
+/// Find the existing selections of an article, so the same article is not selected twice.
+///
+/// The articles in the step directories of the base branch of the upstream repository,
+/// and the articles added or changed by the open pull requests against it,
+/// are matched by the `via` field of their front matter, comparing the [canonical URLs](canonical_url).
+/// Only the articles missing from `index` are read, and the index is updated with them.
+///
+/// # Arguments
+/// - `forge`: The forge hosting the upstream repository.
+/// - `upstream`: The upstream repository.
+/// - `repo_rule`: The rule of the upstream repository, defining the directories of the articles.
+/// - `via`: The URL of the article to select.
+/// - `index`: The [ArticleIndex](ArticleIndex) of the articles read by the previous searches.
+///
+/// # Returns
+/// - `Result<Vec<ExistingSelection>>`
+///     - `Ok(Vec<ExistingSelection>)`: Every [ExistingSelection](ExistingSelection) of the article. Empty if the article has not been selected yet.
+///     - `Err(Error)`: An error indicating why the upstream repository could not be searched.
+pub async fn find_existing_selections(
+    forge: &impl Forge,
+    upstream: &GitHubRepo,
+    repo_rule: &RepoRule,
+    via: &str,
+    index: &mut ArticleIndex,
+) -> Result<Vec<ExistingSelection>> {
+    let canonical = canonical_url(via);
+    let article_path = article_path_regex(repo_rule)?;
+    // The articles that are neither on the base branch nor in an open pull request anymore are dropped from the index.
+    let mut current = HashSet::new();
+
+    let base = forge.get_tree(upstream, &repo_rule.git.base, true).await?;
+    let articles: Vec<(String, String)> = base.files()
+        .filter(|entry| article_path.is_match(&entry.path))
+        .map(|entry| (entry.path.clone(), entry.sha.clone()))
+        .collect();
+    index.read(forge, upstream, &articles).await?;
+    current.extend(articles.iter().map(|(_, sha)| sha.clone()));
+    let mut selections: Vec<ExistingSelection> = index.matching(&articles, &canonical)
+        .into_iter()
+        .map(|path| ExistingSelection { path, pull_request: None })
+        .collect();
+
+    // Only the articles a pull request adds or changes are read, the others are on the base branch already.
+    for pull_request in forge.list_open_pull_requests(upstream).await? {
+        let Some(head_repo) = &pull_request.head_repo else {
+            // The head repository of the pull request has been deleted.
+            continue;
+        };
+        let changed: Vec<(String, String)> = forge.list_pull_request_files(upstream, pull_request.number).await?
+            .into_iter()
+            .filter(|file| article_path.is_match(&file.path))
+            .filter_map(|file| Some((file.path, file.sha?)))
+            .collect();
+        match index.read(forge, head_repo, &changed).await {
+            Ok(()) => {}
+            Err(Error::NotFound(_)) => continue,
+            Err(e) => return Err(e),
+        }
+        current.extend(changed.iter().map(|(_, sha)| sha.clone()));
+        for path in index.matching(&changed, &canonical) {
+            selections.push(ExistingSelection {
+                path,
+                pull_request: Some(pull_request.clone()),
+            });
+        }
+    }
+
+    index.articles.retain(|sha, _| current.contains(sha));
+    Ok(selections)
+}
@@ -1,11 +1,12 @@
 use std::string::String;
 use std::collections::HashMap;
 
-use handlebars::{Context, Handlebars, Helper, Output, RenderContext, RenderError};
+use crate::error::{Error, Result};
 use crate::models::{article_metadata, HTMLFilterRule};
 use crate::models::repo_rule::Article;
 use crate::models::RepoRule;
 use crate::models::SupportedWebsites;
+use crate::template::TemplateRenderer;
 use crate::utils;
 
 /// Get the content of an HTML page in Markdown format.
@@ -17,10 +18,10 @@ use crate::utils;
 /// - `url`: The URL of the HTML page to get the content from.
 ///
 /// # Returns
-/// - `Result<(String, String), String>`:
+/// - `Result<(String, String)>`:
 ///     - `Ok(String, String)`: The content of the HTML page in Markdown format (first element) and the title of the page (second element).
-///     - `Err(String)`: An error message indicating why the content could not be fetched.
-pub async fn get_content(url: &str) -> Result<(String, String), String> {
+///     - `Err(Error)`: An error indicating why the content could not be fetched.
+pub async fn get_content(url: &str) -> Result<(String, String)> {
     let website = SupportedWebsites::get_website(url)?;
 
     let html_filter_rule = HTMLFilterRule::get_filter_rule(&website);
@@ -38,7 +39,7 @@ pub async fn get_content(url: &str) -> Result<(String, String), String> {
     // The title is the only H1 header in the content, with the `# ` prefix removed.
     let title = &content.split("\n").find(
         |line| line.starts_with("# ")
-    ).unwrap();
+    ).ok_or_else(|| Error::NotFound(format!("title of the article at {}", url)))?;
     // Remove the line from the content
     let content = content.replace(title, "");
     // Remove the `# ` prefix from the title
@@ -55,25 +56,22 @@ pub async fn get_content(url: &str) -> Result<(String, String), String> {
 /// - `vars`: The variables to use when rendering the Handlebars template.
 ///
 /// # Returns
-/// - `Result<(String, String), String>`:
+/// - `Result<(String, String)>`:
 ///     - `Ok((String, String))`: The content of the article, in Markdown format, rendered with provided variables (first element) and the title of the article (second element).
-///     - `Err(String)`: An error message if the content could not be fetched.
+///     - `Err(Error)`: An error if the content could not be fetched or the template could not be rendered.
 pub async fn fetch(
     repo_rule: &RepoRule,
     article_type: &Article,
     vars: &HashMap<&str, String>,
-) -> Result<(String, String), String> {
-    let url = vars.get("via").unwrap();
+) -> Result<(String, String)> {
+    let url = vars.get("via")
+        .ok_or_else(|| Error::InvalidInput("The URL of the article (`via`) is not provided.".to_string()))?;
 
     let mut local_vars = vars.clone();
 
     let html = utils::html::get_html_content(url).await?;
 
-    let content = get_content(url).await;
-    if content.is_err() {
-        return content
-    }
-    let content = content.unwrap();
+    let content = get_content(url).await?;
 
     // Add Info To Vars
     local_vars.insert("title", content.1.clone());
@@ -92,25 +90,9 @@ pub async fn fetch(
     // Get Article Template
     let article_template = repo_rule.get_article_template(article_type);
 
-    let mut handlebars = Handlebars::new();
-    handlebars.set_strict_mode(false);
-    handlebars.register_helper(
-        "helperMissing",
-        Box::new(
-            |h: &Helper<'_>,
-             _: &Handlebars<'_>,
-             _: &Context,
-             _: &mut RenderContext<'_, '_>,
-             out: &mut dyn Output|
-             -> Result<(), RenderError> {
-                out.write(&format!("{{{{{}}}}}", h.name()))?;
-                Ok(())
-            },
-        ),
-    );
-    handlebars.register_escape_fn(handlebars::no_escape);
-
-    let rendered = handlebars.render_template(&*article_template, &local_vars).unwrap();
+    // The placeholders that cannot be filled in automatically are kept, for the contributor to fill them in by hand.
+    let renderer = TemplateRenderer::for_rule(repo_rule)?.keep_missing_placeholders();
+    let rendered = renderer.render("article_template", &article_template, &local_vars)?;
 
     Ok((rendered, content.1))
 }
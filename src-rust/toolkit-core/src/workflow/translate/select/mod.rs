@@ -1,4 +1,6 @@
+pub use duplicate::*;
 pub use fetch::*;
 
+mod duplicate;
 mod fetch;
 pub mod submit;
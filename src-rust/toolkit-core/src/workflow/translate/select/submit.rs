@@ -1,75 +1,63 @@
 use std::collections::HashMap;
 
-use handlebars::Handlebars;
-
-use crate::apis::GitHubApi;
-use crate::models::{GitHubRepo, RepoRule};
+use crate::apis::{Forge, LocalGitRepo};
+use crate::error::Result;
+use crate::models::{GitHubRepo, PullRequest, RepoRule};
 use crate::models::repo_rule::Article;
+use crate::workflow::action;
+
+/// The name of the action selecting an article in the repository rule.
+pub const SELECT: &str = "select";
 
+/// Commit the selected article to a branch in the contributor repository, performing the `select` action
+/// of the repository rule with [`action::commit`](action::commit), which describes how the branch is created
+/// and why committing is safe to retry.
+#[allow(clippy::too_many_arguments)]
 pub async fn commit(
-    github: &GitHubApi,
+    github: &impl Forge,
+    upstream_repo: &GitHubRepo,
     contributor_repo: &GitHubRepo,
     file_name: &str,
     content: &str,
     article_type: &Article,
     repo_rule: &RepoRule,
     vars: &HashMap<&str, String>,
-) -> Result<(), String> {
-    let mut local_vars = vars.clone();
-    local_vars.insert("action_name", "select".to_string());
-    local_vars.insert("action_desc", "选题".to_string());
-    local_vars.insert("step", "sources".to_string());
-
-    let handlebars = Handlebars::new();
-    let branch_name = handlebars.render_template(&*repo_rule.git.branch_naming, &local_vars).unwrap();
-    let commit_message = handlebars.render_template(&*repo_rule.git.commit_message, &local_vars).unwrap();
-    let path = format!(
-        "{}/{}",
-        handlebars.render_template(&*article_type.directory, &local_vars).unwrap(),
-        file_name,
-    );
-
-    let r = github.create_new_reference(contributor_repo, &branch_name).await;
-    if r.is_err() {
-        return Err(format!("Failed to create a new branch: {:?}", r.err()));
-    }
-
-    let r = github.create_file(
-        contributor_repo, &branch_name, &path, content, &commit_message
-    ).await;
-    if r.is_err() {
-        return Err(r.err().unwrap());
-    }
+) -> Result<()> {
+    let select = repo_rule.action(SELECT)?;
+    action::commit(
+        github, upstream_repo, contributor_repo, select, file_name, content, article_type, repo_rule, vars,
+    ).await
+}
 
-    Ok(())
+/// Commit the selected article in a local clone of the contributor repository, and push it, returning the SHA of the commit.
+///
+/// This performs the `select` action of the repository rule with [`action::commit_locally`](action::commit_locally),
+/// the counterpart of [`commit`](commit) for the [LocalGitRepo](LocalGitRepo) backend.
+#[allow(clippy::too_many_arguments)]
+pub async fn commit_locally(
+    local_repo: &LocalGitRepo,
+    upstream_url: &str,
+    file_name: &str,
+    content: &str,
+    article_type: &Article,
+    repo_rule: &RepoRule,
+    vars: &HashMap<&str, String>,
+) -> Result<String> {
+    let select = repo_rule.action(SELECT)?;
+    action::commit_locally(local_repo, upstream_url, select, file_name, content, article_type, repo_rule, vars).await
 }
 
+/// Create a pull request for the selected article, returning the created [`PullRequest`](PullRequest)
+/// so its number and URL can be shown to the contributor.
+///
+/// The pull request follows the `select` action of the repository rule, see [`action::create_pr`](action::create_pr).
 pub async fn create_pr(
-    github: &GitHubApi,
+    github: &impl Forge,
     upstream_repo: &GitHubRepo,
     contributor_repo: &GitHubRepo,
     repo_rule: &RepoRule,
     vars: &HashMap<&str, String>,
-) -> Result<(), String> {
-    let mut local_vars = vars.clone();
-    local_vars.insert("action_name", "select".to_string());
-    local_vars.insert("action_desc", "选题".to_string());
-    local_vars.insert("step", "sources".to_string());
-
-    let handlebars = Handlebars::new();
-    let branch_name = handlebars.render_template(&*repo_rule.git.branch_naming, &local_vars).unwrap();
-    let pr_title = handlebars.render_template(&*repo_rule.git.commit_message, &local_vars).unwrap();
-
-    let r = github.create_pull_request(
-        upstream_repo,
-        contributor_repo,
-        &pr_title,
-        &branch_name,
-        &repo_rule.git.base,
-    ).await;
-    if r.is_err() {
-        return Err(r.err().unwrap());
-    }
-
-    Ok(())
+) -> Result<PullRequest> {
+    let select = repo_rule.action(SELECT)?;
+    action::create_pr(github, upstream_repo, contributor_repo, select, repo_rule, vars).await
 }
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use fosscopetoolkit_core::apis::{Forge, SandboxForge};
+    use fosscopetoolkit_core::models::GitHubRepo;
+    use fosscopetoolkit_core::models::repo_rule::get_repo_rule;
+    use fosscopetoolkit_core::workflow::action::{self, StepArticle};
+    use fosscopetoolkit_core::workflow::translate::select::submit;
+
+    fn upstream() -> GitHubRepo {
+        GitHubRepo::new("FOSScope".to_string(), "TranslateProject".to_string())
+    }
+
+    fn fork() -> GitHubRepo {
+        GitHubRepo::new("sandbox".to_string(), "TranslateProject".to_string())
+    }
+
+    fn vars() -> HashMap<&'static str, String> {
+        HashMap::from([
+            ("contributor", "sandbox".to_string()),
+            ("type_name", "news".to_string()),
+            ("type_desc", "新闻".to_string()),
+            ("article_title", "Hello".to_string()),
+            ("article_id", "20240715-hello".to_string()),
+        ])
+    }
+
+    /// The paths of the articles on the base branch of the upstream repository.
+    async fn upstream_articles(sandbox: &SandboxForge) -> Vec<String> {
+        sandbox.get_tree(&upstream(), "main", true).await.unwrap()
+            .files()
+            .map(|entry| entry.path.clone())
+            .filter(|path| path.ends_with(".md") && path != "README.md")
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn translate_moves_the_selected_article() {
+        let sandbox = SandboxForge::seeded("sandbox".to_string());
+        sandbox.create_fork(&fork(), &upstream()).await.unwrap();
+        let repo_rule = get_repo_rule(&upstream(), &sandbox).await.unwrap();
+        let translate = repo_rule.action("translate").unwrap();
+
+        assert!(action::list_step_articles(&sandbox, &upstream(), translate, &repo_rule).await.unwrap().is_empty());
+
+        submit::commit(
+            &sandbox, &upstream(), &fork(), "20240715-hello.md", "Hello", &repo_rule.articles[0], &repo_rule, &vars(),
+        ).await.unwrap();
+        let pr = submit::create_pr(&sandbox, &upstream(), &fork(), &repo_rule, &vars()).await.unwrap();
+        sandbox.merge_pull_request(&upstream(), pr.number).unwrap();
+        assert_eq!(upstream_articles(&sandbox).await, vec!["sources/news/20240715-hello.md"]);
+
+        let articles = action::list_step_articles(&sandbox, &upstream(), translate, &repo_rule).await.unwrap();
+        assert_eq!(articles.len(), 1);
+        let StepArticle { article_type, file_name, path, sha } = &articles[0];
+        assert_eq!((article_type.as_str(), file_name.as_str()), ("news", "20240715-hello.md"));
+        assert_eq!(path, "sources/news/20240715-hello.md");
+        assert_eq!(sandbox.get_blob(&upstream(), sha).await.unwrap(), b"Hello");
+        // Selecting creates the article, so there is nothing to select from.
+        let select = repo_rule.action("select").unwrap();
+        assert!(action::list_step_articles(&sandbox, &upstream(), select, &repo_rule).await.unwrap().is_empty());
+
+        // The required variables have to be given.
+        let mut missing = vars();
+        missing.remove("article_title");
+        let error = action::commit(
+            &sandbox, &upstream(), &fork(), translate, file_name, "你好", &repo_rule.articles[0], &repo_rule, &missing,
+        ).await.unwrap_err();
+        assert_eq!(error.kind(), "InvalidInput");
+
+        action::commit(
+            &sandbox, &upstream(), &fork(), translate, file_name, "你好", &repo_rule.articles[0], &repo_rule, &vars(),
+        ).await.unwrap();
+        let pr = action::create_pr(&sandbox, &upstream(), &fork(), translate, &repo_rule, &vars()).await.unwrap();
+        assert_eq!(pr.head, "translate/news/20240715-hello");
+        assert_eq!(pr.title, "[翻译][新闻] Hello");
+
+        sandbox.merge_pull_request(&upstream(), pr.number).unwrap();
+        assert_eq!(upstream_articles(&sandbox).await, vec!["translated/news/20240715-hello.md"]);
+
+        // Proofreading edits the article in place.
+        let proofread = repo_rule.action("proofread").unwrap();
+        action::commit(
+            &sandbox, &upstream(), &fork(), proofread, file_name, "你好！", &repo_rule.articles[0], &repo_rule, &vars(),
+        ).await.unwrap();
+        let pr = action::create_pr(&sandbox, &upstream(), &fork(), proofread, &repo_rule, &vars()).await.unwrap();
+        sandbox.merge_pull_request(&upstream(), pr.number).unwrap();
+        assert_eq!(upstream_articles(&sandbox).await, vec!["translated/news/20240715-hello.md"]);
+        let tree = sandbox.get_tree(&upstream(), "main", true).await.unwrap();
+        let article = tree.files().find(|entry| entry.path == "translated/news/20240715-hello.md").unwrap();
+        assert_eq!(sandbox.get_blob(&upstream(), &article.sha).await.unwrap(), "你好！".as_bytes());
+    }
+}
@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use wiremock::{matchers::any, Mock, MockServer, ResponseTemplate};
+
+    use fosscopetoolkit_core::apis::{Forge, GiteaApi, SandboxForge};
+    use fosscopetoolkit_core::models::{FileChange, GitHubRepo};
+    use fosscopetoolkit_core::models::repo_rule::{ArticleIdRule, get_repo_rule, Transliteration};
+    use fosscopetoolkit_core::workflow::article_id;
+
+    const REPORULE: &str = r#"article_template = "{{content}}"
+
+[[articles]]
+type = "news"
+description = "新闻"
+directory = "{{step}}/news"
+
+[article_id]
+transliteration = "pinyin"
+max_length = 33
+
+[git]
+base = "main"
+branch_naming = "{{action_name}}/{{type_name}}/{{article_id}}"
+commit_message = "[{{action_desc}}][{{type_desc}}] {{article_title}}"
+"#;
+
+    fn upstream() -> GitHubRepo {
+        GitHubRepo::new("FOSScope".to_string(), "Articles".to_string())
+    }
+
+    fn fork() -> GitHubRepo {
+        GitHubRepo::new("sandbox".to_string(), "Articles".to_string())
+    }
+
+    fn vars() -> HashMap<&'static str, String> {
+        HashMap::from([("publishing_date", "20240715".to_string())])
+    }
+
+    #[test]
+    fn slugify() {
+        let rule = ArticleIdRule::default();
+        assert_eq!(rule.slugify("Hello, World!"), "hello-world");
+        assert_eq!(rule.slugify("  Don't Panic: Linux 6.10 is out  "), "dont-panic-linux-6-10-is-out");
+        assert_eq!(rule.slugify("开源软件的未来"), "开源软件的未来");
+        assert_eq!(rule.slugify("?!"), "");
+
+        let rule = ArticleIdRule { transliteration: Transliteration::Pinyin, ..ArticleIdRule::default() };
+        assert_eq!(rule.slugify("开源软件的未来"), "kai-yuan-ruan-jian-de-wei-lai");
+        assert_eq!(rule.slugify("Linux 内核 6.10 发布了！"), "linux-nei-he-6-10-fa-bu-le");
+        assert_eq!(rule.slugify("女性开发者"), "nv-xing-kai-fa-zhe");
+    }
+
+    #[test]
+    fn generate() {
+        let rule = ArticleIdRule::default();
+        assert_eq!(rule.generate("Hello, World!", &vars()).unwrap(), "20240715-hello-world");
+        // The dashes around an empty slug are removed.
+        assert_eq!(rule.generate("?!", &vars()).unwrap(), "20240715");
+
+        // The slug is shortened between words to fit.
+        let rule = ArticleIdRule { max_length: 24, ..ArticleIdRule::default() };
+        assert_eq!(rule.generate("The quick brown fox jumps", &vars()).unwrap(), "20240715-the-quick-brown");
+        let rule = ArticleIdRule { max_length: 12, ..ArticleIdRule::default() };
+        assert_eq!(rule.generate("Supercalifragilistic", &vars()).unwrap(), "20240715-sup");
+        let rule = ArticleIdRule { max_length: 6, ..ArticleIdRule::default() };
+        assert_eq!(rule.generate("Hello", &vars()).unwrap_err().kind(), "InvalidInput");
+
+        let rule = ArticleIdRule { pattern: "{{type_name}}/{{slug}}".to_string(), ..ArticleIdRule::default() };
+        let vars = HashMap::from([("type_name", "news".to_string())]);
+        assert_eq!(rule.generate("Hello", &vars).unwrap_err().kind(), "InvalidInput");
+    }
+
+    #[tokio::test]
+    async fn collisions() {
+        let sandbox = SandboxForge::new("sandbox".to_string());
+        sandbox.add_repo(&upstream(), &[
+            ("REPORULE", REPORULE),
+            ("sources/news/20240715-kai-yuan-ruan-jian.md", "开源软件"),
+            ("translated/news/20240715-kai-yuan-ruan-jian-2.md", "开源软件"),
+        ]).unwrap();
+        let repo_rule = get_repo_rule(&upstream(), &sandbox).await.unwrap();
+        let article_type = &repo_rule.articles[0];
+
+        // The fork has not been created yet.
+        let id = article_id::generate(&sandbox, &upstream(), &fork(), article_type, &repo_rule, "开源软件", &vars()).await.unwrap();
+        assert_eq!(id.id, "20240715-kai-yuan-ruan-jian-3");
+
+        sandbox.create_fork(&fork(), &upstream()).await.unwrap();
+        sandbox.commit_files(
+            &fork(), "main", &[FileChange::write("sources/news/20240715-kai-yuan-ruan-jian-3.md", "开源软件")], "选题",
+        ).await.unwrap();
+        let id = article_id::generate(&sandbox, &upstream(), &fork(), article_type, &repo_rule, "开源软件", &vars()).await.unwrap();
+        assert_eq!(id.id, "20240715-kai-yuan-ruan-jian-4");
+
+        // The slug is shortened to make room for the number.
+        let title = "开源基金会年度报告";
+        let id = article_id::generate(&sandbox, &upstream(), &fork(), article_type, &repo_rule, title, &vars()).await.unwrap();
+        assert_eq!(id.id, "20240715-kai-yuan-ji-jin-hui-nian");
+        sandbox.commit_files(
+            &fork(), "main", &[FileChange::write("sources/news/20240715-kai-yuan-ji-jin-hui-nian.md", title)], "选题",
+        ).await.unwrap();
+        let id = article_id::generate(&sandbox, &upstream(), &fork(), article_type, &repo_rule, title, &vars()).await.unwrap();
+        assert_eq!(id.id, "20240715-kai-yuan-ji-jin-hui-2");
+
+        // Other article types and other files do not collide.
+        let id = article_id::generate(&sandbox, &upstream(), &fork(), article_type, &repo_rule, "REPORULE", &vars()).await.unwrap();
+        assert_eq!(id.id, "20240715-reporule");
+    }
+
+    #[tokio::test]
+    async fn unchecked() {
+        let sandbox = SandboxForge::new("sandbox".to_string());
+        sandbox.add_repo(&upstream(), &[("REPORULE", REPORULE)]).unwrap();
+        let repo_rule = get_repo_rule(&upstream(), &sandbox).await.unwrap();
+
+        // The files cannot be listed, so the ID is generated without checking it.
+        let mock_server = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(500).set_body_json(serde_json::json!({ "message": "internal error" })))
+            .mount(&mock_server)
+            .await;
+        let gitea = GiteaApi::new("sandbox".to_string(), mock_server.uri(), "0123456789abcdef".to_string());
+        let id = article_id::generate(&gitea, &upstream(), &fork(), &repo_rule.articles[0], &repo_rule, "开源软件", &vars())
+            .await
+            .unwrap();
+        assert_eq!(id.id, "20240715-kai-yuan-ruan-jian");
+        assert_eq!(id.unchecked.unwrap().kind(), "Forge");
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use fosscopetoolkit_core::apis::{Forge, SandboxForge};
+    use fosscopetoolkit_core::models::{FileChange, GitHubRepo, PullRequestState};
+    use fosscopetoolkit_core::models::repo_rule::get_repo_rule;
+    use fosscopetoolkit_core::workflow::branches::{clean_up_branches, list_contribution_branches};
+    use fosscopetoolkit_core::workflow::translate::select::submit;
+
+    fn upstream() -> GitHubRepo {
+        GitHubRepo::new("FOSScope".to_string(), "TranslateProject".to_string())
+    }
+
+    fn fork() -> GitHubRepo {
+        GitHubRepo::new("sandbox".to_string(), "TranslateProject".to_string())
+    }
+
+    fn vars(article_id: &str) -> HashMap<&'static str, String> {
+        HashMap::from([
+            ("via", format!("https://example.com/{}", article_id)),
+            ("selector", "sandbox".to_string()),
+            ("type_name", "news".to_string()),
+            ("type_desc", "新闻".to_string()),
+            ("article_title", article_id.to_string()),
+            ("article_id", article_id.to_string()),
+        ])
+    }
+
+    /// Select an article in the sandbox, returning the number of the pull request.
+    async fn select(sandbox: &SandboxForge, article_id: &str) -> u64 {
+        let repo_rule = get_repo_rule(&upstream(), sandbox).await.unwrap();
+        let vars = vars(article_id);
+        submit::commit(
+            sandbox, &upstream(), &fork(), &format!("{}.md", article_id), "Hello", &repo_rule.articles[0], &repo_rule, &vars,
+        ).await.unwrap();
+        submit::create_pr(sandbox, &upstream(), &fork(), &repo_rule, &vars).await.unwrap().number
+    }
+
+    #[tokio::test]
+    async fn clean_up_merged_and_closed_branches() {
+        let sandbox = SandboxForge::seeded("sandbox".to_string());
+        sandbox.create_fork(&fork(), &upstream()).await.unwrap();
+        let repo_rule = get_repo_rule(&upstream(), &sandbox).await.unwrap();
+
+        let merged = select(&sandbox, "merged").await;
+        let closed = select(&sandbox, "closed").await;
+        select(&sandbox, "open").await;
+        let revived = select(&sandbox, "revived").await;
+        sandbox.merge_pull_request(&upstream(), merged).unwrap();
+        sandbox.close_pull_request(&upstream(), closed).await.unwrap();
+        sandbox.close_pull_request(&upstream(), revived).await.unwrap();
+        // Work continues on a branch after its pull request has been closed.
+        sandbox.commit_files(&fork(), "select/news/revived", &[FileChange::write("draft.md", "Draft")], "Continue")
+            .await.unwrap();
+        // Branches that do not follow the branch naming are not contribution branches.
+        sandbox.create_reference_from(&fork(), "experiment", &upstream(), "main").await.unwrap();
+        // Neither is a branch without a pull request deletable.
+        sandbox.create_reference_from(&fork(), "select/news/unsubmitted", &upstream(), "main").await.unwrap();
+
+        let branches = list_contribution_branches(&sandbox, &upstream(), &fork(), &repo_rule).await.unwrap();
+        let summary: Vec<(&str, Option<PullRequestState>, bool)> = branches.iter()
+            .map(|branch| (branch.name.as_str(), branch.pull_request.as_ref().map(|pr| pr.state), branch.is_deletable()))
+            .collect();
+        assert_eq!(summary, vec![
+            ("select/news/closed", Some(PullRequestState::Closed), true),
+            ("select/news/merged", Some(PullRequestState::Merged), true),
+            ("select/news/open", Some(PullRequestState::Open), false),
+            ("select/news/revived", Some(PullRequestState::Closed), false),
+            ("select/news/unsubmitted", None, false),
+        ]);
+
+        // A dry run only lists the branches.
+        let deletable = clean_up_branches(&sandbox, &upstream(), &fork(), &repo_rule, true).await.unwrap();
+        let names: Vec<&str> = deletable.iter().map(|branch| branch.name.as_str()).collect();
+        assert_eq!(names, vec!["select/news/closed", "select/news/merged"]);
+        sandbox.get_branch_sha(&fork(), "select/news/merged").await.unwrap();
+
+        let deleted = clean_up_branches(&sandbox, &upstream(), &fork(), &repo_rule, false).await.unwrap();
+        assert_eq!(deleted, deletable);
+        let remaining: Vec<String> = sandbox.list_branches(&fork()).await.unwrap()
+            .into_iter()
+            .map(|branch| branch.name)
+            .collect();
+        assert_eq!(remaining, vec![
+            "experiment", "main", "select/news/open", "select/news/revived", "select/news/unsubmitted",
+        ]);
+        assert!(clean_up_branches(&sandbox, &upstream(), &fork(), &repo_rule, false).await.unwrap().is_empty());
+
+        assert_eq!(sandbox.delete_branch(&fork(), "main").await.unwrap_err().kind(), "Conflict");
+        assert_eq!(sandbox.delete_branch(&fork(), "select/news/merged").await.unwrap_err().kind(), "NotFound");
+    }
+}
@@ -0,0 +1,249 @@
+use octocrab::Octocrab;
+use serde_json::json;
+use wiremock::{
+    matchers::{body_partial_json, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+use mock_error::setup_error_handler;
+
+mod mock_error;
+
+// This function sets up the mock server with the upstream base branch and the reference creation endpoint of the fork.
+async fn setup_api() -> MockServer {
+    let mock_server = MockServer::start().await;
+
+    let mocked_path = "/repos/octocat/Hello-World/git/ref/heads/main";
+
+    Mock::given(method("GET"))
+        .and(path(mocked_path))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::from_str::<serde_json::Value>(include_str!("resources/reference.json")).unwrap())
+        )
+        .mount(&mock_server)
+        .await;
+    // The new branch in the fork must point to the head of the upstream base branch.
+    Mock::given(method("POST"))
+        .and(path("/repos/FOSScope/Hello-World/git/refs"))
+        .and(body_partial_json(json!({
+            "ref": "refs/heads/select/news/hello",
+            "sha": "aa218f56b14c9653891f9e74264a383fa43fefbd",
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "ref": "refs/heads/select/news/hello",
+            "node_id": "MDM6UmVmcmVmcy9oZWFkcy9zZWxlY3Q=",
+            "url": "https://api.github.com/repos/FOSScope/Hello-World/git/refs/heads/select/news/hello",
+            "object": {
+                "type": "commit",
+                "sha": "aa218f56b14c9653891f9e74264a383fa43fefbd",
+                "url": "https://api.github.com/repos/FOSScope/Hello-World/git/commits/aa218f56b14c9653891f9e74264a383fa43fefbd"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+    setup_error_handler(
+        &mock_server,
+        &format!("GET on {mocked_path} was not received"),
+    ).await;
+
+    mock_server
+}
+
+// This function sets up the Octocrab client with the base URI of the mock server.
+fn setup_octocrab(uri: &str) -> Octocrab {
+    Octocrab::builder().base_uri(uri).unwrap().build().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use fosscopetoolkit_core::apis::GitHubApi;
+    use fosscopetoolkit_core::Error;
+    use fosscopetoolkit_core::models::{Branch, GitHubRepo};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn get_branch_sha() {
+        let mock_server = setup_api().await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let result = github.get_branch_sha(
+            &GitHubRepo::new("octocat".to_string(), "Hello-World".to_string()),
+            "main",
+        ).await.unwrap();
+
+        assert_eq!(result, "aa218f56b14c9653891f9e74264a383fa43fefbd");
+    }
+
+    #[tokio::test]
+    async fn get_non_existent_branch_sha() {
+        let mock_server = setup_api().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/octocat/Hello-World/git/ref/heads/dev"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+                "message": "Not Found",
+                "documentation_url": "https://docs.github.com/rest/git/refs#get-a-reference",
+            })))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let result = github.get_branch_sha(
+            &GitHubRepo::new("octocat".to_string(), "Hello-World".to_string()),
+            "dev",
+        ).await;
+
+        assert!(matches!(result, Err(Error::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn branch_from_upstream() {
+        let mock_server = setup_api().await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let result = github.create_reference_from(
+            &GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string()),
+            "select/news/hello",
+            &GitHubRepo::new("octocat".to_string(), "Hello-World".to_string()),
+            "main",
+        ).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn branch_from_upstream_missing_in_fork() {
+        let mock_server = setup_api().await;
+        // The commit is only available in the fork once it is synchronized with the upstream repository.
+        Mock::given(method("POST"))
+            .and(path("/repos/FOSScope/Hello-World/git/refs"))
+            .respond_with(ResponseTemplate::new(422).set_body_json(json!({
+                "message": "Object does not exist",
+                "documentation_url": "https://docs.github.com/rest/git/refs#create-a-reference",
+            })))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/repos/FOSScope/Hello-World/merge-upstream"))
+            .and(body_partial_json(json!({ "branch": "main" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "message": "Successfully fetched and fast-forwarded from upstream octocat:main.",
+                "merge_type": "fast-forward",
+                "base_branch": "octocat:main",
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let result = github.create_reference_from(
+            &GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string()),
+            "select/news/hello",
+            &GitHubRepo::new("octocat".to_string(), "Hello-World".to_string()),
+            "main",
+        ).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn branch_from_upstream_already_exists() {
+        let mock_server = setup_api().await;
+        Mock::given(method("POST"))
+            .and(path("/repos/FOSScope/Hello-World/git/refs"))
+            .respond_with(ResponseTemplate::new(422).set_body_json(json!({
+                "message": "Reference already exists",
+                "documentation_url": "https://docs.github.com/rest/git/refs#create-a-reference",
+            })))
+            .expect(1)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        // The branch of the fork is not touched if the error is not about a missing commit.
+        Mock::given(method("POST"))
+            .and(path("/repos/FOSScope/Hello-World/merge-upstream"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let result = github.create_reference_from(
+            &GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string()),
+            "select/news/hello",
+            &GitHubRepo::new("octocat".to_string(), "Hello-World".to_string()),
+            "main",
+        ).await;
+
+        assert!(matches!(result, Err(Error::GitHub(_))));
+    }
+
+    #[tokio::test]
+    async fn list_branches() {
+        let mock_server = setup_api().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/FOSScope/Hello-World/branches"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {
+                    "name": "main",
+                    "commit": {
+                        "sha": "aa218f56b14c9653891f9e74264a383fa43fefbd",
+                        "url": "https://api.github.com/repos/FOSScope/Hello-World/commits/aa218f56b14c9653891f9e74264a383fa43fefbd"
+                    },
+                    "protected": false
+                },
+                {
+                    "name": "select/news/hello",
+                    "commit": {
+                        "sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e",
+                        "url": "https://api.github.com/repos/FOSScope/Hello-World/commits/6dcb09b5b57875f334f61aebed695e2e4193db5e"
+                    },
+                    "protected": false
+                }
+            ])))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let branches = github.list_branches(&GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(branches, vec![
+            Branch::new("main".to_string(), "aa218f56b14c9653891f9e74264a383fa43fefbd".to_string()),
+            Branch::new("select/news/hello".to_string(), "6dcb09b5b57875f334f61aebed695e2e4193db5e".to_string()),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn delete_branch() {
+        let mock_server = setup_api().await;
+        Mock::given(method("DELETE"))
+            .and(path("/repos/FOSScope/Hello-World/git/refs/heads/select/news/hello"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let result = github.delete_branch(
+            &GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string()),
+            "select/news/hello",
+        ).await;
+
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,162 @@
+use octocrab::Octocrab;
+use serde_json::json;
+use wiremock::{
+    matchers::{body_partial_json, method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+use mock_error::setup_error_handler;
+
+mod mock_error;
+
+fn resource(json: &str) -> serde_json::Value {
+    serde_json::from_str(json).unwrap()
+}
+
+// This function sets up the mock server with every Git Data API endpoint needed to commit multiple files.
+async fn setup_api() -> MockServer {
+    let mock_server = MockServer::start().await;
+
+    let mocked_path = "/repos/FOSScope/Hello-World/git/ref/heads/translate/news/hello";
+
+    Mock::given(method("GET"))
+        .and(path(mocked_path))
+        .respond_with(ResponseTemplate::new(200).set_body_json(resource(include_str!("resources/reference.json"))))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/FOSScope/Hello-World/git/commits/aa218f56b14c9653891f9e74264a383fa43fefbd"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(resource(include_str!("resources/git_commit.json"))))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/FOSScope/Hello-World/git/trees/9fb037999f264ba9a7fc6274d15fa3ae2ab98312"))
+        .and(query_param("recursive", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(resource(include_str!("resources/git_tree.json"))))
+        .mount(&mock_server)
+        .await;
+    // "image" in base64.
+    Mock::given(method("POST"))
+        .and(path("/repos/FOSScope/Hello-World/git/blobs"))
+        .and(body_partial_json(json!({ "content": "aW1hZ2U=", "encoding": "base64" })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "sha": "3a0f86fb8db8eea7ccbb9a95f325ddbedfb25e15",
+            "url": "https://api.github.com/repos/FOSScope/Hello-World/git/blobs/3a0f86fb8db8eea7ccbb9a95f325ddbedfb25e15",
+        })))
+        .mount(&mock_server)
+        .await;
+    // The new tree must be based on the current tree, and contain every change.
+    Mock::given(method("POST"))
+        .and(path("/repos/FOSScope/Hello-World/git/trees"))
+        .and(body_partial_json(json!({
+            "base_tree": "9fb037999f264ba9a7fc6274d15fa3ae2ab98312",
+            "tree": [
+                { "path": "translated/news/images/hello.png", "sha": "3a0f86fb8db8eea7ccbb9a95f325ddbedfb25e15" },
+                { "path": "translated/news/20240715-hello.md", "sha": "45b983be36b73c0788dc9cbcb76cbb80fc7bb057" },
+                { "path": "sources/news/20240715-hello.md", "sha": null },
+                { "path": "README.md", "sha": null },
+            ],
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "sha": "cd8274d15fa3ae2ab983129fb037999f264ba9a7",
+            "url": "https://api.github.com/repos/FOSScope/Hello-World/git/trees/cd8274d15fa3ae2ab983129fb037999f264ba9a7",
+            "tree": [],
+            "truncated": false,
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/repos/FOSScope/Hello-World/git/commits"))
+        .and(body_partial_json(json!({
+            "message": "[翻译][新闻] Hello",
+            "tree": "cd8274d15fa3ae2ab983129fb037999f264ba9a7",
+            "parents": ["aa218f56b14c9653891f9e74264a383fa43fefbd"],
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "sha": "7638417db6d59f3c431d3e1f261cc637155684cd",
+            "node_id": "MDY6Q29tbWl0NzYzODQxN2RiNmQ1OWYzYzQzMWQzZTFmMjYxY2M2MzcxNTU2ODRjZA==",
+            "url": "https://api.github.com/repos/FOSScope/Hello-World/git/commits/7638417db6d59f3c431d3e1f261cc637155684cd",
+            "message": "[翻译][新闻] Hello",
+            "tree": {
+                "sha": "cd8274d15fa3ae2ab983129fb037999f264ba9a7",
+                "url": "https://api.github.com/repos/FOSScope/Hello-World/git/trees/cd8274d15fa3ae2ab983129fb037999f264ba9a7",
+            },
+            "parents": [{
+                "sha": "aa218f56b14c9653891f9e74264a383fa43fefbd",
+                "url": "https://api.github.com/repos/FOSScope/Hello-World/git/commits/aa218f56b14c9653891f9e74264a383fa43fefbd",
+            }],
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/repos/FOSScope/Hello-World/git/refs/heads/translate/news/hello"))
+        .and(body_partial_json(json!({ "sha": "7638417db6d59f3c431d3e1f261cc637155684cd", "force": false })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "ref": "refs/heads/translate/news/hello",
+            "node_id": "MDM6UmVmcmVmcy9oZWFkcy90cmFuc2xhdGU=",
+            "url": "https://api.github.com/repos/FOSScope/Hello-World/git/refs/heads/translate/news/hello",
+            "object": {
+                "type": "commit",
+                "sha": "7638417db6d59f3c431d3e1f261cc637155684cd",
+                "url": "https://api.github.com/repos/FOSScope/Hello-World/git/commits/7638417db6d59f3c431d3e1f261cc637155684cd",
+            },
+        })))
+        .mount(&mock_server)
+        .await;
+    setup_error_handler(
+        &mock_server,
+        &format!("GET on {mocked_path} was not received"),
+    ).await;
+
+    mock_server
+}
+
+// This function sets up the Octocrab client with the base URI of the mock server.
+fn setup_octocrab(uri: &str) -> Octocrab {
+    Octocrab::builder().base_uri(uri).unwrap().build().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use fosscopetoolkit_core::apis::GitHubApi;
+    use fosscopetoolkit_core::Error;
+    use fosscopetoolkit_core::models::{FileChange, GitHubRepo};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn commit_multiple_files() {
+        let mock_server = setup_api().await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let result = github.commit_files(
+            &GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string()),
+            "translate/news/hello",
+            &[
+                FileChange::write("translated/news/images/hello.png", "image"),
+                FileChange::rename("sources/news/20240715-hello.md", "translated/news/20240715-hello.md"),
+                FileChange::delete("README.md"),
+            ],
+            "[翻译][新闻] Hello",
+        ).await.unwrap();
+
+        assert_eq!(result, "7638417db6d59f3c431d3e1f261cc637155684cd");
+    }
+
+    #[tokio::test]
+    async fn rename_non_existent_file() {
+        let mock_server = setup_api().await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let result = github.commit_files(
+            &GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string()),
+            "translate/news/hello",
+            &[FileChange::rename("sources/news/missing.md", "translated/news/missing.md")],
+            "[翻译][新闻] Missing",
+        ).await;
+
+        assert!(matches!(result, Err(Error::NotFound(_))));
+    }
+}
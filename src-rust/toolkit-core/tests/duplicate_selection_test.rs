@@ -0,0 +1,224 @@
+use base64::Engine;
+use octocrab::Octocrab;
+use serde_json::json;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+use mock_error::setup_error_handler;
+
+mod mock_error;
+
+// The blob SHAs of the article on the base branch and of the article added by the pull request.
+const BASE_ARTICLE_SHA: &str = "3a0f86fb8db8eea7ccbb9a95f325ddbedfb25e15";
+const PR_ARTICLE_SHA: &str = "6b584e8ece562ebffc15d38808cd6b98fc3d97ea";
+
+// This function sets up the mock server with a base branch and an open pull request adding an article each.
+async fn setup_api(base_article: &str, pr_article: &str) -> MockServer {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/Hello-World/git/trees/main"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sha": "9fb037999f264ba9a7fc6274d15fa3ae2ab98312",
+            "url": "https://api.github.com/repos/octocat/Hello-World/git/trees/9fb037999f264ba9a7fc6274d15fa3ae2ab98312",
+            "tree": [
+                { "path": "README.md", "mode": "100644", "type": "blob", "size": 13, "sha": "980a0d5f19a64b4b30a87d4206aade58726b60e3" },
+                { "path": "sources", "mode": "040000", "type": "tree", "sha": "f484d249c660418515fb01c2b9662073663c242e" },
+                { "path": "sources/news", "mode": "040000", "type": "tree", "sha": "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391" },
+                { "path": "sources/news/20240101-hello.md", "mode": "100644", "type": "blob", "size": 42, "sha": BASE_ARTICLE_SHA },
+            ],
+            "truncated": false,
+        })))
+        .mount(&mock_server)
+        .await;
+    let pull_request: serde_json::Value = serde_json::from_str(include_str!("resources/pull_request.json")).unwrap();
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/Hello-World/pulls"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([pull_request])))
+        .mount(&mock_server)
+        .await;
+    // Only the files changed by the pull request are listed, instead of the whole tree of its head.
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/Hello-World/pulls/1347/files"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {
+                "sha": PR_ARTICLE_SHA,
+                "filename": "sources/news/20240715-hello.md",
+                "status": "added",
+                "additions": 9,
+                "deletions": 0,
+                "changes": 9,
+                "blob_url": null,
+                "raw_url": null,
+                "contents_url": "https://api.github.com/repos/FOSScope/Hello-World/contents/sources/news/20240715-hello.md",
+            },
+            {
+                "sha": "980a0d5f19a64b4b30a87d4206aade58726b60e3",
+                "filename": "sources/news/20231231-removed.md",
+                "status": "removed",
+                "additions": 0,
+                "deletions": 9,
+                "changes": 9,
+                "blob_url": null,
+                "raw_url": null,
+                "contents_url": "https://api.github.com/repos/FOSScope/Hello-World/contents/sources/news/20231231-removed.md",
+            },
+        ])))
+        .mount(&mock_server)
+        .await;
+    // Every article is read once, however many times the upstream repository is searched.
+    for (repo, sha, article) in [("octocat", BASE_ARTICLE_SHA, base_article), ("FOSScope", PR_ARTICLE_SHA, pr_article)] {
+        Mock::given(method("GET"))
+            .and(path(format!("/repos/{}/Hello-World/git/blobs/{}", repo, sha)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "sha": sha,
+                "size": article.len(),
+                "url": format!("https://api.github.com/repos/{}/Hello-World/git/blobs/{}", repo, sha),
+                "content": base64::prelude::BASE64_STANDARD.encode(article),
+                "encoding": "base64",
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+    }
+    setup_error_handler(&mock_server, "GET on the tree, pull requests or blobs was not received").await;
+
+    mock_server
+}
+
+// This function sets up the Octocrab client with the base URI of the mock server.
+fn setup_octocrab(uri: &str) -> Octocrab {
+    Octocrab::builder().base_uri(uri).unwrap().build().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use fosscopetoolkit_core::apis::{Forge, GitHubApi, SandboxForge};
+    use fosscopetoolkit_core::models::{GitHubRepo, RepoRule};
+    use fosscopetoolkit_core::models::repo_rule::get_repo_rule;
+    use fosscopetoolkit_core::workflow::translate::select::{
+        ArticleIndex, canonical_url, ExistingSelection, find_existing_selections, front_matter_via, submit,
+    };
+
+    use super::*;
+
+    const ARTICLE: &str = r#"---
+title: Hello
+authorInfo: |
+  via: http://www.example.com/news/hello/?utm_source=rss
+
+  选题：[sandbox](https://github.com/sandbox)
+---
+
+Hello
+"#;
+
+    fn upstream() -> GitHubRepo {
+        GitHubRepo::new("FOSScope".to_string(), "TranslateProject".to_string())
+    }
+
+    fn fork() -> GitHubRepo {
+        GitHubRepo::new("sandbox".to_string(), "TranslateProject".to_string())
+    }
+
+    #[test]
+    fn canonical_urls() {
+        assert_eq!(canonical_url("https://example.com/news/hello"), "example.com/news/hello");
+        assert_eq!(canonical_url(" http://WWW.Example.com/news/hello/#comments "), "example.com/news/hello");
+        assert_eq!(
+            canonical_url("https://example.com/news/hello?utm_source=rss&id=1&fbclid=abc"),
+            "example.com/news/hello?id=1",
+        );
+        assert_eq!(canonical_url("https://example.com:8080/news"), "example.com:8080/news");
+        assert_ne!(canonical_url("https://example.com/news/hello"), canonical_url("https://example.com/news/hello-2"));
+    }
+
+    #[test]
+    fn via_of_front_matter() {
+        assert_eq!(front_matter_via(ARTICLE).as_deref(), Some("http://www.example.com/news/hello/?utm_source=rss"));
+        assert_eq!(front_matter_via("---\nvia: \"https://example.com\"\n---\n").as_deref(), Some("https://example.com"));
+        // Only the front matter is searched.
+        assert_eq!(front_matter_via("---\ntitle: Hello\n---\n\nvia: https://example.com\n"), None);
+        assert_eq!(front_matter_via("via: https://example.com\n"), None);
+    }
+
+    #[tokio::test]
+    async fn existing_selections() {
+        let sandbox = SandboxForge::seeded("sandbox".to_string());
+        sandbox.create_fork(&fork(), &upstream()).await.unwrap();
+        let repo_rule = get_repo_rule(&upstream(), &sandbox).await.unwrap();
+        let via = "https://example.com/news/hello";
+        let mut index = ArticleIndex::default();
+
+        assert!(find_existing_selections(&sandbox, &upstream(), &repo_rule, via, &mut index).await.unwrap().is_empty());
+
+        // Someone is selecting the article in a pull request.
+        let vars = HashMap::from([
+            ("type_name", "news".to_string()),
+            ("type_desc", "新闻".to_string()),
+            ("article_title", "Hello".to_string()),
+            ("article_id", "20240715-hello".to_string()),
+        ]);
+        submit::commit(
+            &sandbox, &upstream(), &fork(), "20240715-hello.md", ARTICLE, &repo_rule.articles[0], &repo_rule, &vars,
+        ).await.unwrap();
+        let pr = submit::create_pr(&sandbox, &upstream(), &fork(), &repo_rule, &vars).await.unwrap();
+        let selections = find_existing_selections(&sandbox, &upstream(), &repo_rule, via, &mut index).await.unwrap();
+        assert_eq!(selections, vec![ExistingSelection {
+            path: "sources/news/20240715-hello.md".to_string(),
+            pull_request: Some(pr),
+        }]);
+        assert!(find_existing_selections(&sandbox, &upstream(), &repo_rule, "https://example.com/news/other", &mut index)
+            .await.unwrap().is_empty());
+
+        // The article has been selected.
+        sandbox.merge_pull_request(&upstream(), 1).unwrap();
+        let selections = find_existing_selections(&sandbox, &upstream(), &repo_rule, via, &mut index).await.unwrap();
+        assert_eq!(selections, vec![ExistingSelection {
+            path: "sources/news/20240715-hello.md".to_string(),
+            pull_request: None,
+        }]);
+    }
+
+    #[tokio::test]
+    async fn indexed_articles_are_not_read_again() {
+        let other = ARTICLE.replace("example.com/news/hello", "example.com/news/other");
+        let mock_server = setup_api(&other, ARTICLE).await;
+        let github = GitHubApi::new("FOSScope".to_string(), setup_octocrab(&mock_server.uri()));
+        let repo_rule: RepoRule = toml::from_str(r#"
+            article_template = "{{content}}"
+
+            [[articles]]
+            type = "news"
+            description = "新闻"
+            directory = "{{step}}/news"
+
+            [git]
+            base = "main"
+            branch_naming = "{{action_name}}/{{type_name}}/{{article_id}}"
+            commit_message = "[{{action_desc}}][{{type_desc}}]: {{article_title}}"
+        "#).unwrap();
+        let upstream = GitHubRepo::new("octocat".to_string(), "Hello-World".to_string());
+        let mut index = ArticleIndex::default();
+
+        for _ in 0..2 {
+            let selections = find_existing_selections(&github, &upstream, &repo_rule, "https://example.com/news/hello", &mut index)
+                .await
+                .unwrap();
+            assert_eq!(selections.len(), 1);
+            assert_eq!(selections[0].path, "sources/news/20240715-hello.md");
+            assert_eq!(selections[0].pull_request.as_ref().map(|pr| pr.number), Some(1347));
+        }
+        let selections = find_existing_selections(&github, &upstream, &repo_rule, "https://example.com/news/other", &mut index)
+            .await
+            .unwrap();
+        assert_eq!(selections, vec![ExistingSelection {
+            path: "sources/news/20240101-hello.md".to_string(),
+            pull_request: None,
+        }]);
+    }
+}
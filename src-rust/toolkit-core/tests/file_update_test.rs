@@ -0,0 +1,138 @@
+use octocrab::Octocrab;
+use serde_json::json;
+use wiremock::{
+    matchers::{body_partial_json, method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+use mock_error::setup_error_handler;
+
+mod mock_error;
+
+// This function sets up the mock server with the file on the branch, and the endpoints to update and delete it.
+// Only requests carrying the current SHA of the file are accepted, others are rejected with 409 Conflict.
+async fn setup_api() -> MockServer {
+    let mock_server = MockServer::start().await;
+
+    let mocked_path = "/repos/octocat/Hello-World/contents/README";
+
+    Mock::given(method("GET"))
+        .and(path(mocked_path))
+        .and(query_param("ref", "select/news/hello"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::from_str::<serde_json::Value>(include_str!("resources/get_file.json")).unwrap())
+        )
+        .mount(&mock_server)
+        .await;
+    for request_method in ["PUT", "DELETE"] {
+        Mock::given(method(request_method))
+            .and(path(mocked_path))
+            .and(body_partial_json(json!({
+                "sha": "980a0d5f19a64b4b30a87d4206aade58726b60e3",
+                "branch": "select/news/hello",
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::from_str::<serde_json::Value>(include_str!("resources/file_update.json")).unwrap())
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method(request_method))
+            .and(path(mocked_path))
+            .respond_with(ResponseTemplate::new(409).set_body_json(json!({
+                "message": "README does not match 980a0d5f19a64b4b30a87d4206aade58726b60e3",
+                "documentation_url": "https://docs.github.com/rest/repos/contents#create-or-update-file-contents",
+            })))
+            .mount(&mock_server)
+            .await;
+    }
+    setup_error_handler(
+        &mock_server,
+        &format!("GET on {mocked_path} was not received"),
+    ).await;
+
+    mock_server
+}
+
+// This function sets up the Octocrab client with the base URI of the mock server.
+fn setup_octocrab(uri: &str) -> Octocrab {
+    Octocrab::builder().base_uri(uri).unwrap().build().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use fosscopetoolkit_core::apis::GitHubApi;
+    use fosscopetoolkit_core::Error;
+    use fosscopetoolkit_core::models::GitHubRepo;
+
+    use super::*;
+
+    fn repo() -> GitHubRepo {
+        GitHubRepo::new("octocat".to_string(), "Hello-World".to_string())
+    }
+
+    #[tokio::test]
+    async fn get_file_sha() {
+        let mock_server = setup_api().await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("octocat".to_string(), client);
+        let result = github.get_file_sha(&repo(), "select/news/hello", "README").await.unwrap();
+
+        assert_eq!(result, "980a0d5f19a64b4b30a87d4206aade58726b60e3");
+    }
+
+    #[tokio::test]
+    async fn update_file() {
+        let mock_server = setup_api().await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("octocat".to_string(), client);
+        let result = github.update_file(
+            &repo(),
+            "select/news/hello",
+            "README",
+            "Hello FOSScope!",
+            "980a0d5f19a64b4b30a87d4206aade58726b60e3",
+            "Update README",
+        ).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn update_file_with_outdated_sha() {
+        let mock_server = setup_api().await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("octocat".to_string(), client);
+        let result = github.update_file(
+            &repo(),
+            "select/news/hello",
+            "README",
+            "Hello FOSScope!",
+            "0000000000000000000000000000000000000000",
+            "Update README",
+        ).await;
+
+        assert!(matches!(result, Err(Error::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn delete_file() {
+        let mock_server = setup_api().await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("octocat".to_string(), client);
+        let result = github.delete_file(
+            &repo(),
+            "select/news/hello",
+            "README",
+            "980a0d5f19a64b4b30a87d4206aade58726b60e3",
+            "Delete README",
+        ).await;
+
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,124 @@
+use octocrab::Octocrab;
+use serde_json::json;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+use mock_error::setup_error_handler;
+
+mod mock_error;
+
+// This function sets up the mock server with the comparison between the fork and the upstream repository,
+// and the merge upstream endpoint of the fork responding with the given template.
+async fn setup_api(merge_upstream_template: ResponseTemplate) -> MockServer {
+    let mock_server = MockServer::start().await;
+
+    let mocked_path = "/repos/octocat/Hello-World/compare/main...FOSScope:main";
+
+    Mock::given(method("GET"))
+        .and(path(mocked_path))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::from_str::<serde_json::Value>(include_str!("resources/compare.json")).unwrap())
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/repos/FOSScope/Hello-World/merge-upstream"))
+        .respond_with(merge_upstream_template)
+        .mount(&mock_server)
+        .await;
+    setup_error_handler(
+        &mock_server,
+        &format!("GET on {mocked_path} was not received"),
+    ).await;
+
+    mock_server
+}
+
+// This function sets up the Octocrab client with the base URI of the mock server.
+fn setup_octocrab(uri: &str) -> Octocrab {
+    Octocrab::builder().base_uri(uri).unwrap().build().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use fosscopetoolkit_core::apis::GitHubApi;
+    use fosscopetoolkit_core::Error;
+    use fosscopetoolkit_core::models::{ForkStatus, GitHubRepo, TokenCapability};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn fork_status() {
+        let mock_server = setup_api(ResponseTemplate::new(200)).await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let result = github.fork_status(
+            &GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string()),
+            &GitHubRepo::new("octocat".to_string(), "Hello-World".to_string()),
+            "main",
+        ).await.unwrap();
+
+        assert_eq!(result, ForkStatus::new(1, 2));
+        assert!(!result.is_up_to_date());
+        assert!(result.has_diverged());
+    }
+
+    #[tokio::test]
+    async fn sync_fork() {
+        let template = ResponseTemplate::new(200).set_body_json(json!({
+            "message": "Successfully fetched and fast-forwarded from upstream octocat:main.",
+            "merge_type": "fast-forward",
+            "base_branch": "octocat:main",
+        }));
+        let mock_server = setup_api(template).await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let result = github.sync_fork(
+            &GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string()),
+            "main",
+        ).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn sync_diverged_fork() {
+        let template = ResponseTemplate::new(409).set_body_json(json!({
+            "message": "There are merge conflicts",
+            "documentation_url": "https://docs.github.com/rest/branches/branches#sync-a-fork-branch-with-the-upstream-repository",
+        }));
+        let mock_server = setup_api(template).await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let result = github.sync_fork(
+            &GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string()),
+            "main",
+        ).await;
+
+        assert!(matches!(result, Err(Error::ForkDiverged(_))));
+    }
+
+    #[tokio::test]
+    async fn sync_fork_without_workflow_scope() {
+        let template = ResponseTemplate::new(422).set_body_json(json!({
+            "message": "refusing to allow a Personal Access Token to create or update workflow `.github/workflows/check.yml` without `workflow` scope",
+            "documentation_url": "https://docs.github.com/rest/branches/branches#sync-a-fork-branch-with-the-upstream-repository",
+        }));
+        let mock_server = setup_api(template).await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let result = github.sync_fork(
+            &GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string()),
+            "main",
+        ).await;
+
+        assert!(matches!(result, Err(Error::MissingPermissions(missing)) if missing == vec![TokenCapability::Workflow]));
+    }
+}
@@ -0,0 +1,625 @@
+use serde_json::json;
+use wiremock::{
+    matchers::{body_json, body_partial_json, header, method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+use mock_error::setup_error_handler;
+
+mod mock_error;
+
+fn repository(owner: &str, name: &str, parent: Option<serde_json::Value>) -> serde_json::Value {
+    json!({
+        "name": name,
+        "full_name": format!("{}/{}", owner, name),
+        "owner": { "login": owner },
+        "fork": parent.is_some(),
+        "parent": parent,
+        "default_branch": "main",
+    })
+}
+
+// This function sets up the mock server as a Forgejo instance hosting a mirror of the translation project.
+async fn setup_api() -> MockServer {
+    let mock_server = MockServer::start().await;
+
+    let mocked_path = "/api/v1/user/repos";
+    let upstream = repository("FOSScope", "TranslateProject", None);
+
+    Mock::given(method("GET"))
+        .and(path(mocked_path))
+        .and(header("authorization", "token 0123456789abcdef"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            repository("octocat", "Hello-World", Some(repository("someone", "Hello-World", None))),
+            repository("FOSScope-Translators", "TranslateProject", Some(upstream.clone())),
+            repository("octocat", "TranslateProject-mirror", Some(upstream.clone())),
+        ])))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/repos/octocat/Hello-World"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(
+            repository("octocat", "Hello-World", Some(repository("someone", "Hello-World", None)))
+        ))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/repos/FOSScope/TranslateProject/branches/main"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "main",
+            "commit": { "id": "7fd1a60b01f91b314f59955a4e4d4e80d8edf11d" },
+        })))
+        .mount(&mock_server)
+        .await;
+    // The fork has the head of the upstream base branch already.
+    Mock::given(method("GET"))
+        .and(path("/api/v1/repos/octocat/TranslateProject-mirror/git/commits/7fd1a60b01f91b314f59955a4e4d4e80d8edf11d"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sha": "7fd1a60b01f91b314f59955a4e4d4e80d8edf11d",
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/repos/octocat/TranslateProject-mirror/branches"))
+        .and(body_partial_json(json!({
+            "new_branch_name": "select/news/hello",
+            "old_ref_name": "7fd1a60b01f91b314f59955a4e4d4e80d8edf11d",
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "name": "select/news/hello",
+            "commit": { "id": "7fd1a60b01f91b314f59955a4e4d4e80d8edf11d" },
+        })))
+        .mount(&mock_server)
+        .await;
+    // The source article exists, the translated article does not exist yet.
+    Mock::given(method("GET"))
+        .and(path("/api/v1/repos/octocat/TranslateProject-mirror/contents/sources/news/hello.md"))
+        .and(query_param("ref", "select/news/hello"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "name": "hello.md",
+            "path": "sources/news/hello.md",
+            "sha": "45b983be36b73c0788dc9cbcb76cbb80fc7bb057",
+            "type": "file",
+            "encoding": "base64",
+            "content": "SGVsbG8g\nV29ybGQhCg==",
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/repos/octocat/TranslateProject-mirror/contents/translated/news/image.png"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({ "message": "GetContentsOrList" })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/repos/octocat/TranslateProject-mirror/contents"))
+        .and(body_partial_json(json!({
+            "branch": "select/news/hello",
+            "message": "[翻译][新闻] Hello",
+            "files": [
+                { "operation": "create", "path": "translated/news/image.png", "content": "aW1hZ2U=" },
+                {
+                    "operation": "update",
+                    "from_path": "sources/news/hello.md",
+                    "path": "translated/news/hello.md",
+                    "content": "SGVsbG8gV29ybGQhCg==",
+                    "sha": "45b983be36b73c0788dc9cbcb76cbb80fc7bb057",
+                },
+            ],
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "commit": { "sha": "7638417db6d59f3c431d3e1f261cc637155684cd" },
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/repos/FOSScope/TranslateProject/pulls"))
+        .and(body_partial_json(json!({
+            "title": "WIP: [选题][新闻] Hello",
+            "head": "octocat:select/news/hello",
+            "base": "main",
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "number": 42,
+            "html_url": "https://forgejo.example.com/FOSScope/TranslateProject/pulls/42",
+            "title": "WIP: [选题][新闻] Hello",
+            "state": "open",
+            "merged": false,
+            "draft": true,
+            "head": {
+                "ref": "select/news/hello",
+                "sha": "7638417db6d59f3c431d3e1f261cc637155684cd",
+                "repo": repository("octocat", "TranslateProject-mirror", Some(upstream.clone())),
+            },
+            "base": { "ref": "main", "sha": "7fd1a60b01f91b314f59955a4e4d4e80d8edf11d", "repo": upstream },
+        })))
+        .mount(&mock_server)
+        .await;
+    setup_error_handler(
+        &mock_server,
+        &format!("GET on {mocked_path} was not received"),
+    ).await;
+
+    mock_server
+}
+
+#[cfg(test)]
+mod tests {
+    use fosscopetoolkit_core::apis::{Forge, GiteaApi};
+    use fosscopetoolkit_core::Error;
+    use fosscopetoolkit_core::models::{
+        Branch, CheckRun, FileChange, ForkStatus, GitHubRepo, PullRequestFile, PullRequestState, ReviewDecision,
+    };
+
+    use super::*;
+
+    fn setup_gitea(uri: &str) -> GiteaApi {
+        GiteaApi::new("octocat".to_string(), uri.to_string(), "0123456789abcdef".to_string())
+    }
+
+    #[tokio::test]
+    async fn gitea_user_forks() {
+        let mock_server = setup_api().await;
+        let gitea = setup_gitea(&mock_server.uri());
+
+        let result = gitea.get_user_forks(
+            &GitHubRepo::new("FOSScope".to_string(), "TranslateProject".to_string()),
+        ).await.unwrap();
+
+        assert_eq!(result, vec![
+            GitHubRepo::new("octocat".to_string(), "TranslateProject-mirror".to_string()),
+            GitHubRepo::new("FOSScope-Translators".to_string(), "TranslateProject".to_string()),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn gitea_wrong_parent() {
+        let mock_server = setup_api().await;
+        let gitea = setup_gitea(&mock_server.uri());
+
+        let result = gitea.validate_repo(
+            &GitHubRepo::new("octocat".to_string(), "Hello-World".to_string()),
+            &GitHubRepo::new("FOSScope".to_string(), "TranslateProject".to_string()),
+        ).await;
+
+        assert!(matches!(result, Err(Error::WrongParent { .. })));
+    }
+
+    #[tokio::test]
+    async fn gitea_submit() {
+        let mock_server = setup_api().await;
+        let gitea = setup_gitea(&mock_server.uri());
+        let upstream = GitHubRepo::new("FOSScope".to_string(), "TranslateProject".to_string());
+        let contributor = GitHubRepo::new("octocat".to_string(), "TranslateProject-mirror".to_string());
+
+        gitea.create_reference_from(&contributor, "select/news/hello", &upstream, "main").await.unwrap();
+        let sha = gitea.commit_files(
+            &contributor,
+            "select/news/hello",
+            &[
+                FileChange::write("translated/news/image.png", "image"),
+                FileChange::rename("sources/news/hello.md", "translated/news/hello.md"),
+            ],
+            "[翻译][新闻] Hello",
+        ).await.unwrap();
+        assert_eq!(sha, "7638417db6d59f3c431d3e1f261cc637155684cd");
+
+        let result = gitea.create_pull_request(
+            &upstream, &contributor, "[选题][新闻] Hello", "", "select/news/hello", "main", true,
+        ).await.unwrap();
+        assert_eq!(result.number, 42);
+        assert_eq!(result.state, PullRequestState::Open);
+        assert!(result.draft);
+        assert_eq!(result.head_repo, Some(contributor));
+    }
+
+    #[tokio::test]
+    async fn gitea_branch_not_found() {
+        let mock_server = setup_api().await;
+        let gitea = setup_gitea(&mock_server.uri());
+
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/FOSScope/TranslateProject/branches/missing"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({ "message": "branch does not exist" })))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        let result = gitea.get_branch_sha(
+            &GitHubRepo::new("FOSScope".to_string(), "TranslateProject".to_string()),
+            "missing",
+        ).await;
+
+        assert!(matches!(result, Err(Error::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn gitea_branch_pull_requests() {
+        let mock_server = setup_api().await;
+        let gitea = setup_gitea(&mock_server.uri());
+        let upstream = repository("FOSScope", "TranslateProject", None);
+        let pull_request = |number: u64, owner: &str, head: &str, state: &str| json!({
+            "number": number,
+            "html_url": format!("https://forgejo.example.com/FOSScope/TranslateProject/pulls/{}", number),
+            "title": "[选题][新闻] Hello",
+            "state": state,
+            "merged": false,
+            "head": {
+                "ref": head,
+                "sha": "7638417db6d59f3c431d3e1f261cc637155684cd",
+                "repo": repository(owner, "TranslateProject-mirror", Some(upstream.clone())),
+            },
+            "base": { "ref": "main", "sha": "7fd1a60b01f91b314f59955a4e4d4e80d8edf11d", "repo": upstream.clone() },
+        });
+
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/FOSScope/TranslateProject/pulls"))
+            .and(query_param("state", "all"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                pull_request(41, "octocat", "select/news/hello", "closed"),
+                pull_request(42, "octocat", "select/news/other", "open"),
+                pull_request(43, "someone", "select/news/hello", "open"),
+                pull_request(44, "octocat", "select/news/hello", "open"),
+            ])))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        let result = gitea.list_branch_pull_requests(
+            &GitHubRepo::new("FOSScope".to_string(), "TranslateProject".to_string()),
+            &GitHubRepo::new("octocat".to_string(), "TranslateProject-mirror".to_string()),
+            "select/news/hello",
+        ).await.unwrap();
+
+        let numbers: Vec<(u64, PullRequestState)> = result.iter().map(|pr| (pr.number, pr.state)).collect();
+        assert_eq!(numbers, vec![(44, PullRequestState::Open), (41, PullRequestState::Closed)]);
+    }
+
+    #[tokio::test]
+    async fn gitea_add_assignees() {
+        let mock_server = setup_api().await;
+        let gitea = setup_gitea(&mock_server.uri());
+
+        // A maintainer has assigned the pull request already.
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/FOSScope/TranslateProject/issues/42"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "number": 42,
+                "assignees": [{ "login": "maintainer" }, { "login": "octocat" }],
+            })))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/api/v1/repos/FOSScope/TranslateProject/issues/42"))
+            .and(body_json(json!({ "assignees": ["maintainer", "octocat", "translator"] })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({ "number": 42 })))
+            .expect(1)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+
+        gitea.add_assignees(
+            &GitHubRepo::new("FOSScope".to_string(), "TranslateProject".to_string()),
+            42,
+            &["OctoCat".to_string(), "translator".to_string()],
+        ).await.unwrap();
+    }
+
+    // A pull request from the fork of the user, as returned by Gitea and Forgejo.
+    fn fork_pull_request(number: u64, title: &str) -> serde_json::Value {
+        let upstream = repository("FOSScope", "TranslateProject", None);
+        json!({
+            "number": number,
+            "html_url": format!("https://forgejo.example.com/FOSScope/TranslateProject/pulls/{}", number),
+            "title": title,
+            "state": "open",
+            "merged": false,
+            "mergeable": true,
+            "head": {
+                "ref": "select/news/hello",
+                "sha": "7638417db6d59f3c431d3e1f261cc637155684cd",
+                "repo": repository("octocat", "TranslateProject-mirror", Some(upstream.clone())),
+            },
+            "base": { "ref": "main", "sha": "7fd1a60b01f91b314f59955a4e4d4e80d8edf11d", "repo": upstream },
+        })
+    }
+
+    #[tokio::test]
+    async fn gitea_pull_request_files() {
+        let mock_server = setup_api().await;
+        let gitea = setup_gitea(&mock_server.uri());
+        let upstream = GitHubRepo::new("FOSScope".to_string(), "TranslateProject".to_string());
+
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/FOSScope/TranslateProject/pulls"))
+            .and(query_param("state", "open"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                fork_pull_request(41, "[选题][新闻] Hello"),
+                fork_pull_request(42, "[选题][新闻] World"),
+            ])))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/FOSScope/TranslateProject/pulls/41"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fork_pull_request(41, "[选题][新闻] Hello")))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/FOSScope/TranslateProject/pulls/41/files"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "filename": "sources/news/hello.md", "status": "added" },
+                { "filename": "sources/news/old.md", "status": "deleted" },
+            ])))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        // The blob SHA of the file is read from the head commit of the pull request.
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/octocat/TranslateProject-mirror/contents/sources/news/hello.md"))
+            .and(query_param("ref", "7638417db6d59f3c431d3e1f261cc637155684cd"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "name": "hello.md",
+                "path": "sources/news/hello.md",
+                "sha": "45b983be36b73c0788dc9cbcb76cbb80fc7bb057",
+                "type": "file",
+            })))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+
+        let numbers: Vec<u64> = gitea.list_open_pull_requests(&upstream).await.unwrap()
+            .iter().map(|pr| pr.number).collect();
+        assert_eq!(numbers, vec![42, 41]);
+        assert_eq!(gitea.list_pull_request_files(&upstream, 41).await.unwrap(), vec![
+            PullRequestFile { path: "sources/news/hello.md".to_string(), sha: Some("45b983be36b73c0788dc9cbcb76cbb80fc7bb057".to_string()) },
+            PullRequestFile { path: "sources/news/old.md".to_string(), sha: None },
+        ]);
+    }
+
+    #[tokio::test]
+    async fn gitea_user_pull_requests() {
+        let mock_server = setup_api().await;
+        let gitea = setup_gitea(&mock_server.uri());
+
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/issues/search"))
+            .and(query_param("type", "pulls"))
+            .and(query_param("created", "true"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "number": 7, "assignees": null, "repository": { "full_name": "octocat/Hello-World" } },
+                { "number": 41, "assignees": null, "repository": { "full_name": "FOSScope/TranslateProject" } },
+            ])))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/FOSScope/TranslateProject/pulls/41"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fork_pull_request(41, "[选题][新闻] Hello")))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+
+        let result = gitea.list_user_pull_requests(
+            &GitHubRepo::new("FOSScope".to_string(), "TranslateProject".to_string()),
+        ).await.unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].number, 41);
+        assert_eq!(result[0].head, "select/news/hello");
+    }
+
+    #[tokio::test]
+    async fn gitea_pull_request_status() {
+        let mock_server = setup_api().await;
+        let gitea = setup_gitea(&mock_server.uri());
+        let upstream = GitHubRepo::new("FOSScope".to_string(), "TranslateProject".to_string());
+
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/FOSScope/TranslateProject/pulls/41"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fork_pull_request(41, "WIP: [选题][新闻] Hello")))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        // The change request has been followed by an approval of the same reviewer.
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/FOSScope/TranslateProject/pulls/41/reviews"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "user": { "login": "maintainer" }, "state": "REQUEST_CHANGES" },
+                { "user": { "login": "someone" }, "state": "COMMENT" },
+                { "user": { "login": "maintainer" }, "state": "APPROVED" },
+            ])))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/FOSScope/TranslateProject/commits/7638417db6d59f3c431d3e1f261cc637155684cd/status"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "state": "pending",
+                "statuses": [
+                    { "context": "lint", "status": "success" },
+                    { "context": "build", "status": "pending" },
+                ],
+            })))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/api/v1/repos/FOSScope/TranslateProject/pulls/41"))
+            .and(body_json(json!({ "title": "[选题][新闻] Hello" })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(fork_pull_request(41, "[选题][新闻] Hello")))
+            .expect(1)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+
+        let status = gitea.pull_request_status(&upstream, 41).await.unwrap();
+        assert_eq!(status.review_decision, ReviewDecision::Approved);
+        assert_eq!(status.mergeable, Some(true));
+        assert_eq!(status.checks, vec![
+            CheckRun { name: "lint".to_string(), status: "completed".to_string(), conclusion: Some("success".to_string()) },
+            CheckRun { name: "build".to_string(), status: "in_progress".to_string(), conclusion: None },
+        ]);
+
+        gitea.mark_ready_for_review(&upstream, &status.pull_request).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn gitea_clone_url() {
+        let mock_server = setup_api().await;
+        let gitea = setup_gitea(&mock_server.uri());
+
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/octocat/TranslateProject-mirror"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "name": "TranslateProject-mirror",
+                "full_name": "octocat/TranslateProject-mirror",
+                "owner": { "login": "octocat" },
+                "clone_url": "https://forgejo.example.com/octocat/TranslateProject-mirror.git",
+            })))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+
+        let url = gitea.get_clone_url(&GitHubRepo::new("octocat".to_string(), "TranslateProject-mirror".to_string())).await.unwrap();
+        assert_eq!(url, "https://forgejo.example.com/octocat/TranslateProject-mirror.git");
+    }
+
+    #[tokio::test]
+    async fn gitea_branch_from_stale_fork() {
+        let mock_server = setup_api().await;
+        let gitea = setup_gitea(&mock_server.uri());
+        let upstream = GitHubRepo::new("FOSScope".to_string(), "TranslateProject".to_string());
+        let contributor = GitHubRepo::new("octocat".to_string(), "TranslateProject-mirror".to_string());
+
+        // The fork is a repository of its own, which does not have the new commits of the upstream repository yet.
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/octocat/TranslateProject-mirror/git/commits/7fd1a60b01f91b314f59955a4e4d4e80d8edf11d"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({ "message": "object does not exist" })))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/repos/octocat/TranslateProject-mirror/merge-upstream"))
+            .and(body_partial_json(json!({ "branch": "main" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "merge_type": "fast-forward" })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        gitea.create_reference_from(&contributor, "select/news/hello", &upstream, "main").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn gitea_branches() {
+        let mock_server = setup_api().await;
+        let gitea = setup_gitea(&mock_server.uri());
+        let contributor = GitHubRepo::new("octocat".to_string(), "TranslateProject-mirror".to_string());
+
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/octocat/TranslateProject-mirror/branches"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "name": "select/news/hello", "commit": { "id": "7638417db6d59f3c431d3e1f261cc637155684cd" } },
+                { "name": "main", "commit": { "id": "7fd1a60b01f91b314f59955a4e4d4e80d8edf11d" } },
+            ])))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/api/v1/repos/octocat/TranslateProject-mirror/branches/select/news/hello"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+
+        assert_eq!(gitea.list_branches(&contributor).await.unwrap(), vec![
+            Branch::new("main".to_string(), "7fd1a60b01f91b314f59955a4e4d4e80d8edf11d".to_string()),
+            Branch::new("select/news/hello".to_string(), "7638417db6d59f3c431d3e1f261cc637155684cd".to_string()),
+        ]);
+        gitea.delete_branch(&contributor, "select/news/hello").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn gitea_tree_and_blob() {
+        let mock_server = setup_api().await;
+        let gitea = setup_gitea(&mock_server.uri());
+        let upstream = GitHubRepo::new("FOSScope".to_string(), "TranslateProject".to_string());
+        let entry = |path: &str, kind: &str, sha: &str| json!({ "path": path, "mode": "100644", "type": kind, "sha": sha, "size": 12 });
+
+        // The tree is listed in pages, every page but the last one is truncated.
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/FOSScope/TranslateProject/git/trees/7fd1a60b01f91b314f59955a4e4d4e80d8edf11d"))
+            .and(query_param("recursive", "true"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "sha": "b9d3b9b1a1c0e9a8f7d6c5b4a3928170f6e5d4c3",
+                "tree": [
+                    entry("sources", "tree", "0a1b2c3d4e5f60718293a4b5c6d7e8f901234567"),
+                    entry("sources/news", "tree", "1a1b2c3d4e5f60718293a4b5c6d7e8f901234567"),
+                ],
+                "truncated": true,
+            })))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/FOSScope/TranslateProject/git/trees/7fd1a60b01f91b314f59955a4e4d4e80d8edf11d"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "sha": "b9d3b9b1a1c0e9a8f7d6c5b4a3928170f6e5d4c3",
+                "tree": [entry("sources/news/hello.md", "blob", "45b983be36b73c0788dc9cbcb76cbb80fc7bb057")],
+                "truncated": false,
+            })))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/FOSScope/TranslateProject/git/blobs/45b983be36b73c0788dc9cbcb76cbb80fc7bb057"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "sha": "45b983be36b73c0788dc9cbcb76cbb80fc7bb057",
+                "encoding": "base64",
+                "content": "SGVsbG8g\nV29ybGQhCg==",
+                "size": 13,
+            })))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+
+        // The branch is resolved to the SHA of its head.
+        let tree = gitea.get_tree(&upstream, "main", true).await.unwrap();
+        let files: Vec<&str> = tree.files().map(|entry| entry.path.as_str()).collect();
+        assert_eq!(files, vec!["sources/news/hello.md"]);
+        assert_eq!(tree.entries.len(), 3);
+
+        let blob = gitea.get_blob(&upstream, &tree.get("sources/news/hello.md").unwrap().sha).await.unwrap();
+        assert_eq!(blob, b"Hello World!\n");
+    }
+
+    #[tokio::test]
+    async fn gitea_fork_status() {
+        let mock_server = setup_api().await;
+        let gitea = setup_gitea(&mock_server.uri());
+
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/FOSScope/TranslateProject/compare/main...octocat:main"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "total_commits": 1, "commits": [] })))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/repos/octocat/TranslateProject-mirror/compare/main...FOSScope:main"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "total_commits": 3, "commits": [] })))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+
+        let status = gitea.fork_status(
+            &GitHubRepo::new("octocat".to_string(), "TranslateProject-mirror".to_string()),
+            &GitHubRepo::new("FOSScope".to_string(), "TranslateProject".to_string()),
+            "main",
+        ).await.unwrap();
+        assert_eq!(status, ForkStatus::new(1, 3));
+        assert!(status.has_diverged());
+    }
+}
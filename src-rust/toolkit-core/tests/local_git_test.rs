@@ -0,0 +1,196 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Run a `git` command with a fixed identity, so the tests do not depend on the Git configuration of the machine.
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .env("GIT_AUTHOR_NAME", "FOSScope")
+        .env("GIT_AUTHOR_EMAIL", "toolkit@fosscope.com")
+        .env("GIT_COMMITTER_NAME", "FOSScope")
+        .env("GIT_COMMITTER_EMAIL", "toolkit@fosscope.com")
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+// This function creates a bare repository, standing in for a remote repository,
+// with an initial commit on `main`, and returns its path.
+fn setup_bare_repo(root: &Path, name: &str) -> PathBuf {
+    let bare = root.join(format!("{}.git", name));
+    let seed = root.join(format!("{}-seed", name));
+    std::fs::create_dir_all(&bare).unwrap();
+    std::fs::create_dir_all(&seed).unwrap();
+    git(&bare, &["init", "--quiet", "--bare", "--initial-branch=main"]);
+    git(&seed, &["init", "--quiet", "--initial-branch=main"]);
+    std::fs::write(seed.join("README.md"), "# Hello World\n").unwrap();
+    git(&seed, &["add", "README.md"]);
+    git(&seed, &["commit", "--quiet", "-m", "Initial commit"]);
+    git(&seed, &["push", "--quiet", bare.to_str().unwrap(), "main"]);
+    bare
+}
+
+// A unique temporary directory for each test.
+fn temp_root(test: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("fosscope-toolkit-{}-{}", test, std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&root).unwrap();
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use fosscopetoolkit_core::apis::LocalGitRepo;
+    use fosscopetoolkit_core::Error;
+    use fosscopetoolkit_core::models::{FileChange, RepoRule};
+    use fosscopetoolkit_core::models::repo_rule::{Article, GitRule};
+    use fosscopetoolkit_core::workflow::translate::select::submit;
+
+    use super::*;
+
+    async fn clone(root: &Path, bare: &Path) -> LocalGitRepo {
+        let local_repo = LocalGitRepo::clone_from(bare.to_str().unwrap(), root.join("clone")).await.unwrap();
+        git(local_repo.path(), &["config", "user.name", "FOSScope"]);
+        git(local_repo.path(), &["config", "user.email", "toolkit@fosscope.com"]);
+        local_repo
+    }
+
+    #[tokio::test]
+    async fn open_or_clone() {
+        let root = temp_root("open-or-clone");
+        let bare = setup_bare_repo(&root, "Hello-World");
+
+        assert!(LocalGitRepo::open(root.join("clone")).is_err());
+        let cloned = LocalGitRepo::open_or_clone(bare.to_str().unwrap(), root.join("clone")).await.unwrap();
+        assert!(cloned.path().join("README.md").exists());
+        // The second time, the existing clone is opened.
+        let opened = LocalGitRepo::open_or_clone("/does/not/exist", root.join("clone")).await.unwrap();
+        assert_eq!(opened.head_sha().await.unwrap(), cloned.head_sha().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn commit_and_push_changes() {
+        let root = temp_root("commit-and-push");
+        let bare = setup_bare_repo(&root, "Hello-World");
+        let local_repo = clone(&root, &bare).await;
+
+        local_repo.checkout_branch("translate/news/hello", "origin/main").await.unwrap();
+        local_repo.apply_changes(&[
+            FileChange::write("translated/news/hello.md", "Hello"),
+            FileChange::rename("README.md", "docs/README.md"),
+        ]).await.unwrap();
+        let sha = local_repo.commit("[翻译][新闻] Hello").await.unwrap();
+        local_repo.push("origin", "translate/news/hello").await.unwrap();
+
+        let bare_sha = git(&bare, &["rev-parse", "refs/heads/translate/news/hello"]);
+        assert_eq!(sha, bare_sha);
+        let files = git(&bare, &["ls-tree", "-r", "--name-only", &bare_sha]);
+        assert_eq!(files.lines().collect::<Vec<_>>(), vec!["docs/README.md", "translated/news/hello.md"]);
+        assert_eq!(git(&bare, &["log", "-1", "--format=%s", &bare_sha]), "[翻译][新闻] Hello");
+    }
+
+    #[tokio::test]
+    async fn commit_without_changes() {
+        let root = temp_root("commit-without-changes");
+        let bare = setup_bare_repo(&root, "Hello-World");
+        let local_repo = clone(&root, &bare).await;
+        let head = local_repo.head_sha().await.unwrap();
+
+        // Writing the content the file already has stages nothing.
+        local_repo.apply_changes(&[FileChange::write("README.md", "# Hello World\n")]).await.unwrap();
+        assert_eq!(local_repo.commit("Nothing changed").await.unwrap(), head);
+        assert_eq!(git(local_repo.path(), &["rev-list", "--count", "HEAD"]), "1");
+    }
+
+    #[tokio::test]
+    async fn checkout_existing_branch() {
+        let root = temp_root("checkout-existing-branch");
+        let bare = setup_bare_repo(&root, "Hello-World");
+        let local_repo = clone(&root, &bare).await;
+        let main = local_repo.head_sha().await.unwrap();
+
+        // A stale local branch, e.g. left behind by a failed attempt.
+        local_repo.checkout_branch("select/news/hello", "origin/main").await.unwrap();
+        local_repo.apply_changes(&[FileChange::write("stale.md", "Stale")]).await.unwrap();
+        local_repo.commit("Stale").await.unwrap();
+        git(local_repo.path(), &["checkout", "--quiet", "main"]);
+
+        local_repo.checkout_branch("select/news/hello", "origin/main").await.unwrap();
+        assert_eq!(git(local_repo.path(), &["rev-parse", "--abbrev-ref", "HEAD"]), "select/news/hello");
+        assert_eq!(local_repo.head_sha().await.unwrap(), main);
+        assert!(!local_repo.path().join("stale.md").exists());
+    }
+
+    #[tokio::test]
+    async fn git_errors() {
+        let root = temp_root("git-errors");
+        let bare = setup_bare_repo(&root, "Hello-World");
+        let local_repo = clone(&root, &bare).await;
+
+        assert!(!local_repo.has_ref("refs/remotes/origin/missing").await.unwrap());
+        assert!(local_repo.remote_has_branch("origin", "main").await.unwrap());
+        assert!(!local_repo.remote_has_branch("origin", "missing").await.unwrap());
+        // A remote that cannot be reached is not mistaken for a remote without the branch.
+        git(local_repo.path(), &["remote", "add", "unreachable", root.join("missing.git").to_str().unwrap()]);
+        let err = local_repo.remote_has_branch("unreachable", "main").await.unwrap_err();
+        assert_eq!(err.kind(), "Git");
+        let err = local_repo.fetch("origin", "missing").await.unwrap_err();
+        assert_eq!(err.kind(), "Git");
+
+        // A URL starting with `-` is read as the repository to clone, not as an option.
+        let err = LocalGitRepo::clone_from("--upload-pack=touch pwned", root.join("option")).await.err().unwrap();
+        assert!(matches!(err, Error::Git(message) if message.contains("'--upload-pack=touch pwned' does not exist")));
+    }
+
+    #[tokio::test]
+    async fn commit_locally() {
+        let root = temp_root("commit-locally");
+        let upstream = setup_bare_repo(&root, "Upstream");
+        let fork = setup_bare_repo(&root, "Fork");
+        let local_repo = clone(&root, &fork).await;
+
+        // The upstream repository has moved on since the fork was created.
+        let upstream_work = root.join("upstream-work");
+        git(&root, &["clone", "--quiet", upstream.to_str().unwrap(), upstream_work.to_str().unwrap()]);
+        std::fs::write(upstream_work.join("NEWS.md"), "News").unwrap();
+        git(&upstream_work, &["add", "NEWS.md"]);
+        git(&upstream_work, &["commit", "--quiet", "-m", "Add news"]);
+        git(&upstream_work, &["push", "--quiet", "origin", "main"]);
+        let upstream_head = git(&upstream, &["rev-parse", "main"]);
+
+        let news = Article::new("news".to_string(), "新闻".to_string(), "{{step}}/news".to_string(), None);
+        let git_rule = GitRule::new(
+            "main".to_string(),
+            "{{action_name}}/{{type_name}}/{{article_id}}".to_string(),
+            "[{{action_desc}}][{{type_desc}}] {{article_title}}".to_string(),
+        );
+        let repo_rule = RepoRule::new("{{content}}".to_string(), vec![news], git_rule);
+        let news = &repo_rule.articles[0];
+        let vars: HashMap<&str, String> = HashMap::from([
+            ("type_name", "news".to_string()),
+            ("type_desc", "新闻".to_string()),
+            ("article_id", "hello".to_string()),
+            ("article_title", "Hello".to_string()),
+        ]);
+
+        let sha = submit::commit_locally(
+            &local_repo, upstream.to_str().unwrap(), "hello.md", "Hello", news, &repo_rule, &vars,
+        ).await.unwrap();
+
+        // The branch is based on the head of upstream, and pushed to the fork.
+        assert_eq!(git(&fork, &["rev-parse", "refs/heads/select/news/hello"]), sha);
+        assert_eq!(git(&fork, &["rev-parse", &format!("{}^", sha)]), upstream_head);
+        assert_eq!(git(&fork, &["show", &format!("{}:sources/news/hello.md", sha)]), "Hello");
+        assert_eq!(git(&fork, &["log", "-1", "--format=%s", &sha]), "[选题][新闻] Hello");
+
+        // A follow-up revision continues the pushed branch.
+        let revision = submit::commit_locally(
+            &local_repo, upstream.to_str().unwrap(), "hello.md", "Hello, World", news, &repo_rule, &vars,
+        ).await.unwrap();
+        assert_eq!(git(&fork, &["rev-parse", &format!("{}^", revision)]), sha);
+    }
+}
@@ -6,8 +6,11 @@ mod tests {
     #[test]
     fn read_and_write_repo() {
         let repo = GitHubRepo::new("octocat".to_string(), "Hello-World".to_string());
-        set_contributor_repo(repo.clone());
-        let contributor_repo = get_contributor_repo().unwrap();
+        let upstream = GitHubRepo::new("upstream".to_string(), "Hello-World".to_string());
+        let workspace = std::env::temp_dir().join(format!("fosscope-toolkit-workspace-{}", std::process::id()));
+        assert_eq!(get_contributor_repo(&workspace, &upstream).unwrap(), None);
+        set_contributor_repo(&workspace, repo.clone(), &upstream).unwrap();
+        let contributor_repo = get_contributor_repo(&workspace, &upstream).unwrap().unwrap();
         assert_eq!(contributor_repo, repo);
     }
 }
@@ -0,0 +1,278 @@
+use octocrab::Octocrab;
+use serde_json::{json, Value};
+use wiremock::{
+    matchers::{body_partial_json, method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+use mock_error::setup_error_handler;
+
+mod mock_error;
+
+const PULLS_PATH: &str = "/repos/octocat/Hello-World/pulls";
+const PULL_PATH: &str = "/repos/octocat/Hello-World/pulls/1347";
+
+// This function returns the mocked pull request, opened by the given user.
+fn pull_request(login: &str) -> Value {
+    let mut pr = serde_json::from_str::<Value>(include_str!("resources/pull_request.json")).unwrap();
+    pr["user"]["login"] = json!(login);
+    pr
+}
+
+// This function returns a mocked review of the pull request, submitted by the given user.
+fn review(id: u64, login: &str, state: &str) -> Value {
+    let user = pull_request(login)["user"].clone();
+    json!({
+        "id": id,
+        "node_id": "MDE3OlB1bGxSZXF1ZXN0UmV2aWV3ODA=",
+        "html_url": format!("https://github.com/octocat/Hello-World/pull/1347#pullrequestreview-{id}"),
+        "user": user,
+        "state": state,
+    })
+}
+
+// This function sets up the mock server with the pull request endpoints of the upstream repository.
+async fn setup_api() -> MockServer {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path(PULLS_PATH))
+        .and(body_partial_json(json!({
+            "head": "FOSScope:select/news/hello-world",
+            "base": "main",
+            "body": "Via: https://example.com/hello-world",
+            "draft": true,
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(pull_request("FOSScope")))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(PULLS_PATH))
+        .and(query_param("state", "open"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!([pull_request("FOSScope"), pull_request("octocat")]))
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(PULL_PATH))
+        .respond_with(ResponseTemplate::new(200).set_body_json(pull_request("FOSScope")))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("{PULL_PATH}/reviews")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            review(1, "alice", "CHANGES_REQUESTED"),
+            review(2, "bob", "COMMENTED"),
+            review(3, "alice", "APPROVED"),
+        ])))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/Hello-World/commits/6dcb09b5b57875f334f61aebed695e2e4193db5e/check-runs"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "total_count": 1,
+            "check_runs": [{
+                "id": 4,
+                "name": "lint",
+                "status": "completed",
+                "conclusion": "success",
+                "html_url": "https://github.com/octocat/Hello-World/runs/4",
+            }],
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(PULL_PATH))
+        .and(body_partial_json(json!({ "state": "closed" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json({
+            let mut pr = pull_request("FOSScope");
+            pr["state"] = json!("closed");
+            pr
+        }))
+        .mount(&mock_server)
+        .await;
+    setup_error_handler(
+        &mock_server,
+        &format!("GET on {PULL_PATH} was not received"),
+    ).await;
+
+    mock_server
+}
+
+// This function sets up the Octocrab client with the base URI of the mock server.
+fn setup_octocrab(uri: &str) -> Octocrab {
+    Octocrab::builder().base_uri(uri).unwrap().build().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use fosscopetoolkit_core::apis::GitHubApi;
+    use fosscopetoolkit_core::Error;
+    use fosscopetoolkit_core::models::{GitHubRepo, PullRequestState, ReviewDecision};
+
+    use super::*;
+
+    fn upstream() -> GitHubRepo {
+        GitHubRepo::new("octocat".to_string(), "Hello-World".to_string())
+    }
+
+    #[tokio::test]
+    async fn create_pull_request() {
+        let mock_server = setup_api().await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let pr = github.create_pull_request(
+            &upstream(),
+            &GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string()),
+            "[选题] Hello World",
+            "Via: https://example.com/hello-world",
+            "select/news/hello-world",
+            "main",
+            true,
+        ).await.unwrap();
+
+        assert_eq!(pr.number, 1347);
+        assert_eq!(pr.url, "https://github.com/octocat/Hello-World/pull/1347");
+        assert_eq!(pr.head, "select/news/hello-world");
+        assert_eq!(pr.head_repo, Some(GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string())));
+        assert_eq!(pr.state, PullRequestState::Open);
+        assert!(pr.draft);
+    }
+
+    #[tokio::test]
+    async fn add_labels_reviewers_and_assignees() {
+        let mock_server = setup_api().await;
+        Mock::given(method("POST"))
+            .and(path("/repos/octocat/Hello-World/issues/1347/labels"))
+            .and(body_partial_json(json!({ "labels": ["选题"] })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!("{PULL_PATH}/requested_reviewers")))
+            .and(body_partial_json(json!({ "reviewers": ["alice"] })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(pull_request("FOSScope")))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/repos/octocat/Hello-World/issues/1347/assignees"))
+            .and(body_partial_json(json!({ "assignees": ["bob"] })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({})))
+            .mount(&mock_server)
+            .await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        assert!(github.add_labels(&upstream(), 1347, &["选题".to_string()]).await.is_ok());
+        assert!(github.request_reviewers(&upstream(), 1347, &["alice".to_string()]).await.is_ok());
+        assert!(github.add_assignees(&upstream(), 1347, &["bob".to_string()]).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn list_user_pull_requests() {
+        let mock_server = setup_api().await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let prs = github.list_user_pull_requests(&upstream()).await.unwrap();
+
+        assert_eq!(prs.len(), 1);
+        assert_eq!(prs[0].number, 1347);
+    }
+
+    #[tokio::test]
+    async fn list_branch_pull_requests() {
+        let mock_server = setup_api().await;
+        let mut merged = pull_request("FOSScope");
+        merged["state"] = json!("closed");
+        merged["merged_at"] = json!("2011-01-26T19:01:12Z");
+        Mock::given(method("GET"))
+            .and(path(PULLS_PATH))
+            .and(query_param("state", "all"))
+            .and(query_param("head", "FOSScope:select/news/hello-world"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([merged])))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let prs = github.list_branch_pull_requests(
+            &upstream(),
+            &GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string()),
+            "select/news/hello-world",
+        ).await.unwrap();
+
+        assert_eq!(prs.len(), 1);
+        assert_eq!(prs[0].state, PullRequestState::Merged);
+    }
+
+    #[tokio::test]
+    async fn pull_request_status() {
+        let mock_server = setup_api().await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let status = github.pull_request_status(&upstream(), 1347).await.unwrap();
+
+        assert_eq!(status.review_decision, ReviewDecision::Approved);
+        assert_eq!(status.mergeable, Some(true));
+        assert_eq!(status.mergeable_state.as_deref(), Some("clean"));
+        assert_eq!(status.checks.len(), 1);
+        assert_eq!(status.checks[0].conclusion.as_deref(), Some("success"));
+    }
+
+    #[tokio::test]
+    async fn close_pull_request() {
+        let mock_server = setup_api().await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let pr = github.close_pull_request(&upstream(), 1347).await.unwrap();
+
+        assert_eq!(pr.state, PullRequestState::Closed);
+    }
+
+    #[tokio::test]
+    async fn mark_ready_for_review() {
+        let mock_server = setup_api().await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .and(body_partial_json(json!({ "variables": { "id": "MDExOlB1bGxSZXF1ZXN0MQ==" } })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "markPullRequestReadyForReview": { "pullRequest": { "isDraft": false } } },
+            })))
+            .mount(&mock_server)
+            .await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let pr = github.get_pull_request(&upstream(), 1347).await.unwrap();
+        let result = github.mark_ready_for_review(&pr).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn mark_ready_for_review_rejected() {
+        let mock_server = setup_api().await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "markPullRequestReadyForReview": null },
+                "errors": [{ "message": "Pull request is not a draft" }],
+            })))
+            .mount(&mock_server)
+            .await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let pr = github.get_pull_request(&upstream(), 1347).await.unwrap();
+        let result = github.mark_ready_for_review(&pr).await;
+
+        assert!(matches!(result, Err(Error::GraphQL(message)) if message == "Pull request is not a draft"));
+    }
+}
@@ -0,0 +1,246 @@
+use std::time::Duration;
+
+use octocrab::Octocrab;
+use octocrab::service::middleware::retry::RetryConfig;
+use serde_json::json;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+use mock_error::setup_error_handler;
+
+mod mock_error;
+
+const REF_PATH: &str = "/repos/FOSScope/Hello-World/git/ref/heads/main";
+
+// This function returns the response of the reference endpoint, with the given rate limit headers.
+fn reference_response(remaining: u64) -> ResponseTemplate {
+    ResponseTemplate::new(200)
+        .insert_header("x-ratelimit-limit", "5000")
+        .insert_header("x-ratelimit-remaining", remaining.to_string().as_str())
+        .insert_header("x-ratelimit-used", (5000 - remaining).to_string().as_str())
+        .insert_header("x-ratelimit-reset", "1721030400")
+        .set_body_json(serde_json::from_str::<serde_json::Value>(include_str!("resources/reference.json")).unwrap())
+}
+
+// This function sets up the mock server with the reference endpoint failing with the given template
+// the given number of times before succeeding.
+async fn setup_api(failure: ResponseTemplate, failures: u64) -> MockServer {
+    let mock_server = MockServer::start().await;
+
+    if failures > 0 {
+        Mock::given(method("GET"))
+            .and(path(REF_PATH))
+            .respond_with(failure)
+            .up_to_n_times(failures)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path(REF_PATH))
+        .respond_with(reference_response(4999))
+        .with_priority(2)
+        .mount(&mock_server)
+        .await;
+    setup_error_handler(
+        &mock_server,
+        &format!("GET on {REF_PATH} was not received"),
+    ).await;
+
+    mock_server
+}
+
+// This function sets up the Octocrab client with the base URI of the mock server.
+// Octocrab does not retry on its own, so only the retries of GitHubApi are made, as when signing in.
+fn setup_octocrab(uri: &str) -> Octocrab {
+    let mut builder = Octocrab::builder();
+    builder.add_retry_config(RetryConfig::None);
+    builder.base_uri(uri).unwrap().build().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use fosscopetoolkit_core::apis::{GitHubApi, RetryPolicy};
+    use fosscopetoolkit_core::Error;
+    use fosscopetoolkit_core::models::{GitHubRepo, PullRequest, RateLimit};
+
+    use super::*;
+
+    fn github(uri: &str) -> GitHubApi {
+        GitHubApi::new("FOSScope".to_string(), setup_octocrab(uri))
+            .with_retry_policy(RetryPolicy::new(2, Duration::from_millis(1), Duration::from_millis(10)))
+    }
+
+    fn fork() -> GitHubRepo {
+        GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string())
+    }
+
+    #[tokio::test]
+    async fn track_quota() {
+        let mock_server = setup_api(ResponseTemplate::new(500), 0).await;
+        let github = github(&mock_server.uri());
+
+        assert_eq!(github.rate_limit(), None);
+        github.get_branch_sha(&fork(), "main").await.unwrap();
+
+        assert_eq!(github.rate_limit(), Some(RateLimit::new(5000, 4999, 1, 1721030400)));
+    }
+
+    #[tokio::test]
+    async fn retry_server_error() {
+        let failure = ResponseTemplate::new(502).set_body_string("<html>Bad Gateway</html>");
+        let mock_server = setup_api(failure, 2).await;
+        let github = github(&mock_server.uri());
+
+        let sha = github.get_branch_sha(&fork(), "main").await;
+
+        assert!(sha.is_ok());
+    }
+
+    #[tokio::test]
+    async fn give_up_after_max_retries() {
+        let failure = ResponseTemplate::new(503).set_body_json(json!({ "message": "Service Unavailable" }));
+        let mock_server = setup_api(failure, u64::MAX).await;
+        let github = github(&mock_server.uri());
+
+        let sha = github.get_branch_sha(&fork(), "main").await;
+
+        assert!(matches!(sha, Err(Error::GitHub(_))));
+    }
+
+    #[tokio::test]
+    async fn retry_secondary_rate_limit() {
+        let failure = ResponseTemplate::new(403)
+            .insert_header("retry-after", "0")
+            .set_body_json(json!({
+                "message": "You have exceeded a secondary rate limit. Please wait a few minutes before you try again.",
+            }));
+        let mock_server = setup_api(failure, 1).await;
+        let github = github(&mock_server.uri());
+
+        let sha = github.get_branch_sha(&fork(), "main").await;
+
+        assert!(sha.is_ok());
+    }
+
+    #[tokio::test]
+    async fn quota_exhausted() {
+        let failure = ResponseTemplate::new(403)
+            .insert_header("x-ratelimit-limit", "5000")
+            .insert_header("x-ratelimit-remaining", "0")
+            .insert_header("x-ratelimit-used", "5000")
+            .insert_header("x-ratelimit-reset", "1721030400")
+            .set_body_json(json!({
+                "message": "API rate limit exceeded for user ID 1.",
+            }));
+        let mock_server = setup_api(failure, 1).await;
+        let github = github(&mock_server.uri());
+
+        let sha = github.get_branch_sha(&fork(), "main").await;
+
+        assert!(matches!(sha, Err(Error::QuotaExhausted { limit: 5000, reset: 1721030400 })));
+        assert!(github.rate_limit().unwrap().is_exhausted());
+    }
+
+    #[tokio::test]
+    async fn fetch_rate_limit() {
+        let mock_server = setup_api(ResponseTemplate::new(500), 0).await;
+        Mock::given(method("GET"))
+            .and(path("/rate_limit"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "resources": {},
+                "rate": { "limit": 5000, "remaining": 4321, "used": 679, "reset": 1721030400 },
+            })))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        let github = github(&mock_server.uri());
+
+        let rate_limit = github.fetch_rate_limit().await.unwrap();
+
+        assert_eq!(rate_limit, RateLimit::new(5000, 4321, 679, 1721030400));
+        assert_eq!(github.rate_limit(), Some(rate_limit));
+    }
+
+    // This function mounts the pull request creation endpoint failing with a server error,
+    // and the listing of the pull requests from the branch returning the given pull requests.
+    async fn setup_pull_request_creation(mock_server: &MockServer, pull_requests: serde_json::Value) {
+        // The pull request is not created again, as it may have been created before the error.
+        Mock::given(method("POST"))
+            .and(path("/repos/octocat/Hello-World/pulls"))
+            .respond_with(ResponseTemplate::new(502).set_body_json(json!({ "message": "Server Error" })))
+            .expect(1)
+            .mount(mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/octocat/Hello-World/pulls"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pull_requests))
+            .with_priority(1)
+            .mount(mock_server)
+            .await;
+    }
+
+    async fn create_pull_request(github: &GitHubApi) -> fosscopetoolkit_core::Result<PullRequest> {
+        github.create_pull_request(
+            &GitHubRepo::new("octocat".to_string(), "Hello-World".to_string()),
+            &fork(),
+            "[选题][新闻]: Hello World",
+            "",
+            "select/news/hello-world",
+            "main",
+            false,
+        ).await
+    }
+
+    #[tokio::test]
+    async fn pull_request_created_despite_error() {
+        let mock_server = setup_api(ResponseTemplate::new(500), 0).await;
+        let pull_request: serde_json::Value = serde_json::from_str(include_str!("resources/pull_request.json")).unwrap();
+        setup_pull_request_creation(&mock_server, json!([pull_request])).await;
+        let github = github(&mock_server.uri());
+
+        let pull_request = create_pull_request(&github).await.unwrap();
+
+        assert_eq!(pull_request.number, 1347);
+    }
+
+    #[tokio::test]
+    async fn pull_request_not_created() {
+        let mock_server = setup_api(ResponseTemplate::new(500), 0).await;
+        setup_pull_request_creation(&mock_server, json!([])).await;
+        let github = github(&mock_server.uri());
+
+        let pull_request = create_pull_request(&github).await;
+
+        assert!(pull_request.is_err());
+    }
+
+    #[tokio::test]
+    async fn retry_idempotent_post() {
+        let mock_server = setup_api(ResponseTemplate::new(500), 0).await;
+        Mock::given(method("POST"))
+            .and(path("/repos/octocat/Hello-World/issues/1347/labels"))
+            .respond_with(ResponseTemplate::new(502).set_body_string("<html>Bad Gateway</html>"))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/repos/octocat/Hello-World/issues/1347/labels"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .with_priority(2)
+            .mount(&mock_server)
+            .await;
+        let github = github(&mock_server.uri());
+
+        let result = github.add_labels(
+            &GitHubRepo::new("octocat".to_string(), "Hello-World".to_string()),
+            1347,
+            &["选题".to_string()],
+        ).await;
+
+        assert!(result.is_ok());
+    }
+}
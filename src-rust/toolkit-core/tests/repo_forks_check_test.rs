@@ -12,6 +12,7 @@ mod mock_error;
 enum RequestType {
     GET,
     POST,
+    GRAPHQL,
 }
 
 // This is a fake page that we can use to deserialize the response from the GitHub API.
@@ -41,6 +42,13 @@ async fn setup_api(template: ResponseTemplate, request_type: RequestType) -> Moc
                 .mount(&mock_server)
                 .await;
         }
+        RequestType::GRAPHQL => {
+            Mock::given(method("POST"))
+                .and(path("/graphql"))
+                .respond_with(template)
+                .mount(&mock_server)
+                .await;
+        }
     }
 
     setup_error_handler(
@@ -51,6 +59,31 @@ async fn setup_api(template: ResponseTemplate, request_type: RequestType) -> Moc
     mock_server
 }
 
+// This function mocks the default branch of the new fork, which is missing until the fork has been created.
+async fn setup_fork_branch(mock_server: &MockServer, not_ready_times: u64) {
+    let mocked_path = "/repos/FOSScope/Hello-World/git/ref/heads/master";
+    if not_ready_times > 0 {
+        Mock::given(method("GET"))
+            .and(path(mocked_path))
+            .respond_with(ResponseTemplate::new(409).set_body_json(serde_json::json!({
+                "message": "Git Repository is empty.",
+                "documentation_url": "https://docs.github.com/rest/git/refs#get-a-reference",
+            })))
+            .up_to_n_times(not_ready_times)
+            .with_priority(1)
+            .mount(mock_server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path(mocked_path))
+        .respond_with(ResponseTemplate::new(200).set_body_json(
+            serde_json::from_str::<serde_json::Value>(include_str!("resources/reference.json")).unwrap()
+        ))
+        .with_priority(2)
+        .mount(mock_server)
+        .await;
+}
+
 // This function sets up the Octocrab client with the base URI of the mock server.
 fn setup_octocrab(uri: &str) -> Octocrab {
     Octocrab::builder().base_uri(uri).unwrap().build().unwrap()
@@ -60,7 +93,11 @@ fn setup_octocrab(uri: &str) -> Octocrab {
 mod tests {
     use octocrab::models::Repository;
 
-    use fosscopetoolkit_core::apis::GitHubApi;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use fosscopetoolkit_core::apis::{ForkWaitPolicy, GitHubApi};
+    use fosscopetoolkit_core::Error;
     use fosscopetoolkit_core::models::GitHubRepo;
 
     use super::*;
@@ -79,17 +116,17 @@ mod tests {
                 owner: "octocat".to_string(),
                 name: "Hello-World".to_string(),
             }
-        ).await;
+        ).await.unwrap();
 
         assert_eq!(result.len(), 30);
     }
 
     #[tokio::test]
     async fn user_dont_have_fork() {
-        let mocked_response: Vec<Repository> =
-            serde_json::from_str(include_str!("resources/repo_forks.json")).unwrap();
+        let mocked_response: serde_json::Value =
+            serde_json::from_str(include_str!("resources/user_forks.json")).unwrap();
         let template = ResponseTemplate::new(200).set_body_json(&mocked_response);
-        let mock_server = setup_api(template, RequestType::GET).await;
+        let mock_server = setup_api(template, RequestType::GRAPHQL).await;
         let client = setup_octocrab(&mock_server.uri());
 
         let github = GitHubApi::new("octocat".to_string(), client);
@@ -100,15 +137,15 @@ mod tests {
             }
         ).await;
 
-        assert_eq!(result, Err("User has not forked the upstream repository."));
+        assert!(matches!(result, Err(Error::NotFound(_))));
     }
 
     #[tokio::test]
     async fn user_has_fork() {
-        let mocked_response: Vec<Repository> =
-            serde_json::from_str(include_str!("resources/repo_forks.json")).unwrap();
+        let mocked_response: serde_json::Value =
+            serde_json::from_str(include_str!("resources/user_forks.json")).unwrap();
         let template = ResponseTemplate::new(200).set_body_json(&mocked_response);
-        let mock_server = setup_api(template, RequestType::GET).await;
+        let mock_server = setup_api(template, RequestType::GRAPHQL).await;
         let client = setup_octocrab(&mock_server.uri());
 
         let github = GitHubApi::new("FOSScope".to_string(), client);
@@ -117,12 +154,42 @@ mod tests {
                 owner: "octocat".to_string(),
                 name: "Hello-World".to_string(),
             }
-        ).await;
+        ).await.unwrap();
 
-        assert_eq!(result, Ok(GitHubRepo {
+        // The fork has been renamed.
+        assert_eq!(result, GitHubRepo {
             owner: "FOSScope".to_string(),
-            name: "Hello-World".to_string(),
-        }));
+            name: "Hello-World-Translations".to_string(),
+        });
+    }
+
+    #[tokio::test]
+    async fn user_fork_candidates() {
+        let mocked_response: serde_json::Value =
+            serde_json::from_str(include_str!("resources/user_forks.json")).unwrap();
+        let template = ResponseTemplate::new(200).set_body_json(&mocked_response);
+        let mock_server = setup_api(template, RequestType::GRAPHQL).await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let result = github.get_user_forks(
+            &GitHubRepo {
+                owner: "octocat".to_string(),
+                name: "hello-world".to_string(),
+            }
+        ).await.unwrap();
+
+        // The user's own fork comes before the fork owned by an organization.
+        assert_eq!(result, vec![
+            GitHubRepo {
+                owner: "FOSScope".to_string(),
+                name: "Hello-World-Translations".to_string(),
+            },
+            GitHubRepo {
+                owner: "FOSScope-Translators".to_string(),
+                name: "Hello-World".to_string(),
+            },
+        ]);
     }
 
     #[tokio::test]
@@ -131,6 +198,7 @@ mod tests {
             serde_json::from_str(include_str!("resources/fork_repo.json")).unwrap();
         let template = ResponseTemplate::new(202).set_body_json(&mocked_response);
         let mock_server = setup_api(template, RequestType::POST).await;
+        setup_fork_branch(&mock_server, 0).await;
         let client = setup_octocrab(&mock_server.uri());
 
         let github = GitHubApi::new("octocat".to_string(), client);
@@ -143,11 +211,52 @@ mod tests {
                 owner: "octocat".to_string(),
                 name: "Hello-World".to_string(),
             },
-        ).await;
+        ).await.unwrap();
 
-        assert_eq!(result, Ok(GitHubRepo {
+        assert_eq!(result, GitHubRepo {
             owner: "FOSScope".to_string(),
             name: "Hello-World".to_string(),
-        }));
+        });
+    }
+
+    #[tokio::test]
+    async fn fork_creation_waits_for_the_fork() {
+        let mocked_response: Repository =
+            serde_json::from_str(include_str!("resources/fork_repo.json")).unwrap();
+        let template = ResponseTemplate::new(202).set_body_json(&mocked_response);
+        let mock_server = setup_api(template, RequestType::POST).await;
+        setup_fork_branch(&mock_server, 2).await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("octocat".to_string(), client)
+            .with_fork_wait_policy(ForkWaitPolicy::new(Duration::from_millis(10), Duration::from_secs(5)));
+        let waited = Mutex::new(0);
+        let result = github.create_fork_with_progress(
+            GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string()),
+            GitHubRepo::new("octocat".to_string(), "Hello-World".to_string()),
+            |_| *waited.lock().unwrap() += 1,
+        ).await.unwrap();
+
+        assert_eq!(result, GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string()));
+        assert_eq!(*waited.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn fork_creation_timeout() {
+        let mocked_response: Repository =
+            serde_json::from_str(include_str!("resources/fork_repo.json")).unwrap();
+        let template = ResponseTemplate::new(202).set_body_json(&mocked_response);
+        let mock_server = setup_api(template, RequestType::POST).await;
+        setup_fork_branch(&mock_server, u64::MAX).await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("octocat".to_string(), client)
+            .with_fork_wait_policy(ForkWaitPolicy::new(Duration::from_millis(10), Duration::from_millis(50)));
+        let result = github.create_fork(
+            GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string()),
+            GitHubRepo::new("octocat".to_string(), "Hello-World".to_string()),
+        ).await;
+
+        assert!(matches!(result, Err(Error::ForkTimeout { ref repo, .. }) if repo == "FOSScope/Hello-World"));
     }
 }
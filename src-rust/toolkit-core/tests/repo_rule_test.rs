@@ -41,11 +41,15 @@ fn setup_octocrab(uri: &str) -> Octocrab {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use octocrab::models::repos::Content;
 
     use fosscopetoolkit_core::apis::GitHubApi;
     use fosscopetoolkit_core::models::GitHubRepo;
-    use fosscopetoolkit_core::models::repo_rule::{Article, get_repo_rule, GitRule};
+    use fosscopetoolkit_core::models::repo_rule::{
+        Action, Article, get_repo_rule, GitRule, PullRequestRule, Variable, VariableType,
+    };
     use fosscopetoolkit_core::models::RepoRule;
 
     use super::*;
@@ -111,4 +115,202 @@ authorInfo: |
 
         assert_eq!(rule.unwrap(), expected);
     }
+
+    #[test]
+    fn pull_request_rule() {
+        let rule: RepoRule = toml::from_str(r#"
+article_template = "{{content}}"
+articles = []
+
+[[actions]]
+name = "select"
+description = "选题"
+target_step = "sources"
+pr_title = "[选题] {{article_title}}"
+labels = ["选题"]
+draft = true
+
+[[actions]]
+name = "translate"
+description = "翻译"
+source_step = "sources"
+target_step = "translated"
+reviewers = ["bob"]
+
+[[actions]]
+name = "publish"
+description = "发布"
+source_step = "translated"
+target_step = "published"
+
+[git]
+base = "main"
+branch_naming = "{{action_name}}/{{type_name}}/{{article_id}}"
+commit_message = "[{{action_desc}}][{{type_desc}}] {{article_title}}"
+pr_body = "Via: {{via}}"
+labels = ["待审核"]
+reviewers = ["alice"]
+
+# The deprecated per-action tables are still applied, below the overrides of the actions.
+[git.actions.translate]
+pr_body = "译者：@{{contributor}}"
+reviewers = ["carol"]
+"#).unwrap();
+
+        let publish_rule = rule.action("publish").unwrap().pull_request_rule(&rule.git);
+        assert_eq!(publish_rule, rule.git.pull_request);
+        assert_eq!(publish_rule.pr_title, None);
+        assert_eq!(publish_rule.draft, None);
+
+        let select_rule = rule.action("select").unwrap().pull_request_rule(&rule.git);
+        assert_eq!(select_rule, PullRequestRule {
+            pr_title: Some("[选题] {{article_title}}".to_string()),
+            pr_body: Some("Via: {{via}}".to_string()),
+            labels: Some(vec!["选题".to_string()]),
+            reviewers: Some(vec!["alice".to_string()]),
+            assignees: None,
+            draft: Some(true),
+        });
+
+        let translate_rule = rule.action("translate").unwrap().pull_request_rule(&rule.git);
+        assert_eq!(translate_rule.pr_body, Some("译者：@{{contributor}}".to_string()));
+        assert_eq!(translate_rule.labels, Some(vec!["待审核".to_string()]));
+        assert_eq!(translate_rule.reviewers, Some(vec!["bob".to_string()]));
+    }
+
+    #[test]
+    fn actions() {
+        let git_rule = GitRule::new("main".to_string(), "{{action_name}}/{{article_id}}".to_string(), "{{article_title}}".to_string());
+        let default_rule = RepoRule::new("{{content}}".to_string(), vec![], git_rule);
+        let names: Vec<&str> = default_rule.actions.iter().map(|action| action.name.as_str()).collect();
+        assert_eq!(names, vec!["select", "translate", "proofread", "publish"]);
+        assert_eq!(default_rule.action("select").unwrap().source_step, None);
+        assert!(default_rule.action("translate").unwrap().moves_article());
+        assert!(!default_rule.action("proofread").unwrap().moves_article());
+        assert_eq!(default_rule.action("review").unwrap_err().kind(), "NotFound");
+
+        let rule: RepoRule = toml::from_str(r#"
+article_template = "{{content}}"
+articles = []
+
+[[actions]]
+name = "write"
+description = "写作"
+target_step = "drafts"
+required_vars = ["article_id"]
+commit_message = "[写作] {{article_id}}"
+
+[[actions]]
+name = "publish"
+description = "发布"
+source_step = "drafts"
+target_step = "published"
+
+[git]
+base = "main"
+branch_naming = "{{action_name}}/{{article_id}}"
+commit_message = "[{{action_desc}}] {{article_title}}"
+"#).unwrap();
+        let write = rule.action("write").unwrap();
+        assert_eq!(*write, Action {
+            required_vars: vec!["article_id".to_string()],
+            commit_message: Some("[写作] {{article_id}}".to_string()),
+            ..Action::new("write".to_string(), "写作".to_string(), None, "drafts".to_string())
+        });
+        assert_eq!(write.commit_message(&rule.git), "[写作] {{article_id}}");
+        assert_eq!(write.branch_naming(&rule.git), "{{action_name}}/{{article_id}}");
+        assert!(rule.action("translate").is_err());
+
+        let publish = rule.action("publish").unwrap();
+        let vars = publish.vars(&HashMap::from([("article_id", "hello".to_string())]));
+        assert_eq!(vars["action_name"], "publish");
+        assert_eq!(vars["action_desc"], "发布");
+        assert_eq!(vars["source_step"], "drafts");
+        assert_eq!(vars["step"], "published");
+        assert!(write.missing_vars(&vars).is_empty());
+        assert_eq!(write.missing_vars(&HashMap::from([("article_id", " ".to_string())])), vec!["article_id"]);
+        assert_eq!(write.check_vars(&HashMap::new()).unwrap_err().kind(), "InvalidInput");
+    }
+
+    #[test]
+    fn variables() {
+        let rule: RepoRule = toml::from_str(r#"
+article_template = "{{content}}"
+articles = []
+
+[[variables]]
+name = "release_date"
+prompt = "请输入文章的发布日期"
+type = "date"
+
+[[variables]]
+name = "cover_image"
+prompt = "请输入封面图片的 URL"
+type = "url"
+required = false
+
+[[variables]]
+name = "tags"
+prompt = "请输入文章的标签"
+type = "list"
+actions = ["select"]
+
+[[variables]]
+name = "category"
+prompt = "请选择文章的分类"
+type = "enum"
+options = ["新闻", "技术"]
+default = "新闻"
+
+[[variables]]
+name = "translator"
+prompt = "请输入译者的 GitHub 用户名"
+pattern = "[A-Za-z0-9-]+"
+actions = ["translate"]
+
+[git]
+base = "main"
+branch_naming = "{{action_name}}/{{article_id}}"
+commit_message = "{{article_title}}"
+"#).unwrap();
+
+        let names = |action: &str| -> Vec<String> {
+            rule.variables_of(action).into_iter().map(|variable| variable.name.clone()).collect()
+        };
+        assert_eq!(names("select"), vec!["release_date", "cover_image", "tags", "category"]);
+        assert_eq!(names("translate"), vec!["release_date", "cover_image", "category", "translator"]);
+        assert_eq!(rule.variable("category").unwrap().r#type, VariableType::Enum);
+        assert_eq!(rule.variable("via"), None);
+
+        let parse = |name: &str, input: &str| rule.variable(name).unwrap().parse(input).map_err(|e| e.kind());
+        assert_eq!(parse("release_date", " 2024-02-29 "), Ok("2024-02-29".to_string()));
+        assert_eq!(parse("release_date", "20240715"), Ok("20240715".to_string()));
+        assert_eq!(parse("release_date", "2023-02-29"), Err("InvalidInput"));
+        assert_eq!(parse("release_date", "2024-7-15"), Err("InvalidInput"));
+        assert_eq!(parse("release_date", "2024/07/15"), Err("InvalidInput"));
+        assert_eq!(parse("release_date", "July 15"), Err("InvalidInput"));
+        assert_eq!(parse("release_date", ""), Err("InvalidInput"));
+        assert_eq!(parse("cover_image", "https://example.com/cover.png"), Ok("https://example.com/cover.png".to_string()));
+        assert_eq!(parse("cover_image", "ftp://example.com/cover.png"), Err("InvalidInput"));
+        assert_eq!(parse("cover_image", ""), Ok(String::new()));
+        assert_eq!(parse("tags", "linux,, open source ,"), Ok("linux, open source".to_string()));
+        assert_eq!(parse("category", "技术"), Ok("技术".to_string()));
+        assert_eq!(parse("category", ""), Ok("新闻".to_string()));
+        assert_eq!(parse("category", "评论"), Err("InvalidInput"));
+        assert_eq!(parse("translator", "octocat"), Ok("octocat".to_string()));
+        assert_eq!(parse("translator", "octo cat"), Err("InvalidInput"));
+
+        let vars = HashMap::from([
+            ("release_date", "2024-07-15".to_string()),
+            ("tags", "linux".to_string()),
+        ]);
+        assert!(rule.check_variables("select", &vars).is_ok());
+        // The translator is only asked for when translating.
+        assert_eq!(rule.check_variables("translate", &vars).unwrap_err().kind(), "InvalidInput");
+        let vars = HashMap::from([("release_date", "2024-13-01".to_string()), ("tags", "linux".to_string())]);
+        assert_eq!(rule.check_variables("select", &vars).unwrap_err().kind(), "InvalidInput");
+
+        let via = Variable::new("via".to_string(), "URL".to_string(), VariableType::Url);
+        assert_eq!(via.parse("example.com").unwrap_err().kind(), "InvalidInput");
+    }
 }
@@ -0,0 +1,211 @@
+#[cfg(test)]
+mod tests {
+    use fosscopetoolkit_core::models::repo_rule::{RuleDiagnostic, Severity, validate_repo_rule};
+
+    const INVALID_RULE: &str = r#"article_template = "{{content}}"
+
+[[articles]]
+type = "news"
+description = "新闻"
+directory = "{{step}}/news"
+
+[[articles]]
+type = "news"
+description = "技术"
+directory = "../{{step}}/tech"
+
+[[articles]]
+type = "talk"
+description = "评论"
+directory = ""
+
+[[actions]]
+name = "select"
+description = "选题"
+target_step = "sources"
+required_vars = ["article_id"]
+commit_message = "[选题] {{article_id}} {{#if}}"
+
+[git]
+base = "main"
+branch_naming = "{{action_name}}/{{type_name}}/{{article_slug}}"
+commit_message = "[{{action_desc}}] {{article_id}}"
+"#;
+
+    /// The diagnostics, as `(line, column, field)`, which is what the maintainers look at first.
+    fn positions(diagnostics: &[RuleDiagnostic]) -> Vec<(Option<usize>, Option<usize>, &str)> {
+        diagnostics.iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.column, diagnostic.field.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn valid_rule() {
+        assert_eq!(validate_repo_rule(include_str!("../resources/sandbox/REPORULE")), vec![]);
+    }
+
+    #[test]
+    fn invalid_toml() {
+        let diagnostics = validate_repo_rule("[git]\nbase = \"main\"\nbranch_naming = \n");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (Some(3), Some(17)));
+
+        // Missing fields are reported as well.
+        let diagnostics = validate_repo_rule("article_template = \"\"\narticles = []\n");
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("git"), "{}", diagnostics[0]);
+    }
+
+    #[test]
+    fn invalid_rule() {
+        let diagnostics = validate_repo_rule(INVALID_RULE);
+        assert_eq!(positions(&diagnostics), vec![
+            (Some(9), Some(8), "articles[1].type"),
+            (Some(11), Some(13), "articles[1].directory"),
+            (Some(16), Some(13), "articles[2].directory"),
+            (Some(23), Some(18), "actions[0].commit_message"),
+            (Some(27), Some(17), "git.branch_naming"),
+        ]);
+        assert!(diagnostics.iter().all(RuleDiagnostic::is_error));
+        assert_eq!(diagnostics[0].message, "the article type `news` is defined more than once");
+        assert_eq!(
+            diagnostics[1].to_string(),
+            "11:13: error: articles[1].directory: the directory is unsafe, as it leaves the repository with `..`",
+        );
+        assert_eq!(diagnostics[2].message, "the directory is empty");
+        assert!(diagnostics[3].message.starts_with("the template does not parse"), "{}", diagnostics[3]);
+        assert_eq!(diagnostics[4].message, "the placeholder `{{article_slug}}` is not provided by any workflow");
+    }
+
+    #[test]
+    fn placeholders() {
+        let rule = r#"article_template = "{{content}}"
+articles = []
+
+[[actions]]
+name = "translate"
+description = "翻译"
+source_step = "sources"
+target_step = "translated"
+required_vars = ["translator"]
+branch_naming = "translate/{{translator}}/{{article_id}}"
+
+[git]
+base = "main"
+branch_naming = "{{action_name}}/{{article_id}}"
+commit_message = "[{{action_desc}}] {{#if article_title}}{{article_title}}{{else}}{{article_id}}{{/if}}"
+pr_body = "{{#each reviewers}}{{name}}{{/each}} {{translator}} {{proofreader}}"
+
+[git.actions.review]
+labels = ["审核"]
+"#;
+        let diagnostics = validate_repo_rule(rule);
+        let summary: Vec<(Severity, &str, &str)> = diagnostics.iter()
+            .map(|diagnostic| (diagnostic.severity, diagnostic.field.as_str(), diagnostic.message.as_str()))
+            .collect();
+        assert_eq!(summary, vec![
+            (Severity::Warning, "git.actions.review", "`[git.actions.review]` is deprecated, move its settings to the `[[actions]]` named `review`"),
+            (Severity::Warning, "git.actions.review", "no action is named `review`"),
+            (Severity::Error, "git.pr_body", "the placeholder `{{reviewers}}` is not provided by any workflow"),
+            (Severity::Error, "git.pr_body", "the placeholder `{{proofreader}}` is not provided by any workflow"),
+        ]);
+        assert_eq!(diagnostics[0].line, None);
+        assert_eq!(diagnostics[2].line, Some(16));
+    }
+
+    #[test]
+    fn variables() {
+        let rule = r#"article_template = "{{content}}"
+articles = []
+
+[[variables]]
+name = "category"
+prompt = "请选择文章的分类"
+type = "enum"
+
+[[variables]]
+name = "category"
+prompt = "请输入文章的分类"
+pattern = "[a-z+"
+
+[[variables]]
+name = "release_date"
+prompt = "请输入文章的发布日期"
+type = "date"
+default = "yesterday"
+actions = ["write"]
+
+[git]
+base = "main"
+branch_naming = "{{action_name}}/{{article_id}}"
+commit_message = "[{{category}}] {{article_title}} {{release_date}}"
+"#;
+        let diagnostics = validate_repo_rule(rule);
+        let summary: Vec<(Option<usize>, &str, Severity)> = diagnostics.iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.field.as_str(), diagnostic.severity))
+            .collect();
+        // The declared variables can be used in the templates.
+        assert_eq!(summary, vec![
+            (Some(7), "variables[0].type", Severity::Error),
+            (Some(10), "variables[1].name", Severity::Error),
+            (Some(12), "variables[1].pattern", Severity::Error),
+            (Some(15), "variables[2].name", Severity::Warning),
+            (Some(18), "variables[2].default", Severity::Error),
+        ]);
+    }
+
+    #[test]
+    fn article_id() {
+        let rule = r#"article_template = "{{content}}"
+articles = []
+
+[article_id]
+pattern = "{{type_name}}/{{publishing_date}}-{{article_id}}"
+max_length = 0
+
+[git]
+base = "main"
+branch_naming = "{{action_name}}/{{article_id}}"
+commit_message = "[{{action_desc}}] {{article_title}}"
+"#;
+        let diagnostics = validate_repo_rule(rule);
+        let summary: Vec<(Option<usize>, &str, &str)> = diagnostics.iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.field.as_str(), diagnostic.message.as_str()))
+            .collect();
+        assert_eq!(summary, vec![
+            (Some(5), "article_id.pattern", "the placeholder `{{article_id}}` is not provided by any workflow"),
+            (Some(5), "article_id.pattern", "the article ID is a file name, so it cannot contain a slash"),
+            (Some(5), "article_id.pattern", "the pattern does not contain `{{slug}}`, so articles with different titles get the same ID"),
+            (Some(6), "article_id.max_length", "the maximum length has to be greater than 0"),
+        ]);
+    }
+
+    #[test]
+    fn partials() {
+        let rule = r#"article_template = "{{content}}\n{{> footer}}"
+articles = []
+
+[partials]
+footer = "via: {{via}}"
+signature = "{{#if}}"
+
+[git]
+base = "main"
+branch_naming = "{{action_name}}/{{slugify article_id}}"
+commit_message = "[{{action_desc}}] {{article_title}}"
+pr_body = "{{> signature}} {{> reviewers}} {{join tags \" / \"}}"
+"#;
+        let diagnostics = validate_repo_rule(rule);
+        let summary: Vec<(Option<usize>, &str, &str)> = diagnostics.iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.field.as_str(), diagnostic.message.as_str()))
+            .collect();
+        // The helpers are not placeholders, but their parameters are.
+        assert_eq!(summary[1..], [
+            (Some(12), "git.pr_body", "the partial `reviewers` is not defined"),
+            (Some(12), "git.pr_body", "the placeholder `{{tags}}` is not provided by any workflow"),
+        ]);
+        assert_eq!((summary[0].0, summary[0].1), (Some(6), "partials.signature"));
+        assert!(summary[0].2.starts_with("the template does not parse"), "{}", diagnostics[0]);
+    }
+}
@@ -0,0 +1,161 @@
+use octocrab::Octocrab;
+use serde_json::json;
+use wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+use mock_error::setup_error_handler;
+
+mod mock_error;
+
+fn resource(json: &str) -> serde_json::Value {
+    serde_json::from_str(json).unwrap()
+}
+
+// This function sets up the mock server with the trees and blobs of the repository.
+async fn setup_api(truncated: bool) -> MockServer {
+    let mock_server = MockServer::start().await;
+
+    let mocked_path = "/repos/FOSScope/Hello-World/git/trees/main";
+
+    let mut tree = resource(include_str!("resources/git_tree.json"));
+    tree["truncated"] = json!(truncated);
+    Mock::given(method("GET"))
+        .and(path(mocked_path))
+        .and(query_param("recursive", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(tree))
+        .mount(&mock_server)
+        .await;
+    // The subtrees listed one level at a time, with paths relative to each subtree.
+    Mock::given(method("GET"))
+        .and(path("/repos/FOSScope/Hello-World/git/trees/9fb037999f264ba9a7fc6274d15fa3ae2ab98312"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sha": "9fb037999f264ba9a7fc6274d15fa3ae2ab98312",
+            "url": "https://api.github.com/repos/FOSScope/Hello-World/git/trees/9fb037999f264ba9a7fc6274d15fa3ae2ab98312",
+            "tree": [
+                { "path": "README.md", "mode": "100644", "type": "blob", "size": 13, "sha": "980a0d5f19a64b4b30a87d4206aade58726b60e3" },
+                { "path": "sources", "mode": "040000", "type": "tree", "sha": "f484d249c660418515fb01c2b9662073663c242e" },
+            ],
+            "truncated": false,
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/FOSScope/Hello-World/git/trees/f484d249c660418515fb01c2b9662073663c242e"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sha": "f484d249c660418515fb01c2b9662073663c242e",
+            "url": "https://api.github.com/repos/FOSScope/Hello-World/git/trees/f484d249c660418515fb01c2b9662073663c242e",
+            "tree": [
+                { "path": "news", "mode": "040000", "type": "tree", "sha": "5f7a0a8a2d4e0b1e4d9a5c1c4d7e0a5b8f3d2c1b" },
+            ],
+            "truncated": false,
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/FOSScope/Hello-World/git/trees/5f7a0a8a2d4e0b1e4d9a5c1c4d7e0a5b8f3d2c1b"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sha": "5f7a0a8a2d4e0b1e4d9a5c1c4d7e0a5b8f3d2c1b",
+            "url": "https://api.github.com/repos/FOSScope/Hello-World/git/trees/5f7a0a8a2d4e0b1e4d9a5c1c4d7e0a5b8f3d2c1b",
+            "tree": [
+                { "path": "20240715-hello.md", "mode": "100644", "type": "blob", "size": 132, "sha": "45b983be36b73c0788dc9cbcb76cbb80fc7bb057" },
+            ],
+            "truncated": false,
+        })))
+        .mount(&mock_server)
+        .await;
+    // "Hello World!\n" in base64, wrapped like GitHub does.
+    Mock::given(method("GET"))
+        .and(path("/repos/FOSScope/Hello-World/git/blobs/980a0d5f19a64b4b30a87d4206aade58726b60e3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "sha": "980a0d5f19a64b4b30a87d4206aade58726b60e3",
+            "content": "SGVsbG8g\nV29ybGQhCg==\n",
+            "encoding": "base64",
+            "size": 13,
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/FOSScope/Hello-World/git/blobs/45b983be36b73c0788dc9cbcb76cbb80fc7bb057"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "message": "Not Found",
+            "documentation_url": "https://docs.github.com/rest/git/blobs#get-a-blob",
+        })))
+        .mount(&mock_server)
+        .await;
+    setup_error_handler(
+        &mock_server,
+        &format!("GET on {mocked_path} was not received"),
+    ).await;
+
+    mock_server
+}
+
+// This function sets up the Octocrab client with the base URI of the mock server.
+fn setup_octocrab(uri: &str) -> Octocrab {
+    Octocrab::builder().base_uri(uri).unwrap().build().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use fosscopetoolkit_core::apis::GitHubApi;
+    use fosscopetoolkit_core::Error;
+    use fosscopetoolkit_core::models::{GitHubRepo, TreeEntryKind};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn list_tree_recursively() {
+        let mock_server = setup_api(false).await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let result = github.get_tree(
+            &GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string()),
+            "main",
+            true,
+        ).await.unwrap();
+
+        assert_eq!(result.sha, "9fb037999f264ba9a7fc6274d15fa3ae2ab98312");
+        assert_eq!(result.entries.len(), 4);
+        assert_eq!(result.get("sources/news").unwrap().kind, TreeEntryKind::Tree);
+        let articles: Vec<&str> = result.files_in("sources/").map(|entry| entry.path.as_str()).collect();
+        assert_eq!(articles, vec!["sources/news/20240715-hello.md"]);
+    }
+
+    #[tokio::test]
+    async fn walk_truncated_tree() {
+        let mock_server = setup_api(true).await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let result = github.get_tree(
+            &GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string()),
+            "main",
+            true,
+        ).await.unwrap();
+
+        let mut paths: Vec<&str> = result.entries.iter().map(|entry| entry.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["README.md", "sources", "sources/news", "sources/news/20240715-hello.md"]);
+    }
+
+    #[tokio::test]
+    async fn fetch_blobs_on_demand() {
+        let mock_server = setup_api(false).await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let repo = GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string());
+        let tree = github.get_tree(&repo, "main", true).await.unwrap();
+        let result = github.get_blobs(&repo, tree.files(), 4).await;
+
+        // A missing blob does not fail the other files.
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].0, "README.md");
+        assert_eq!(result[0].1.as_ref().unwrap(), b"Hello World!\n");
+        assert_eq!(result[1].0, "sources/news/20240715-hello.md");
+        assert!(matches!(result[1].1, Err(Error::NotFound(_))));
+    }
+}
@@ -44,6 +44,7 @@ mod tests {
     use octocrab::models::Repository;
 
     use fosscopetoolkit_core::apis::GitHubApi;
+    use fosscopetoolkit_core::Error;
     use fosscopetoolkit_core::models::GitHubRepo;
 
     use super::*;
@@ -54,6 +55,15 @@ mod tests {
             serde_json::from_str(include_str!("resources/forked_repo.json")).unwrap();
         let template = ResponseTemplate::new(200).set_body_json(&mocked_response);
         let mock_server = setup_api(template).await;
+        Mock::given(method("GET"))
+            .and(path("/repos/someone/Hello-World"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "message": "Not Found",
+                "documentation_url": "https://docs.github.com/rest/repos/repos#get-a-repository",
+            })))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
         let client = setup_octocrab(&mock_server.uri());
 
         let github = GitHubApi::new("octocat".to_string(), client);
@@ -68,7 +78,7 @@ mod tests {
             },
         ).await;
 
-        assert_eq!(result, Err("Repository does not exist"));
+        assert!(matches!(result, Err(Error::NotFound(_))));
     }
 
     #[tokio::test]
@@ -91,7 +101,7 @@ mod tests {
             },
         ).await;
 
-        assert_eq!(result, Err("Repository is not a fork"));
+        assert!(matches!(result, Err(Error::NotAFork(_))));
     }
 
     #[tokio::test]
@@ -114,7 +124,7 @@ mod tests {
             },
         ).await;
 
-        assert_eq!(result, Err("Repository is not a fork of the upstream repository"));
+        assert!(matches!(result, Err(Error::WrongParent { .. })));
     }
 
     #[tokio::test]
@@ -137,6 +147,6 @@ mod tests {
             },
         ).await;
 
-        assert_eq!(result, Ok(()));
+        assert!(result.is_ok());
     }
 }
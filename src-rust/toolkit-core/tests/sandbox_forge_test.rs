@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use fosscopetoolkit_core::apis::{Forge, SandboxForge};
+    use fosscopetoolkit_core::models::{FileChange, GitHubRepo, PullRequestState};
+    use fosscopetoolkit_core::models::repo_rule::get_repo_rule;
+    use fosscopetoolkit_core::workflow::translate::select::submit;
+
+    fn upstream() -> GitHubRepo {
+        GitHubRepo::new("FOSScope".to_string(), "TranslateProject".to_string())
+    }
+
+    fn fork() -> GitHubRepo {
+        GitHubRepo::new("sandbox".to_string(), "TranslateProject".to_string())
+    }
+
+    fn vars() -> HashMap<&'static str, String> {
+        HashMap::from([
+            ("via", "https://example.com/hello".to_string()),
+            ("selector", "sandbox".to_string()),
+            ("type_name", "news".to_string()),
+            ("type_desc", "新闻".to_string()),
+            ("article_title", "Hello".to_string()),
+            ("article_id", "20240715-hello".to_string()),
+        ])
+    }
+
+    #[tokio::test]
+    async fn forks() {
+        let sandbox = SandboxForge::seeded("sandbox".to_string());
+
+        assert!(sandbox.get_user_forks(&upstream()).await.unwrap().is_empty());
+        assert_eq!(sandbox.validate_repo(&fork(), &upstream()).await.unwrap_err().kind(), "NotFound");
+        assert_eq!(sandbox.validate_repo(&upstream(), &upstream()).await.unwrap_err().kind(), "NotAFork");
+
+        let created = sandbox.create_fork(&fork(), &upstream()).await.unwrap();
+        assert_eq!(created, fork());
+        sandbox.validate_repo(&fork(), &upstream()).await.unwrap();
+        assert_eq!(sandbox.get_user_forks(&upstream()).await.unwrap(), vec![fork()]);
+        // Forking again returns the existing fork.
+        assert_eq!(sandbox.fork_repo(&fork(), &upstream()).unwrap(), fork());
+        assert_eq!(sandbox.get_user_forks(&upstream()).await.unwrap(), vec![fork()]);
+
+        let articles = GitHubRepo::new("FOSScope".to_string(), "Articles".to_string());
+        assert_eq!(sandbox.validate_repo(&fork(), &articles).await.unwrap_err().kind(), "WrongParent");
+    }
+
+    #[tokio::test]
+    async fn files() {
+        let sandbox = SandboxForge::seeded("sandbox".to_string());
+        sandbox.create_fork(&fork(), &upstream()).await.unwrap();
+        sandbox.create_reference_from(&fork(), "edit", &upstream(), "main").await.unwrap();
+        assert_eq!(
+            sandbox.create_reference_from(&fork(), "edit", &upstream(), "main").await.unwrap_err().kind(),
+            "Conflict",
+        );
+
+        sandbox.create_file(&fork(), "edit", "hello.md", "Hello", "Add hello").await.unwrap();
+        let sha = sandbox.get_file_sha(&fork(), "edit", "hello.md").await.unwrap();
+        // The SHA is saved with the sandbox, so it is the stable SHA-256 of the Git blob.
+        assert_eq!(sha, "1301800ffa9c48e2a82cbfda7fe9d17d5605cfa5df7c673639c44d8fcc244a71");
+        sandbox.update_file(&fork(), "edit", "hello.md", "Hello, World", &sha, "Update hello").await.unwrap();
+        // The file has changed since the SHA was read.
+        assert_eq!(
+            sandbox.update_file(&fork(), "edit", "hello.md", "Hi", &sha, "Update hello").await.unwrap_err().kind(),
+            "Conflict",
+        );
+
+        sandbox.commit_files(&fork(), "edit", &[
+            FileChange::rename("hello.md", "news/hello.md"),
+            FileChange::delete("README.md"),
+        ], "Move hello").await.unwrap();
+        assert_eq!(sandbox.get_file_sha(&fork(), "edit", "hello.md").await.unwrap_err().kind(), "NotFound");
+        assert_eq!(sandbox.get_file_sha(&fork(), "edit", "README.md").await.unwrap_err().kind(), "NotFound");
+        sandbox.get_file_sha(&fork(), "edit", "news/hello.md").await.unwrap();
+        // A failed commit leaves the branch untouched.
+        let head = sandbox.get_branch_sha(&fork(), "edit").await.unwrap();
+        assert_eq!(
+            sandbox.commit_files(&fork(), "edit", &[
+                FileChange::write("new.md", "New"),
+                FileChange::delete("missing.md"),
+            ], "Broken").await.unwrap_err().kind(),
+            "NotFound",
+        );
+        assert_eq!(sandbox.get_branch_sha(&fork(), "edit").await.unwrap(), head);
+    }
+
+    #[tokio::test]
+    async fn select_workflow() {
+        let sandbox = SandboxForge::seeded("sandbox".to_string());
+        sandbox.create_fork(&fork(), &upstream()).await.unwrap();
+
+        let repo_rule = get_repo_rule(&upstream(), &sandbox).await.unwrap();
+        let news = repo_rule.articles.iter().find(|article| article.r#type == "news").unwrap();
+        submit::commit(
+            &sandbox, &upstream(), &fork(), "20240715-hello.md", "Hello", news, &repo_rule, &vars(),
+        ).await.unwrap();
+        let pr = submit::create_pr(&sandbox, &upstream(), &fork(), &repo_rule, &vars()).await.unwrap();
+        assert_eq!(pr.number, 1);
+        assert_eq!(pr.head, "select/news/20240715-hello");
+        assert_eq!(pr.url, "https://sandbox.fosscope.invalid/FOSScope/TranslateProject/pull/1");
+        assert_eq!(sandbox.list_user_pull_requests(&upstream()).await.unwrap(), vec![pr.clone()]);
+
+        // Opening the same pull request again is rejected, just like on GitHub.
+        assert_eq!(
+            sandbox.create_pull_request(
+                &upstream(), &fork(), "Hello", "", "select/news/20240715-hello", "main", false,
+            ).await.unwrap_err().kind(),
+            "Conflict",
+        );
+        // Submitting again resumes the submission with the open pull request.
+        submit::commit(
+            &sandbox, &upstream(), &fork(), "20240715-hello.md", "Hello", news, &repo_rule, &vars(),
+        ).await.unwrap();
+        assert_eq!(submit::create_pr(&sandbox, &upstream(), &fork(), &repo_rule, &vars()).await.unwrap(), pr);
+
+        // Once merged, the article is in the upstream repository, and the fork is behind.
+        sandbox.merge_pull_request(&upstream(), 1).unwrap();
+        assert_eq!(sandbox.get_pull_request(&upstream(), 1).unwrap().state, PullRequestState::Merged);
+        assert!(sandbox.list_user_pull_requests(&upstream()).await.unwrap().is_empty());
+        assert_eq!(
+            sandbox.get_file_content(&upstream(), "sources/news/20240715-hello.md").await.unwrap(),
+            "Hello",
+        );
+        let status = sandbox.fork_status(&fork(), &upstream(), "main").await.unwrap();
+        assert_eq!((status.ahead_by, status.behind_by), (0, 1));
+        sandbox.sync_fork(&fork(), "main").await.unwrap();
+        assert!(sandbox.fork_status(&fork(), &upstream(), "main").await.unwrap().is_up_to_date());
+    }
+
+    #[tokio::test]
+    async fn file_backed() {
+        let path = std::env::temp_dir().join(format!("fosscope-toolkit-sandbox-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let sandbox = SandboxForge::open("sandbox".to_string(), &path).unwrap();
+        sandbox.create_fork(&fork(), &upstream()).await.unwrap();
+        sandbox.create_reference_from(&fork(), "edit", &upstream(), "main").await.unwrap();
+        sandbox.commit_files(&fork(), "edit", &[FileChange::write("image.png", vec![0x89, 0x50, 0x4e, 0x47])], "Add image")
+            .await.unwrap();
+        let head = sandbox.get_branch_sha(&fork(), "edit").await.unwrap();
+        drop(sandbox);
+
+        // The progress is kept across runs.
+        let sandbox = SandboxForge::open("sandbox".to_string(), &path).unwrap();
+        sandbox.validate_repo(&fork(), &upstream()).await.unwrap();
+        assert_eq!(sandbox.get_branch_sha(&fork(), "edit").await.unwrap(), head);
+        sandbox.get_file_sha(&fork(), "edit", "image.png").await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,174 @@
+use octocrab::Octocrab;
+use serde_json::json;
+use wiremock::{
+    matchers::{body_partial_json, method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+use mock_error::setup_error_handler;
+
+mod mock_error;
+
+const BRANCH_PATH: &str = "/repos/FOSScope/Hello-World/git/ref/heads/select/news/hello-world";
+const ARTICLE_PATH: &str = "/repos/FOSScope/Hello-World/contents/sources/news/hello-world.md";
+
+// This function sets up the mock server with the upstream base branch, and a fork without the contribution branch.
+async fn setup_api() -> MockServer {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path(BRANCH_PATH))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "message": "Not Found",
+            "documentation_url": "https://docs.github.com/rest/git/refs#get-a-reference",
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/octocat/Hello-World/git/ref/heads/main"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::from_str::<serde_json::Value>(include_str!("resources/reference.json")).unwrap())
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/repos/FOSScope/Hello-World/git/refs"))
+        .and(body_partial_json(json!({ "ref": "refs/heads/select/news/hello-world" })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "ref": "refs/heads/select/news/hello-world",
+            "node_id": "MDM6UmVmcmVmcy9oZWFkcy9zZWxlY3Q=",
+            "url": "https://api.github.com/repos/FOSScope/Hello-World/git/refs/heads/select/news/hello-world",
+            "object": {
+                "type": "commit",
+                "sha": "aa218f56b14c9653891f9e74264a383fa43fefbd",
+                "url": "https://api.github.com/repos/FOSScope/Hello-World/git/commits/aa218f56b14c9653891f9e74264a383fa43fefbd"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(ARTICLE_PATH))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "message": "Not Found",
+            "documentation_url": "https://docs.github.com/rest/repos/contents#get-repository-content",
+        })))
+        .mount(&mock_server)
+        .await;
+    setup_error_handler(
+        &mock_server,
+        &format!("GET on {BRANCH_PATH} was not received"),
+    ).await;
+
+    mock_server
+}
+
+// This function sets up the Octocrab client with the base URI of the mock server.
+fn setup_octocrab(uri: &str) -> Octocrab {
+    Octocrab::builder().base_uri(uri).unwrap().build().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use fosscopetoolkit_core::apis::{GitHubApi, RetryPolicy};
+    use fosscopetoolkit_core::models::{GitHubRepo, RepoRule};
+    use fosscopetoolkit_core::models::repo_rule::{Article, GitRule};
+    use fosscopetoolkit_core::workflow::translate::select::submit;
+
+    use super::*;
+
+    fn upstream() -> GitHubRepo {
+        GitHubRepo::new("octocat".to_string(), "Hello-World".to_string())
+    }
+
+    fn fork() -> GitHubRepo {
+        GitHubRepo::new("FOSScope".to_string(), "Hello-World".to_string())
+    }
+
+    fn repo_rule() -> RepoRule {
+        let news = Article::new("news".to_string(), "新闻".to_string(), "{{step}}/news".to_string(), None);
+        let mut git_rule = GitRule::new(
+            "main".to_string(),
+            "{{action_name}}/{{type_name}}/{{article_id}}".to_string(),
+            "[{{action_desc}}][{{type_desc}}] {{article_title}}".to_string(),
+        );
+        git_rule.pull_request.labels = Some(vec!["选题".to_string()]);
+        RepoRule::new("{{content}}".to_string(), vec![news], git_rule)
+    }
+
+    fn vars() -> HashMap<&'static str, String> {
+        HashMap::from([
+            ("type_name", "news".to_string()),
+            ("type_desc", "新闻".to_string()),
+            ("article_title", "Hello World".to_string()),
+            ("article_id", "hello-world".to_string()),
+        ])
+    }
+
+    #[tokio::test]
+    async fn roll_back_branch_on_failed_commit() {
+        let mock_server = setup_api().await;
+        Mock::given(method("PUT"))
+            .and(path(ARTICLE_PATH))
+            .respond_with(ResponseTemplate::new(422).set_body_json(json!({
+                "message": "Invalid request.",
+                "documentation_url": "https://docs.github.com/rest/repos/contents#create-or-update-file-contents",
+            })))
+            .mount(&mock_server)
+            .await;
+        // The branch created for the submission is deleted again.
+        Mock::given(method("DELETE"))
+            .and(path("/repos/FOSScope/Hello-World/git/refs/heads/select/news/hello-world"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client).with_retry_policy(RetryPolicy::none());
+        let repo_rule = repo_rule();
+        let result = submit::commit(
+            &github, &upstream(), &fork(), "hello-world.md", "Hello", &repo_rule.articles[0], &repo_rule, &vars(),
+        ).await;
+
+        assert_eq!(result.unwrap_err().kind(), "GitHub");
+    }
+
+    #[tokio::test]
+    async fn resume_with_open_pull_request() {
+        let mock_server = setup_api().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/octocat/Hello-World/pulls"))
+            .and(query_param("state", "all"))
+            .and(query_param("head", "FOSScope:select/news/hello-world"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                serde_json::from_str::<serde_json::Value>(include_str!("resources/pull_request.json")).unwrap(),
+            ])))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        // No other pull request is opened, but the labels are added to the open one again.
+        Mock::given(method("POST"))
+            .and(path("/repos/octocat/Hello-World/pulls"))
+            .respond_with(ResponseTemplate::new(422))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/repos/octocat/Hello-World/issues/1347/labels"))
+            .and(body_partial_json(json!({ "labels": ["选题"] })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("FOSScope".to_string(), client);
+        let pr = submit::create_pr(&github, &upstream(), &fork(), &repo_rule(), &vars()).await.unwrap();
+
+        assert_eq!(pr.number, 1347);
+        assert_eq!(pr.head, "select/news/hello-world");
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use fosscopetoolkit_core::apis::{Forge, SandboxForge};
+    use fosscopetoolkit_core::models::GitHubRepo;
+    use fosscopetoolkit_core::models::repo_rule::get_repo_rule;
+    use fosscopetoolkit_core::template::TemplateRenderer;
+    use fosscopetoolkit_core::workflow::translate::select::submit;
+
+    fn vars() -> HashMap<&'static str, String> {
+        HashMap::from([
+            ("article_title", "Rust & Linux: <The Future>".to_string()),
+            ("publishing_date", "20240715".to_string()),
+            ("tags", "linux, , rust".to_string()),
+            ("translator", " ".to_string()),
+            ("type_name", "news".to_string()),
+        ])
+    }
+
+    #[test]
+    fn no_escape() {
+        let renderer = TemplateRenderer::new();
+        assert_eq!(
+            renderer.render("commit_message", "[选题] {{article_title}}", &vars()).unwrap(),
+            "[选题] Rust & Linux: <The Future>",
+        );
+        // The missing placeholders render as nothing, unless they are kept.
+        assert_eq!(renderer.render("article_template", "{{cover_image}}|", &vars()).unwrap(), "|");
+        let renderer = TemplateRenderer::new().keep_missing_placeholders();
+        assert_eq!(
+            renderer.render("article_template", "{{cover_image}} {{lowercase type_name}}", &vars()).unwrap(),
+            "{{cover_image}} news",
+        );
+    }
+
+    #[test]
+    fn helpers() {
+        let renderer = TemplateRenderer::new();
+        let render = |template: &str| renderer.render("template", template, &vars()).unwrap();
+        assert_eq!(render("{{slugify article_title}}"), "rust-linux-the-future");
+        assert_eq!(render("{{slugify \"开源软件\" \"pinyin\"}}"), "kai-yuan-ruan-jian");
+        assert_eq!(render("{{date publishing_date}}"), "2024-07-15");
+        assert_eq!(render("{{date publishing_date \"%Y/%m/%d\"}}"), "2024/07/15");
+        assert_eq!(render("{{date \"2024-07-15T08:00:00+08:00\" \"%Y%m%d\"}}"), "20240715");
+        assert_eq!(render("{{lowercase \"README\"}}"), "readme");
+        assert_eq!(render("{{join tags \" / \"}}"), "linux / rust");
+        assert_eq!(render("{{join tags}}"), "linux, rust");
+        assert_eq!(render("{{default translator \"待认领\"}} {{default type_name \"其他\"}}"), "待认领 news");
+
+        let error = renderer.render("article_id.pattern", "{{date article_title}}", &vars()).unwrap_err();
+        assert_eq!(error.kind(), "TemplateRender");
+        assert!(error.to_string().starts_with("Failed to render the template article_id.pattern: "), "{}", error);
+        assert_eq!(renderer.render("template", "{{date publishing_date \"%Q\"}}", &vars()).unwrap_err().kind(), "TemplateRender");
+        assert_eq!(renderer.render("template", "{{slugify article_title \"ascii\"}}", &vars()).unwrap_err().kind(), "TemplateRender");
+        assert_eq!(renderer.render("template", "{{#if}}", &vars()).unwrap_err().kind(), "TemplateRender");
+    }
+
+    #[test]
+    fn partials() {
+        let mut renderer = TemplateRenderer::new();
+        renderer.register_partial("signature", "译者：{{default translator \"待认领\"}}").unwrap();
+        assert_eq!(
+            renderer.render("pr_body", "{{article_title}}\n{{> signature}}", &vars()).unwrap(),
+            "Rust & Linux: <The Future>\n译者：待认领",
+        );
+        assert_eq!(renderer.render("pr_body", "{{> missing}}", &vars()).unwrap_err().kind(), "TemplateRender");
+        assert_eq!(renderer.register_partial("broken", "{{#if}}").unwrap_err().kind(), "Parse");
+    }
+
+    #[test]
+    fn render_path() {
+        let renderer = TemplateRenderer::new();
+        assert_eq!(renderer.render_path("directory", " sources/{{type_name}}/ ", &vars()).unwrap(), "sources/news");
+        for template in ["../{{type_name}}", "/{{type_name}}", "sources/.git", "C:/{{type_name}}", "sources\\news", "{{translator}}"] {
+            let error = renderer.render_path("directory", template, &vars()).unwrap_err();
+            assert_eq!(error.kind(), "InvalidInput", "{}", template);
+        }
+        // A value can make a safe template unsafe.
+        let vars = HashMap::from([("type_name", "../../etc".to_string())]);
+        assert_eq!(renderer.render_path("directory", "sources/{{type_name}}", &vars).unwrap_err().kind(), "InvalidInput");
+    }
+
+    #[tokio::test]
+    async fn submission_is_not_escaped() {
+        let upstream = GitHubRepo::new("FOSScope".to_string(), "TranslateProject".to_string());
+        let fork = GitHubRepo::new("sandbox".to_string(), "TranslateProject".to_string());
+        let sandbox = SandboxForge::seeded("sandbox".to_string());
+        sandbox.create_fork(&fork, &upstream).await.unwrap();
+        let repo_rule = get_repo_rule(&upstream, &sandbox).await.unwrap();
+
+        let vars = HashMap::from([
+            ("contributor", "sandbox".to_string()),
+            ("via", "https://example.com/rust-linux?a=1&b=2".to_string()),
+            ("selector", "sandbox".to_string()),
+            ("type_name", "news".to_string()),
+            ("type_desc", "新闻".to_string()),
+            ("article_title", "Rust & Linux".to_string()),
+            ("article_id", "20240715-rust-linux".to_string()),
+        ]);
+        submit::commit(
+            &sandbox, &upstream, &fork, "20240715-rust-linux.md", "Rust & Linux", &repo_rule.articles[0], &repo_rule, &vars,
+        ).await.unwrap();
+        let pr = submit::create_pr(&sandbox, &upstream, &fork, &repo_rule, &vars).await.unwrap();
+        // The pull request title is rendered from the commit message template, like the commit message.
+        assert_eq!(pr.title, "[选题][新闻] Rust & Linux");
+    }
+}
@@ -44,6 +44,8 @@ mod tests {
     use octocrab::models::UserProfile;
 
     use fosscopetoolkit_core::apis::GitHubApi;
+    use fosscopetoolkit_core::Error;
+    use fosscopetoolkit_core::models::{TokenCapabilities, TokenCapability};
 
     use super::*;
 
@@ -73,4 +75,76 @@ mod tests {
         // The username and the username from the mocked response should NOT match.
         assert!(!github.verify_user().await);
     }
+
+    #[tokio::test]
+    async fn user_unauthorized() {
+        let template = ResponseTemplate::new(401).set_body_json(serde_json::json!({
+            "message": "Bad credentials",
+            "documentation_url": "https://docs.github.com/rest",
+        }));
+        let mock_server = setup_api(template).await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("octocat".to_string(), client);
+        // An invalid token should be reported as unauthorized.
+        assert!(matches!(github.get_user().await, Err(Error::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn token_missing_capabilities() {
+        let mocked_response: UserProfile =
+            serde_json::from_str(include_str!("resources/user.json")).unwrap();
+        let template = ResponseTemplate::new(200)
+            .set_body_json(&mocked_response)
+            .insert_header("x-oauth-scopes", "public_repo, read:org");
+        let mock_server = setup_api(template).await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("octocat".to_string(), client);
+        // `public_repo` allows everything but updating workflows.
+        assert_eq!(github.check_capabilities().await.unwrap(), TokenCapabilities::Missing(vec![TokenCapability::Workflow]));
+    }
+
+    #[tokio::test]
+    async fn token_has_capabilities() {
+        let mocked_response: UserProfile =
+            serde_json::from_str(include_str!("resources/user.json")).unwrap();
+        let template = ResponseTemplate::new(200)
+            .set_body_json(&mocked_response)
+            .insert_header("x-oauth-scopes", "repo, workflow");
+        let mock_server = setup_api(template).await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("octocat".to_string(), client);
+        assert_eq!(github.check_capabilities().await.unwrap(), TokenCapabilities::Missing(Vec::new()));
+    }
+
+    #[tokio::test]
+    async fn token_without_scopes() {
+        let mocked_response: UserProfile =
+            serde_json::from_str(include_str!("resources/user.json")).unwrap();
+        let template = ResponseTemplate::new(200)
+            .set_body_json(&mocked_response)
+            .insert_header("x-oauth-scopes", "");
+        let mock_server = setup_api(template).await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("octocat".to_string(), client);
+        assert_eq!(github.check_capabilities().await.unwrap(), TokenCapabilities::Missing(TokenCapability::ALL.to_vec()));
+    }
+
+    #[tokio::test]
+    async fn fine_grained_token_capabilities() {
+        let mocked_response: UserProfile =
+            serde_json::from_str(include_str!("resources/user.json")).unwrap();
+        // Fine-grained tokens do not send the `X-OAuth-Scopes` header.
+        let template = ResponseTemplate::new(200).set_body_json(&mocked_response);
+        let mock_server = setup_api(template).await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        let github = GitHubApi::new("octocat".to_string(), client);
+        assert_eq!(github.token_capabilities(), None);
+        assert_eq!(github.check_capabilities().await.unwrap(), TokenCapabilities::Unknown);
+        assert_eq!(github.token_capabilities(), Some(TokenCapabilities::Unknown));
+    }
 }